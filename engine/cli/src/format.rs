@@ -0,0 +1,74 @@
+use std::{fmt, fs, io, path::Path};
+
+use game_of_life_core::{
+    format::{self, cells, detect_format, life105, life106, macrocell, rle, Format, Pattern},
+    rule::Rule,
+    table,
+    universe::Universe,
+};
+
+#[derive(Debug)]
+pub enum CliErr {
+    Io(String, io::Error),
+    UnsupportedFormat(String),
+    InvalidPattern(String, String),
+    InvalidRule(String),
+    TooFar(String),
+}
+
+impl fmt::Display for CliErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliErr::Io(path, e) => write!(f, "{path}: {e}"),
+            CliErr::UnsupportedFormat(path) => {
+                write!(
+                    f,
+                    "{path}: unsupported format, expected a \".rle\", \".cells\", \".lif\" or \".mc\" file!"
+                )
+            }
+            CliErr::InvalidPattern(path, e) => write!(f, "{path}: {e}"),
+            CliErr::InvalidRule(e) => write!(f, "{e}"),
+            CliErr::TooFar(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+pub fn format_of(path: &Path) -> Result<Format, CliErr> {
+    let extension = path.extension().and_then(|e| e.to_str()).map(|e| e.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("rle") => Ok(Format::Rle),
+        Some("cells") => Ok(Format::Cells),
+        Some("lif" | "life") => Ok(Format::Life106),
+        Some("mc") => Ok(Format::Macrocell),
+        _ => Err(CliErr::UnsupportedFormat(path.display().to_string())),
+    }
+}
+
+pub fn read(path: &Path) -> Result<Pattern, CliErr> {
+    let format = format_of(path)?;
+    let text = fs::read_to_string(path).map_err(|e| CliErr::Io(path.display().to_string(), e))?;
+    let invalid = |e: String| CliErr::InvalidPattern(path.display().to_string(), e);
+    match format {
+        Format::Rle => rle::from_string(&text).map_err(|e| invalid(e.to_string())),
+        Format::Cells => cells::from_string(&text).map_err(|e| invalid(e.to_string())),
+        Format::Life105 | Format::Life106 => match detect_format(&text) {
+            Format::Life105 => life105::from_string(&text).map_err(|e| invalid(e.to_string())),
+            _ => life106::from_string(&text).map_err(|e| invalid(e.to_string())),
+        },
+        Format::Macrocell => macrocell::from_string(&text, macrocell::DEFAULT_CELL_LIMIT)
+            .map_err(|e| invalid(e.to_string())),
+    }
+}
+
+/// Reads a Golly `.rule` file, with its path in the errors.
+pub fn read_table(path: &Path) -> Result<Rule, CliErr> {
+    let text = fs::read_to_string(path).map_err(|e| CliErr::Io(path.display().to_string(), e))?;
+    table::from_string(&text)
+        .map(Rule::from)
+        .map_err(|e| CliErr::InvalidRule(format!("{}: {e}", path.display())))
+}
+
+pub fn write(path: &Path, u: &Universe, rule: &Rule) -> Result<(), CliErr> {
+    let text = format::to_string(format_of(path)?, u, rule);
+    fs::write(path, text).map_err(|e| CliErr::Io(path.display().to_string(), e))
+}
@@ -0,0 +1,401 @@
+use std::{collections::HashMap, path::PathBuf, process::ExitCode, time::Instant};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use game_of_life_core::{
+    analysis::analyze,
+    cell::State,
+    evolution::{build_evolution, Algorithm},
+    format::rle,
+    geometry::coordinate::CartesianP,
+    preset::{get_preset, get_preset_groups, get_preset_rule},
+    rule::{self, Rule, RuleKind},
+    universe::{get_bounds, get_population, iterate, Universe},
+};
+
+use format::CliErr;
+
+mod format;
+
+#[derive(Parser)]
+#[command(name = "gol", about = "Evolve, convert and inspect Game of Life patterns")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum AlgorithmArg {
+    Sparse,
+    Hashlife,
+    Tiled,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Evolve a pattern and write the result, to stdout if no output is given
+    Run {
+        input: PathBuf,
+        #[arg(long, default_value_t = 1)]
+        gens: u64,
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Overrides the rule of the pattern, either in a notation such as
+        /// "B36/S23" or as the path of a Golly ".rule" file
+        #[arg(long)]
+        rule: Option<String>,
+        #[arg(long, value_enum, default_value_t = AlgorithmArg::Hashlife)]
+        algorithm: AlgorithmArg,
+    },
+    /// Convert a pattern between formats, picked by the file extensions
+    Convert { input: PathBuf, output: PathBuf },
+    /// Print the population, the bounding box, the period and the speed of a pattern
+    Info {
+        input: PathBuf,
+        #[arg(long, default_value_t = 1000)]
+        max_gens: u64,
+    },
+    /// Time the sparse iteration on every preset, or every algorithm on a
+    /// random soup
+    Bench {
+        #[arg(long, default_value_t = 100)]
+        gens: u64,
+        /// The side of a square soup, half of it alive
+        #[arg(long)]
+        soup: Option<i64>,
+    },
+}
+
+fn run(
+    input: PathBuf,
+    gens: u64,
+    out: Option<PathBuf>,
+    rule: Option<String>,
+    algorithm: AlgorithmArg,
+) -> Result<String, CliErr> {
+    let mut pattern = format::read(&input)?;
+    let rule = match rule {
+        Some(rule) if rule.ends_with(".rule") => format::read_table(&PathBuf::from(rule))?,
+        Some(rule) => rule::from_string(&rule).map_err(|e| CliErr::InvalidRule(e.to_string()))?,
+        None => pattern.rule.unwrap_or_default(),
+    };
+    let algorithm = match algorithm {
+        AlgorithmArg::Sparse => Algorithm::Sparse,
+        AlgorithmArg::Hashlife => Algorithm::HashLife,
+        AlgorithmArg::Tiled => Algorithm::Tiled,
+    };
+    build_evolution(algorithm)
+        .advance(&mut pattern.universe, &rule, gens)
+        .map_err(|e| CliErr::TooFar(e.to_string()))?;
+    match out {
+        Some(out) => {
+            format::write(&out, &pattern.universe, &rule)?;
+            Ok(String::new())
+        }
+        None => Ok(rle::to_string(&pattern.universe, &rule)),
+    }
+}
+
+fn convert(input: PathBuf, output: PathBuf) -> Result<String, CliErr> {
+    let pattern = format::read(&input)?;
+    format::write(&output, &pattern.universe, &pattern.rule.unwrap_or_default())?;
+    Ok(String::new())
+}
+
+fn info(input: PathBuf, max_gens: u64) -> Result<String, CliErr> {
+    let pattern = format::read(&input)?;
+    let rule = pattern.rule.unwrap_or_default();
+    let mut result = format!("rule: {rule}\npopulation: {}\n", pattern.universe.value.len());
+    if rule.kind == RuleKind::Colored {
+        let colors: Vec<String> =
+            get_population(&pattern.universe, rule.states).iter().map(|n| n.to_string()).collect();
+        result.push_str(&format!("by color: {}\n", colors.join(", ")));
+    }
+    match get_bounds(&pattern.universe) {
+        Some(r) => result.push_str(&format!(
+            "bounding box: {}x{} from ({}, {}) to ({}, {})\n",
+            r.x2 - r.x1 + 1,
+            r.y2 - r.y1 + 1,
+            r.x1,
+            r.y1,
+            r.x2,
+            r.y2
+        )),
+        None => result.push_str("bounding box: empty\n"),
+    }
+    match analyze(&pattern.universe, &rule, max_gens) {
+        Some(analysis) => {
+            result.push_str(&format!("type: {}\n", analysis.classification));
+            result.push_str(&format!("period: {}\n", analysis.period));
+            if let Some(speed) = analysis.speed() {
+                result.push_str(&format!("speed: {speed}\n"));
+            }
+            if analysis.start > 0 {
+                result.push_str(&format!("from generation: {}\n", analysis.start));
+            }
+        }
+        None => result.push_str(&format!("period: not found in {max_gens} generations\n")),
+    }
+    Ok(result)
+}
+
+fn bench(gens: u64) -> String {
+    let mut result = String::new();
+    for group in get_preset_groups() {
+        for sub_group in group.sub_groups {
+            for preset in sub_group.items {
+                let Some(mut u) = get_preset(&preset.id) else {
+                    continue;
+                };
+                let rule = get_preset_rule(&preset.id).unwrap_or_default();
+                let start = Instant::now();
+                for _ in 0..gens {
+                    iterate(&mut u, &rule);
+                }
+                let elapsed = start.elapsed().as_secs_f64();
+                result.push_str(&format!(
+                    "{:<24} {:>10.3} ms {:>12.0} gen/s\n",
+                    preset.id,
+                    elapsed * 1000.0,
+                    gens as f64 / elapsed.max(f64::EPSILON)
+                ));
+            }
+        }
+    }
+    result
+}
+
+/// The same soup on every run, so the timings can be compared.
+fn soup(size: i64) -> Universe {
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut value = HashMap::new();
+    for y in 0..size {
+        for x in 0..size {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            if seed & 1 == 1 {
+                value.insert(CartesianP::of(x, y), State::Alive);
+            }
+        }
+    }
+    Universe::from(value)
+}
+
+fn bench_soup(gens: u64, size: i64) -> Result<String, CliErr> {
+    let mut result = String::new();
+    let rule = Rule::default();
+    for (name, algorithm) in [
+        ("sparse", Algorithm::Sparse),
+        ("tiled", Algorithm::Tiled),
+        ("hashlife", Algorithm::HashLife),
+    ] {
+        let mut u = soup(size);
+        let start = Instant::now();
+        build_evolution(algorithm)
+            .advance(&mut u, &rule, gens)
+            .map_err(|e| CliErr::TooFar(e.to_string()))?;
+        let elapsed = start.elapsed().as_secs_f64().max(f64::EPSILON);
+        // The cells of the soup are the ones that count, however it grows.
+        let cells = (size * size) as u64 * gens;
+        result.push_str(&format!(
+            "{:<24} {:>10.3} ms {:>12.0} gen/s {:>14.0} cells/s\n",
+            name,
+            elapsed * 1000.0,
+            gens as f64 / elapsed,
+            cells as f64 / elapsed
+        ));
+    }
+    Ok(result)
+}
+
+fn main() -> ExitCode {
+    let result = match Cli::parse().command {
+        Command::Run { input, gens, out, rule, algorithm } => {
+            run(input, gens, out, rule, algorithm)
+        }
+        Command::Convert { input, output } => convert(input, output),
+        Command::Info { input, max_gens } => info(input, max_gens),
+        Command::Bench { gens, soup: Some(size) } => bench_soup(gens, size),
+        Command::Bench { gens, soup: None } => Ok(bench(gens)),
+    };
+    match result {
+        Ok(output) => {
+            print!("{output}");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::Path};
+
+    use super::*;
+
+    /// A directory of its own for each test, removed with everything in it
+    /// when the test ends, even if it fails.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(test: &str) -> Self {
+            let dir = env::temp_dir().join(format!("gol_test_{}_{test}", std::process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn file(&self, name: &str, content: &str) -> PathBuf {
+            let path = self.0.join(name);
+            fs::write(&path, content).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let dir = TempDir::new("run");
+        let glider = dir.file("run.rle", "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+        assert_eq!(
+            run(glider.clone(), 4, None, None, AlgorithmArg::Hashlife).unwrap(),
+            "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n"
+        );
+        assert_eq!(
+            run(glider.clone(), 1, None, Some(String::from("B3/S")), AlgorithmArg::Sparse).unwrap(),
+            "x = 2, y = 3, rule = B3/S\no2$bo!\n"
+        );
+        let grow = dir.file(
+            "Grow.rule",
+            "@RULE Grow\n@TABLE\nn_states:2\nneighborhood:vonNeumann\nsymmetries:rotate4\n0,1,0,0,0,1\n",
+        );
+        let blinker = dir.file("run_blinker.rle", "x = 3, y = 1\n3o!\n");
+        let table = Some(grow.display().to_string());
+        assert_eq!(
+            run(blinker.clone(), 1, None, table, AlgorithmArg::Hashlife).unwrap(),
+            "x = 5, y = 3, rule = Grow\nb3o$5o$b3o!\n"
+        );
+        let invalid = dir.file("Invalid.rule", "@TABLE\n");
+        assert_eq!(
+            run(blinker, 1, None, Some(invalid.display().to_string()), AlgorithmArg::Sparse)
+                .unwrap_err()
+                .to_string(),
+            format!("{}: The rule must have a \"@RULE\" section!", invalid.display())
+        );
+        assert_eq!(
+            run(glider.clone(), u64::MAX, None, None, AlgorithmArg::Hashlife)
+                .unwrap_err()
+                .to_string(),
+            "The pattern can't be advanced that far!"
+        );
+        let out = glider.with_file_name("run_out.rle");
+        assert_eq!(run(glider, 2, Some(out.clone()), None, AlgorithmArg::Sparse).unwrap(), "");
+        assert_eq!(fs::read_to_string(out).unwrap(), "x = 3, y = 3, rule = B3/S23\n2bo$obo$b2o!\n");
+    }
+
+    #[test]
+    fn test_convert() {
+        let dir = TempDir::new("convert");
+        let input = dir.file("convert.rle", "#N Blinker\nx = 3, y = 1\n3o!\n");
+        let output = input.with_file_name("convert_out.rle");
+        assert_eq!(convert(input, output.clone()).unwrap(), "");
+        assert_eq!(fs::read_to_string(output).unwrap(), "x = 3, y = 1, rule = B3/S23\n3o!\n");
+
+        let cells = dir.file("convert.cells", "!Name: Glider\n.O.\n..O\nOOO\n");
+        let lif = cells.with_file_name("convert_out.lif");
+        assert_eq!(convert(cells, lif.clone()).unwrap(), "");
+        assert_eq!(fs::read_to_string(&lif).unwrap(), "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n");
+        let rle = lif.with_file_name("convert_out2.rle");
+        assert_eq!(convert(lif, rle.clone()).unwrap(), "");
+        assert_eq!(fs::read_to_string(rle).unwrap(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+        let life105 = dir.file("convert_105.lif", "#Life 1.05\n#R 23/36\n#P 0 0\n***\n");
+        let cells = life105.with_file_name("convert_out.cells");
+        assert_eq!(convert(life105, cells.clone()).unwrap(), "");
+        assert_eq!(fs::read_to_string(&cells).unwrap(), "OOO\n");
+        let mc = cells.with_file_name("convert_out.mc");
+        assert_eq!(convert(cells, mc.clone()).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(&mc).unwrap(),
+            "[M2] (game of life)\n#R B3/S23\n$$$$...***$\n"
+        );
+        let rle = mc.with_file_name("convert_out3.rle");
+        assert_eq!(convert(mc, rle.clone()).unwrap(), "");
+        assert_eq!(fs::read_to_string(rle).unwrap(), "x = 3, y = 1, rule = B3/S23\n3o!\n");
+    }
+
+    #[test]
+    fn test_info() {
+        let dir = TempDir::new("info");
+        let blinker = dir.file("info.rle", "x = 3, y = 1\n3o!\n");
+        assert_eq!(
+            info(blinker, 10).unwrap(),
+            "rule: B3/S23\npopulation: 3\nbounding box: 3x1 from (-1, 0) to (1, 0)\ntype: oscillator\nperiod: 2\n"
+        );
+        let t_tetromino = dir.file("info_t.rle", "x = 3, y = 2, rule = B3/S23\nbo$3o!\n");
+        assert!(info(t_tetromino.clone(), 20)
+            .unwrap()
+            .ends_with("period: 2\nfrom generation: 9\n"));
+        assert!(info(t_tetromino, 5).unwrap().ends_with("period: not found in 5 generations\n"));
+        let glider = dir.file("info_glider.rle", "x = 3, y = 3\nbo$2bo$3o!\n");
+        assert!(info(glider, 10)
+            .unwrap()
+            .ends_with("type: spaceship\nperiod: 4\nspeed: c/4 diagonal\n"));
+        let immigration = dir.file("info_colors.rle", "x = 3, y = 1, rule = Immigration\nABA!\n");
+        assert!(info(immigration, 10)
+            .unwrap()
+            .starts_with("rule: Immigration\npopulation: 3\nby color: 2, 1\n"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = TempDir::new("errors");
+        let text = dir.file("pattern.txt", "O\n");
+        assert_eq!(
+            info(text.clone(), 10).unwrap_err().to_string(),
+            format!(
+                "{}: unsupported format, expected a \".rle\", \".cells\", \".lif\" or \".mc\" file!",
+                text.display()
+            )
+        );
+        let missing = Path::new("missing.rle").to_path_buf();
+        assert!(matches!(info(missing, 10), Err(CliErr::Io(_, _))));
+        let invalid = dir.file("invalid.rle", "x = 3, y = 1\n3z!\n");
+        assert_eq!(
+            info(invalid.clone(), 10).unwrap_err().to_string(),
+            format!("{}: Invalid character at line 2, column 2!", invalid.display())
+        );
+        let cells = dir.file("invalid.cells", ".O.\n.x.\n");
+        assert_eq!(
+            info(cells.clone(), 10).unwrap_err().to_string(),
+            format!("{}: Invalid character at line 2, column 2!", cells.display())
+        );
+        let blinker = dir.file("rule.rle", "x = 3, y = 1\n3o!\n");
+        assert!(matches!(
+            run(blinker, 1, None, Some(String::from("X")), AlgorithmArg::Sparse),
+            Err(CliErr::InvalidRule(_))
+        ));
+    }
+
+    #[test]
+    fn test_bench() {
+        let result = bench(1);
+        assert!(result.lines().any(|line| line.starts_with("gosper_glider_gun ")));
+        assert!(result.lines().any(|line| line.starts_with("block ")));
+    }
+
+    #[test]
+    fn test_bench_soup() {
+        assert_eq!(soup(64), soup(64));
+        assert!(soup(64).value.len().abs_diff(64 * 64 / 2) < 200);
+        let result = bench_soup(2, 64).unwrap();
+        let names: Vec<&str> = result.lines().filter_map(|line| line.split(' ').next()).collect();
+        assert_eq!(names, ["sparse", "tiled", "hashlife"]);
+    }
+}
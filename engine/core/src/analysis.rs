@@ -0,0 +1,257 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::CartesianP,
+    rule::{Rule, RuleKind},
+    universe::{get_bounds, iterate, Universe},
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Classification {
+    Empty,
+    StillLife,
+    Oscillator,
+    Spaceship,
+}
+
+impl fmt::Display for Classification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Classification::Empty => write!(f, "empty"),
+            Classification::StillLife => write!(f, "still life"),
+            Classification::Oscillator => write!(f, "oscillator"),
+            Classification::Spaceship => write!(f, "spaceship"),
+        }
+    }
+}
+
+/// A pattern that repeats itself every `period` generations, moved by `dx`
+/// and `dy`, from generation `start` of the evolution on.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Analysis {
+    pub classification: Classification,
+    pub start: u64,
+    pub period: u64,
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Analysis {
+    /// The speed in the c/p notation, in lowest terms, such as "c/4 diagonal"
+    /// for the glider or "(2,1)c/6" for a knightship, if the pattern moves.
+    pub fn speed(&self) -> Option<String> {
+        if self.classification != Classification::Spaceship {
+            return None;
+        }
+        let (dx, dy) = (self.dx.unsigned_abs(), self.dy.unsigned_abs());
+        let (high, low) = (dx.max(dy), dx.min(dy));
+        let c = |k: u64| if k == 1 { String::from("c") } else { format!("{k}c") };
+        let d = gcd(gcd(high, low), self.period);
+        let (high, low, period) = (high / d, low / d, self.period / d);
+        Some(if low == 0 {
+            format!("{}/{period} orthogonal", c(high))
+        } else if low == high {
+            format!("{}/{period} diagonal", c(high))
+        } else {
+            format!("({high},{low})c/{period}")
+        })
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The cells moved so that the bounding box starts at the origin, and where
+/// it started.
+fn normalize(u: &Universe) -> (Vec<(CartesianP, State)>, CartesianP) {
+    let offset = match get_bounds(u) {
+        Some(r) => CartesianP::of(r.x1, r.y1),
+        None => CartesianP::of(0, 0),
+    };
+    let mut cells: Vec<(CartesianP, State)> =
+        u.value.iter().map(|(p, s)| (CartesianP::of(p.x - offset.x, p.y - offset.y), *s)).collect();
+    cells.sort_by_key(|(p, _)| (p.x, p.y));
+    (cells, offset)
+}
+
+fn fingerprint(cells: &[(CartesianP, State)]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    cells.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Evolves the universe until a previous state comes back, anywhere in the
+/// plane, or `None` if that doesn't happen in `max_generations`. The states
+/// are looked up by their fingerprint, and compared cell by cell only when
+/// it matches.
+pub fn analyze(u: &Universe, rule: &Rule, max_generations: u64) -> Option<Analysis> {
+    let mut u = u.clone();
+    let mut states: Vec<(Vec<(CartesianP, State)>, CartesianP)> = Vec::new();
+    let mut seen: HashMap<u64, Vec<u64>> = HashMap::new();
+    for generation in 0..=max_generations {
+        let (cells, offset) = normalize(&u);
+        let key = fingerprint(&cells);
+        let candidates = seen.get(&key).into_iter().flatten();
+        // Block rules alternate their partition, so a state only comes back
+        // on the same one after an even number of generations, moved by an
+        // even number of cells.
+        let same_partition = |g: u64, dx: i64, dy: i64| {
+            !matches!(rule.kind, RuleKind::Margolus(_))
+                || ((generation - g).is_multiple_of(2) && dx % 2 == 0 && dy % 2 == 0)
+        };
+        let found = candidates.copied().find(|g| {
+            let (earlier, first) = &states[*g as usize];
+            same_partition(*g, offset.x - first.x, offset.y - first.y) && *earlier == cells
+        });
+        if let Some(start) = found {
+            let first = states[start as usize].1;
+            let (dx, dy) = (offset.x - first.x, offset.y - first.y);
+            let period = generation - start;
+            let classification = match (cells.is_empty(), dx == 0 && dy == 0, period) {
+                (true, _, _) => Classification::Empty,
+                (false, true, 1) => Classification::StillLife,
+                (false, true, _) => Classification::Oscillator,
+                (false, false, _) => Classification::Spaceship,
+            };
+            return Some(Analysis { classification, start, period, dx, dy });
+        }
+        seen.entry(key).or_default().push(generation);
+        states.push((cells, offset));
+        iterate(&mut u, rule);
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        cell::State, preset::get_preset_unsafe, rule, topology::Topology, universe::from_string,
+    };
+
+    use super::*;
+
+    fn analysis(classification: Classification, start: u64, period: u64) -> Analysis {
+        Analysis { classification, start, period, dx: 0, dy: 0 }
+    }
+
+    #[test]
+    fn test_analyze() {
+        let conway = Rule::default();
+        assert_eq!(
+            analyze(&get_preset_unsafe("block"), &conway, 10),
+            Some(analysis(Classification::StillLife, 0, 1))
+        );
+        assert_eq!(
+            analyze(&get_preset_unsafe("blinker"), &conway, 10),
+            Some(analysis(Classification::Oscillator, 0, 2))
+        );
+        assert_eq!(
+            analyze(&Universe::default(), &conway, 10),
+            Some(analysis(Classification::Empty, 0, 1))
+        );
+        assert_eq!(analyze(&get_preset_unsafe("blinker"), &conway, 1), None);
+        assert_eq!(analyze(&get_preset_unsafe("gosper_glider_gun"), &conway, 100), None);
+    }
+
+    #[test]
+    fn test_analyze_transient() {
+        let t_tetromino = Universe::from(HashMap::from([
+            (CartesianP::of(-1, 0), State::Alive),
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(1, 0), State::Alive),
+            (CartesianP::of(0, 1), State::Alive),
+        ]));
+        assert_eq!(
+            analyze(&t_tetromino, &Rule::default(), 20),
+            Some(analysis(Classification::Oscillator, 9, 2))
+        );
+        let diehard = from_string(vec![
+            String::from("⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬜⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬜⬜⬜"),
+        ])
+        .unwrap();
+        assert_eq!(
+            analyze(&diehard, &Rule::default(), 200),
+            Some(analysis(Classification::Empty, 130, 1))
+        );
+    }
+
+    #[test]
+    fn test_analyze_spaceship() {
+        let glider = analyze(&get_preset_unsafe("glider"), &Rule::default(), 10).unwrap();
+        assert_eq!(glider.classification, Classification::Spaceship);
+        assert_eq!((glider.start, glider.period), (0, 4));
+        assert_eq!((glider.dx.abs(), glider.dy.abs()), (1, 1));
+        assert_eq!(glider.speed(), Some(String::from("c/4 diagonal")));
+
+        let lwss = from_string(vec![
+            String::from("⬛⬜⬛⬛⬜"),
+            String::from("⬜⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬜"),
+            String::from("⬜⬜⬜⬜⬛"),
+        ])
+        .unwrap();
+        let lwss = analyze(&lwss, &Rule::default(), 10).unwrap();
+        assert_eq!(lwss.classification, Classification::Spaceship);
+        assert_eq!((lwss.period, lwss.dx.abs(), lwss.dy), (4, 2, 0));
+        assert_eq!(lwss.speed(), Some(String::from("c/2 orthogonal")));
+
+        let mut brians_brain = Rule::of(&[2], &[]);
+        brians_brain.states = 3;
+        let ship = Universe::from(HashMap::from([
+            (CartesianP::of(-1, 0), State::Dying(1)),
+            (CartesianP::of(-1, 1), State::Dying(1)),
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(0, 1), State::Alive),
+        ]));
+        let ship = analyze(&ship, &brians_brain, 10).unwrap();
+        assert_eq!(ship.classification, Classification::Spaceship);
+        assert_eq!((ship.period, ship.dx, ship.dy), (1, 1, 0));
+    }
+
+    #[test]
+    fn test_analyze_blocks() {
+        let plane = |name| Rule { topology: Topology::Plane, ..rule::from_string(name).unwrap() };
+        let bbm = plane("BBM");
+        let ball = Universe::from(HashMap::from([(CartesianP::of(0, 0), State::Alive)]));
+        let ball = analyze(&ball, &bbm, 10).unwrap();
+        assert_eq!(ball.classification, Classification::Spaceship);
+        assert_eq!((ball.period, ball.dx.abs(), ball.dy.abs()), (2, 2, 2));
+        assert_eq!(ball.speed(), Some(String::from("c/1 diagonal")));
+        // The odd generations of Critters are stored complemented.
+        let critters = plane("Critters");
+        assert_eq!(
+            analyze(&Universe::default(), &critters, 10),
+            Some(analysis(Classification::Empty, 0, 2))
+        );
+    }
+
+    #[test]
+    fn test_speed() {
+        let ship = |period, dx, dy| Analysis {
+            classification: Classification::Spaceship,
+            start: 0,
+            period,
+            dx,
+            dy,
+        };
+        assert_eq!(ship(2, 0, -1).speed(), Some(String::from("c/2 orthogonal")));
+        assert_eq!(ship(12, 3, 3).speed(), Some(String::from("c/4 diagonal")));
+        assert_eq!(ship(6, -1, 2).speed(), Some(String::from("(2,1)c/6")));
+        assert_eq!(ship(12, 4, -2).speed(), Some(String::from("(2,1)c/6")));
+        assert_eq!(ship(5, 2, 0).speed(), Some(String::from("2c/5 orthogonal")));
+        assert_eq!(analysis(Classification::Oscillator, 0, 2).speed(), None);
+        assert_eq!(format!("{}", Classification::StillLife), "still life");
+    }
+}
@@ -0,0 +1,273 @@
+use super::{
+    rule::{Rule, RuleKind},
+    table::{self, Table},
+};
+
+/// `Dying(k)` is the k-th refractory stage of a Generations rule, a cell that
+/// no longer counts as a neighbor and can't be born again until it is dead.
+/// Rule tables and colored rules only go by the number of the state.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum State {
+    Dead,
+    Alive,
+    Dying(u8),
+}
+
+/// The numbering of Golly, shared by multistate RLE and rule tables, where 0
+/// is dead, 1 alive and the dying stages follow.
+impl From<u8> for State {
+    fn from(n: u8) -> Self {
+        match n {
+            0 => State::Dead,
+            1 => State::Alive,
+            n => State::Dying(n - 1),
+        }
+    }
+}
+
+impl From<State> for u8 {
+    fn from(s: State) -> Self {
+        match s {
+            State::Dead => 0,
+            State::Alive => 1,
+            State::Dying(k) => k.saturating_add(1),
+        }
+    }
+}
+
+fn next(s: State, survives: bool, born: bool, rule: &Rule) -> State {
+    match s {
+        State::Alive if survives => State::Alive,
+        State::Alive if rule.states > 2 => State::Dying(1),
+        State::Dead if born => State::Alive,
+        State::Dying(k) if u16::from(k) + 2 < u16::from(rule.states) => State::Dying(k + 1),
+        _ => State::Dead,
+    }
+}
+
+pub fn iterate(s: State, number_of_alive_neighbors: u8, rule: &Rule) -> State {
+    let n = usize::from(number_of_alive_neighbors);
+    let survives = rule.survival.get(n).copied().unwrap_or(false);
+    let born = rule.birth.get(n).copied().unwrap_or(false);
+    next(s, survives, born, rule)
+}
+
+/// Same as `iterate` with the configuration of the 8 neighbors, which only
+/// matters to isotropic rules.
+pub fn iterate_isotropic(s: State, configuration: u8, rule: &Rule) -> State {
+    match &rule.kind {
+        RuleKind::Isotropic(i) => next(
+            s,
+            i.survival[usize::from(configuration)],
+            i.birth[usize::from(configuration)],
+            rule,
+        ),
+        _ => iterate(s, configuration.count_ones() as u8, rule),
+    }
+}
+
+/// Same as `iterate` where every state but the dead one is an alive cell of
+/// some color. A newborn takes the color most of its parents have, or else
+/// the first one none of them has.
+pub fn iterate_colored(s: State, neighbors: &[State], rule: &Rule) -> State {
+    let mut counts = vec![0_u8; usize::from(rule.states)];
+    for n in neighbors {
+        if let Some(count) = counts.get_mut(usize::from(u8::from(*n))) {
+            *count += 1;
+        }
+    }
+    let alive: u8 = counts[1..].iter().sum();
+    let n = usize::from(alive);
+    match s {
+        State::Dead if rule.birth.get(n).copied().unwrap_or(false) => {
+            let color = (1..counts.len())
+                .find(|c| counts[*c] * 2 > alive)
+                .or_else(|| (1..counts.len()).find(|c| counts[*c] == 0))
+                .unwrap_or(1);
+            State::from(color as u8)
+        }
+        State::Dead => State::Dead,
+        s if rule.survival.get(n).copied().unwrap_or(false) => s,
+        _ => State::Dead,
+    }
+}
+
+/// The state given by a rule table to a cell and its neighbors, in the order
+/// of `table::neighbors`.
+pub fn iterate_table(s: State, neighbors: &[State], t: &Table) -> State {
+    let neighbors: Vec<u8> = neighbors.iter().map(|n| u8::from(*n)).collect();
+    State::from(table::next(t, u8::from(s), &neighbors))
+}
+
+/// The state after `s` in the palette of a rule table, which starts over
+/// after the last one.
+pub fn cycle(s: &State, palette: &[u8]) -> State {
+    let position = palette.iter().position(|n| *n == u8::from(*s));
+    let next = position.map(|i| (i + 1) % palette.len()).unwrap_or(0);
+    palette.get(next).map(|n| State::from(*n)).unwrap_or(State::Dead)
+}
+
+pub fn toggle(s: &State) -> State {
+    match s {
+        State::Dead => State::Alive,
+        State::Alive | State::Dying(_) => State::Dead,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        isotropic::{self, Isotropic},
+        rule,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_iterate_any_live_cell_with_fewer_than_two_live_neighbours_dies() {
+        let conway = Rule::default();
+        assert_eq!(iterate(State::Alive, 0, &conway), State::Dead);
+        assert_eq!(iterate(State::Alive, 1, &conway), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_any_live_cell_with_two_or_three_live_neighbours_lives() {
+        let conway = Rule::default();
+        assert_eq!(iterate(State::Alive, 2, &conway), State::Alive);
+        assert_eq!(iterate(State::Alive, 3, &conway), State::Alive);
+    }
+
+    #[test]
+    fn test_iterate_any_live_cell_with_more_than_three_live_neighbours_dies() {
+        let conway = Rule::default();
+        assert_eq!(iterate(State::Alive, 4, &conway), State::Dead);
+        assert_eq!(iterate(State::Alive, 5, &conway), State::Dead);
+        assert_eq!(iterate(State::Alive, 6, &conway), State::Dead);
+        assert_eq!(iterate(State::Alive, 7, &conway), State::Dead);
+        assert_eq!(iterate(State::Alive, 8, &conway), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_any_dead_cell_with_exactly_three_live_neighbours_becomes_a_live_cell() {
+        let conway = Rule::default();
+        assert_eq!(iterate(State::Dead, 0, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 1, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 2, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 3, &conway), State::Alive);
+        assert_eq!(iterate(State::Dead, 4, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 5, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 6, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 7, &conway), State::Dead);
+        assert_eq!(iterate(State::Dead, 8, &conway), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_highlife() {
+        let highlife = Rule::of(&[3, 6], &[2, 3]);
+        assert_eq!(iterate(State::Dead, 3, &highlife), State::Alive);
+        assert_eq!(iterate(State::Dead, 6, &highlife), State::Alive);
+        assert_eq!(iterate(State::Dead, 2, &highlife), State::Dead);
+        assert_eq!(iterate(State::Alive, 2, &highlife), State::Alive);
+        assert_eq!(iterate(State::Alive, 6, &highlife), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_seeds() {
+        let seeds = Rule::of(&[2], &[]);
+        assert_eq!(iterate(State::Dead, 2, &seeds), State::Alive);
+        assert_eq!(iterate(State::Alive, 2, &seeds), State::Dead);
+        assert_eq!(iterate(State::Alive, 3, &seeds), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_brians_brain() {
+        let brians_brain = Rule { states: 3, ..Rule::of(&[2], &[]) };
+        assert_eq!(iterate(State::Dead, 2, &brians_brain), State::Alive);
+        assert_eq!(iterate(State::Dead, 3, &brians_brain), State::Dead);
+        assert_eq!(iterate(State::Alive, 2, &brians_brain), State::Dying(1));
+        assert_eq!(iterate(State::Dying(1), 2, &brians_brain), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_star_wars() {
+        let star_wars = Rule { states: 4, ..Rule::of(&[2], &[3, 4, 5]) };
+        assert_eq!(iterate(State::Alive, 3, &star_wars), State::Alive);
+        assert_eq!(iterate(State::Alive, 2, &star_wars), State::Dying(1));
+        assert_eq!(iterate(State::Dying(1), 2, &star_wars), State::Dying(2));
+        assert_eq!(iterate(State::Dying(2), 2, &star_wars), State::Dead);
+        assert_eq!(iterate(State::Dying(1), 2, &Rule::default()), State::Dead);
+        let longest = Rule { states: 255, ..Rule::of(&[2], &[]) };
+        assert_eq!(iterate(State::Dying(252), 0, &longest), State::Dying(253));
+        assert_eq!(iterate(State::Dying(253), 0, &longest), State::Dead);
+    }
+
+    #[test]
+    fn test_state_number() {
+        assert_eq!(State::from(0), State::Dead);
+        assert_eq!(State::from(1), State::Alive);
+        assert_eq!(State::from(2), State::Dying(1));
+        assert_eq!(u8::from(State::Dying(3)), 4);
+        for n in 0..=255u8 {
+            assert_eq!(u8::from(State::from(n)), n);
+        }
+    }
+
+    #[test]
+    fn test_toggle() {
+        assert_eq!(toggle(&State::Alive), State::Dead);
+        assert_eq!(toggle(&State::Dead), State::Alive);
+        assert_eq!(toggle(&State::Dying(2)), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_colored() {
+        let immigration = rule::from_string("Immigration").unwrap();
+        let quadlife = rule::from_string("QuadLife").unwrap();
+        let [dead, red, blue, green, yellow] = [0, 1, 2, 3, 4].map(State::from);
+        assert_eq!(iterate_colored(dead, &[red, blue, blue, dead], &immigration), blue);
+        assert_eq!(iterate_colored(dead, &[red, red, blue], &immigration), red);
+        assert_eq!(iterate_colored(dead, &[red, blue], &immigration), dead);
+        assert_eq!(iterate_colored(blue, &[red, red], &immigration), blue);
+        assert_eq!(iterate_colored(red, &[blue, blue, blue, blue], &immigration), dead);
+        assert_eq!(iterate_colored(dead, &[green, yellow, green], &quadlife), green);
+        assert_eq!(iterate_colored(dead, &[red, green, yellow], &quadlife), blue);
+        assert_eq!(iterate_colored(dead, &[blue, yellow, green], &quadlife), red);
+        assert_eq!(iterate_colored(yellow, &[red, green, blue], &quadlife), yellow);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let wireworld = [0, 3, 1, 2];
+        assert_eq!(cycle(&State::Dead, &wireworld), State::Dying(2));
+        assert_eq!(cycle(&State::Dying(2), &wireworld), State::Alive);
+        assert_eq!(cycle(&State::Alive, &wireworld), State::Dying(1));
+        assert_eq!(cycle(&State::Dying(1), &wireworld), State::Dead);
+        assert_eq!(cycle(&State::Dying(7), &wireworld), State::Dead);
+        assert_eq!(cycle(&State::Alive, &[]), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_isotropic() {
+        let conway = Rule::default();
+        let hensel = Rule {
+            kind: RuleKind::Isotropic(Box::new(Isotropic {
+                birth: isotropic::from_string("3cekainyqjr").unwrap(),
+                survival: isotropic::from_string("2ceaikn3ceaiknjqry").unwrap(),
+            })),
+            ..Rule::of(&[], &[])
+        };
+        for configuration in 0..=255u8 {
+            let n = configuration.count_ones() as u8;
+            for s in [State::Dead, State::Alive] {
+                assert_eq!(iterate_isotropic(s, configuration, &hensel), iterate(s, n, &conway));
+                assert_eq!(iterate_isotropic(s, configuration, &conway), iterate(s, n, &conway));
+            }
+        }
+        let tlife = rule::from_string("B3/S2-i34q").unwrap();
+        assert_eq!(iterate_isotropic(State::Alive, 0b0100_0010, &tlife), State::Dead);
+        assert_eq!(iterate_isotropic(State::Alive, 0b0001_1000, &tlife), State::Dead);
+        assert_eq!(iterate_isotropic(State::Alive, 0b0100_0001, &tlife), State::Alive);
+        assert_eq!(iterate_isotropic(State::Alive, 0b1000_1011, &tlife), State::Alive);
+        assert_eq!(iterate_isotropic(State::Alive, 0b0000_1111, &tlife), State::Dead);
+    }
+}
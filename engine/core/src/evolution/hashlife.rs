@@ -0,0 +1,576 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::CartesianP,
+    rule::Rule,
+    universe::{get_bounds, set_value, Universe},
+};
+
+use super::{life_like, AdvanceErr, Evolution, Sparse};
+
+type NodeId = usize;
+
+const DEAD: NodeId = 0;
+const ALIVE: NodeId = 1;
+const MIN_ROOT_LEVEL: u8 = 3;
+/// The root is centered on the origin, so this keeps its corners in an `i64`.
+const MAX_ROOT_LEVEL: u8 = 62;
+const DEFAULT_MAX_NODES: usize = 1 << 20;
+/// The largest power of two of generations a single jump can go.
+pub const MAX_POWER: u8 = 62;
+/// The most generations left to the sparse algorithm when HashLife can't go
+/// any further at once.
+const MAX_SPARSE_GENERATIONS: u64 = 1 << 16;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    level: u8,
+    population: u64,
+    nw: NodeId,
+    ne: NodeId,
+    sw: NodeId,
+    se: NodeId,
+}
+
+/// Quadtree evolution with canonical nodes, where a node of level `k` covers a
+/// `2^k` square and its memoised successor is the centered `2^(k-1)` square
+/// advanced by up to `2^(k-2)` generations. Finite topologies fall back to
+/// the sparse algorithm, and so does a pattern that goes too far from the
+/// origin for the coordinates.
+pub struct HashLife {
+    nodes: Vec<Node>,
+    index: HashMap<[NodeId; 4], NodeId>,
+    results: HashMap<(NodeId, u8), NodeId>,
+    empty: Vec<NodeId>,
+    rule: Rule,
+    max_nodes: usize,
+    capped: bool,
+}
+
+impl Default for HashLife {
+    fn default() -> Self {
+        HashLife::new(DEFAULT_MAX_NODES)
+    }
+}
+
+impl HashLife {
+    pub fn new(max_nodes: usize) -> Self {
+        let leaf = |population| Node { level: 0, population, nw: 0, ne: 0, sw: 0, se: 0 };
+        HashLife {
+            nodes: vec![leaf(0), leaf(1)],
+            index: HashMap::new(),
+            results: HashMap::new(),
+            empty: Vec::new(),
+            rule: Rule::default(),
+            max_nodes,
+            capped: false,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn advance_by_power_of_two(
+        &mut self,
+        u: &mut Universe,
+        rule: &Rule,
+        k: u8,
+    ) -> Result<(), AdvanceErr> {
+        if k > MAX_POWER {
+            return Err(AdvanceErr);
+        }
+        self.advance(u, rule, 1 << k)
+    }
+
+    fn set_rule(&mut self, rule: &Rule) {
+        if &self.rule != rule {
+            self.rule = rule.clone();
+            self.results.clear();
+        }
+    }
+
+    fn join(&mut self, nw: NodeId, ne: NodeId, sw: NodeId, se: NodeId) -> NodeId {
+        if let Some(id) = self.index.get(&[nw, ne, sw, se]) {
+            return *id;
+        }
+        let n = &self.nodes;
+        let node = Node {
+            level: n[nw].level + 1,
+            population: n[nw].population + n[ne].population + n[sw].population + n[se].population,
+            nw,
+            ne,
+            sw,
+            se,
+        };
+        let id = self.nodes.len();
+        self.nodes.push(node);
+        self.index.insert([nw, ne, sw, se], id);
+        id
+    }
+
+    fn empty(&mut self, level: u8) -> NodeId {
+        while self.empty.len() <= usize::from(level) {
+            let id = match self.empty.last() {
+                Some(e) => self.join(*e, *e, *e, *e),
+                None => DEAD,
+            };
+            self.empty.push(id);
+        }
+        self.empty[usize::from(level)]
+    }
+
+    fn center(&mut self, id: NodeId) -> NodeId {
+        let n = self.nodes[id];
+        let (nw, ne, sw, se) =
+            (self.nodes[n.nw], self.nodes[n.ne], self.nodes[n.sw], self.nodes[n.se]);
+        self.join(nw.se, ne.sw, sw.ne, se.nw)
+    }
+
+    fn expand(&mut self, id: NodeId) -> NodeId {
+        let n = self.nodes[id];
+        let e = self.empty(n.level - 1);
+        let nw = self.join(e, e, e, n.nw);
+        let ne = self.join(e, e, n.ne, e);
+        let sw = self.join(e, n.sw, e, e);
+        let se = self.join(n.se, e, e, e);
+        self.join(nw, ne, sw, se)
+    }
+
+    fn is_padded(&mut self, id: NodeId) -> bool {
+        let center = self.center(id);
+        self.nodes[id].population == self.nodes[center].population
+    }
+
+    fn cell_of_level_2(&self, id: NodeId, row: usize, col: usize) -> bool {
+        let n = self.nodes[id];
+        let quadrant = |n: Node, row: usize, col: usize| match (row, col) {
+            (0, 0) => n.nw,
+            (0, 1) => n.ne,
+            (1, 0) => n.sw,
+            _ => n.se,
+        };
+        let child = self.nodes[quadrant(n, row / 2, col / 2)];
+        quadrant(child, row % 2, col % 2) == ALIVE
+    }
+
+    fn base(&mut self, id: NodeId) -> NodeId {
+        let mut next = [DEAD; 4];
+        for (i, (row, col)) in [(1, 1), (1, 2), (2, 1), (2, 2)].into_iter().enumerate() {
+            let mut number_of_alive_neighbors = 0;
+            for r in row - 1..=row + 1 {
+                for c in col - 1..=col + 1 {
+                    if (r, c) != (row, col) && self.cell_of_level_2(id, r, c) {
+                        number_of_alive_neighbors += 1;
+                    }
+                }
+            }
+            let alive = if self.cell_of_level_2(id, row, col) {
+                self.rule.survival[number_of_alive_neighbors]
+            } else {
+                self.rule.birth[number_of_alive_neighbors]
+            };
+            if alive {
+                next[i] = ALIVE;
+            }
+        }
+        self.join(next[0], next[1], next[2], next[3])
+    }
+
+    /// Gives up once there are more nodes than the cap while `capped`, so that
+    /// the jump can be made in smaller ones after collecting the garbage.
+    fn successor(&mut self, id: NodeId, j: u8) -> Option<NodeId> {
+        let n = self.nodes[id];
+        if n.population == 0 {
+            return Some(self.empty(n.level - 1));
+        }
+        let j = j.min(n.level - 2);
+        if let Some(result) = self.results.get(&(id, j)) {
+            return Some(*result);
+        }
+        if self.capped && self.nodes.len() > self.max_nodes {
+            return None;
+        }
+        let result = if n.level == 2 {
+            self.base(id)
+        } else {
+            let (a, b, c, d) =
+                (self.nodes[n.nw], self.nodes[n.ne], self.nodes[n.sw], self.nodes[n.se]);
+            let sub = [
+                [a.nw, a.ne, a.sw, a.se],
+                [a.ne, b.nw, a.se, b.sw],
+                [b.nw, b.ne, b.sw, b.se],
+                [a.sw, a.se, c.nw, c.ne],
+                [a.se, b.sw, c.ne, d.nw],
+                [b.sw, b.se, d.nw, d.ne],
+                [c.nw, c.ne, c.sw, c.se],
+                [c.ne, d.nw, c.se, d.sw],
+                [d.nw, d.ne, d.sw, d.se],
+            ];
+            let mut s = [DEAD; 9];
+            for (i, [nw, ne, sw, se]) in sub.into_iter().enumerate() {
+                let joined = self.join(nw, ne, sw, se);
+                s[i] = self.successor(joined, j)?;
+            }
+            let quarters = [
+                [s[0], s[1], s[3], s[4]],
+                [s[1], s[2], s[4], s[5]],
+                [s[3], s[4], s[6], s[7]],
+                [s[4], s[5], s[7], s[8]],
+            ];
+            let mut q = [DEAD; 4];
+            for (i, [nw, ne, sw, se]) in quarters.into_iter().enumerate() {
+                let joined = self.join(nw, ne, sw, se);
+                q[i] =
+                    if j < n.level - 2 { self.center(joined) } else { self.successor(joined, j)? };
+            }
+            self.join(q[0], q[1], q[2], q[3])
+        };
+        self.results.insert((id, j), result);
+        Some(result)
+    }
+
+    /// Advances `root` by `2^j` generations, unless the result would have to
+    /// be past `MAX_ROOT_LEVEL` or a jump of more than one generation reaches
+    /// the node cap.
+    fn step(&mut self, root: NodeId, j: u8) -> Option<NodeId> {
+        let mut root = root;
+        while self.nodes[root].level < j + 2 || !self.is_padded(root) {
+            if self.nodes[root].level >= MAX_ROOT_LEVEL {
+                return None;
+            }
+            root = self.expand(root);
+        }
+        let root = self.expand(root);
+        self.capped = j > 0;
+        self.successor(root, j)
+    }
+
+    fn build(&mut self, cells: &[CartesianP], x0: i64, y0: i64, level: u8) -> NodeId {
+        if cells.is_empty() {
+            return self.empty(level);
+        }
+        if level == 0 {
+            return ALIVE;
+        }
+        let half = 1_i64 << (level - 1);
+        let (mut nw, mut ne, mut sw, mut se) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for p in cells {
+            match (p.x < x0 + half, p.y < y0 + half) {
+                (true, false) => nw.push(*p),
+                (false, false) => ne.push(*p),
+                (true, true) => sw.push(*p),
+                (false, true) => se.push(*p),
+            }
+        }
+        let nw = self.build(&nw, x0, y0 + half, level - 1);
+        let ne = self.build(&ne, x0 + half, y0 + half, level - 1);
+        let sw = self.build(&sw, x0, y0, level - 1);
+        let se = self.build(&se, x0 + half, y0, level - 1);
+        self.join(nw, ne, sw, se)
+    }
+
+    fn load(&mut self, u: &Universe) -> Option<NodeId> {
+        let mut level = MIN_ROOT_LEVEL;
+        if let Some(r) = get_bounds(u) {
+            let fits = |level: u8| {
+                let half = 1_i64 << (level - 1);
+                r.x1 >= -half && r.y1 >= -half && r.x2 < half && r.y2 < half
+            };
+            while !fits(level) {
+                if level == MAX_ROOT_LEVEL {
+                    return None;
+                }
+                level += 1;
+            }
+        }
+        let cells: Vec<CartesianP> =
+            u.value.iter().filter(|(_, s)| **s == State::Alive).map(|(p, _)| *p).collect();
+        let half = 1_i64 << (level - 1);
+        Some(self.build(&cells, -half, -half, level))
+    }
+
+    fn collect(&self, id: NodeId, x0: i64, y0: i64, value: &mut HashMap<CartesianP, State>) {
+        let n = self.nodes[id];
+        if n.population == 0 {
+            return;
+        }
+        if n.level == 0 {
+            value.insert(CartesianP::of(x0, y0), State::Alive);
+            return;
+        }
+        let half = 1_i64 << (n.level - 1);
+        self.collect(n.nw, x0, y0 + half, value);
+        self.collect(n.ne, x0 + half, y0 + half, value);
+        self.collect(n.sw, x0, y0, value);
+        self.collect(n.se, x0 + half, y0, value);
+    }
+
+    fn store(&mut self, root: NodeId, u: &mut Universe) {
+        let half = 1_i64 << (self.nodes[root].level - 1);
+        let mut value = HashMap::new();
+        self.collect(root, -half, -half, &mut value);
+        set_value(u, value);
+    }
+
+    /// Keeps only the nodes reachable from `root`, along with the memoised
+    /// results between them, and returns the new id of `root`.
+    fn gc(&mut self, root: NodeId) -> NodeId {
+        let mut marked: HashSet<NodeId> = HashSet::from([DEAD, ALIVE]);
+        let mut stack = vec![root];
+        while let Some(id) = stack.pop() {
+            if marked.insert(id) {
+                let n = self.nodes[id];
+                stack.extend([n.nw, n.ne, n.sw, n.se]);
+            }
+        }
+        let mut remap: HashMap<NodeId, NodeId> = HashMap::from([(DEAD, DEAD), (ALIVE, ALIVE)]);
+        let mut nodes = vec![self.nodes[DEAD], self.nodes[ALIVE]];
+        let mut index = HashMap::new();
+        for (id, n) in self.nodes.iter().enumerate().skip(2) {
+            if !marked.contains(&id) {
+                continue;
+            }
+            let children = [remap[&n.nw], remap[&n.ne], remap[&n.sw], remap[&n.se]];
+            let new_id = nodes.len();
+            nodes.push(Node {
+                nw: children[0],
+                ne: children[1],
+                sw: children[2],
+                se: children[3],
+                ..*n
+            });
+            index.insert(children, new_id);
+            remap.insert(id, new_id);
+        }
+        self.results = self
+            .results
+            .iter()
+            .filter_map(|((id, j), result)| match (remap.get(id), remap.get(result)) {
+                (Some(id), Some(result)) => Some(((*id, *j), *result)),
+                _ => None,
+            })
+            .collect();
+        self.nodes = nodes;
+        self.index = index;
+        self.empty.clear();
+        remap[&root]
+    }
+}
+
+impl Evolution for HashLife {
+    fn advance(
+        &mut self,
+        u: &mut Universe,
+        rule: &Rule,
+        generations: u64,
+    ) -> Result<(), AdvanceErr> {
+        if !life_like(rule) {
+            return Sparse.advance(u, rule, generations);
+        }
+        u.age.checked_add(generations).ok_or(AdvanceErr)?;
+        if generations == 0 {
+            return Ok(());
+        }
+        self.set_rule(rule);
+        let Some(mut root) = self.load(u) else {
+            if generations > MAX_SPARSE_GENERATIONS {
+                return Err(AdvanceErr);
+            }
+            return Sparse.advance(u, rule, generations);
+        };
+        // The powers of two left to jump, the smallest last. One that can't
+        // be made at once is made in two halves.
+        let mut jumps: Vec<u8> = (0..64).rev().filter(|j| generations >> j & 1 == 1).collect();
+        let mut done = 0;
+        let mut collected = false;
+        while let Some(j) = jumps.pop() {
+            match self.step(root, j) {
+                Some(result) => {
+                    root = result;
+                    done += 1 << j;
+                }
+                // The cap was reached with garbage to collect first.
+                None if !collected && self.nodes.len() > self.max_nodes => jumps.push(j),
+                None if j > 0 => jumps.extend([j - 1, j - 1]),
+                None => break,
+            }
+            collected = self.nodes.len() > self.max_nodes;
+            if collected {
+                root = self.gc(root);
+            }
+        }
+        self.store(root, u);
+        u.age += done;
+        if generations - done > MAX_SPARSE_GENERATIONS {
+            return Err(AdvanceErr);
+        }
+        Sparse.advance(u, rule, generations - done)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        evolution::Sparse, geometry::poligon::rect::Rect, neighbor::Neighborhood,
+        preset::get_preset_unsafe, rule, topology::Topology, universe::from_string,
+    };
+
+    use super::*;
+
+    fn assert_same_as_sparse(preset: &str, rule: &Rule, generations: u64, hashlife: &mut HashLife) {
+        let mut expected = get_preset_unsafe(preset);
+        Sparse.advance(&mut expected, rule, generations).unwrap();
+        let mut u = get_preset_unsafe(preset);
+        hashlife.advance(&mut u, rule, generations).unwrap();
+        assert_eq!(u, expected);
+    }
+
+    #[test]
+    fn test_advance() {
+        let mut hashlife = HashLife::default();
+        for preset in
+            ["block", "blinker", "boat", "glider", "r_pentomino", "gosper_glider_gun", "puffer1"]
+        {
+            for generations in [0, 1, 2, 3, 7, 30, 101] {
+                assert_same_as_sparse(preset, &Rule::default(), generations, &mut hashlife);
+            }
+        }
+    }
+
+    #[test]
+    fn test_advance_rule() {
+        let mut hashlife = HashLife::default();
+        assert_same_as_sparse("r_pentomino", &Rule::of(&[3, 6], &[2, 3]), 50, &mut hashlife);
+        assert_same_as_sparse("r_pentomino", &Rule::of(&[2], &[]), 20, &mut hashlife);
+        assert_same_as_sparse("r_pentomino", &Rule::default(), 50, &mut hashlife);
+    }
+
+    #[test]
+    fn test_advance_by_power_of_two() {
+        let mut hashlife = HashLife::default();
+        let mut u = get_preset_unsafe("glider");
+        hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 10).unwrap();
+        assert_eq!(u.age, 1024);
+        assert_eq!(u.value.len(), 5);
+        assert_eq!(get_bounds(&u), Some(Rect::of(255, -257, 257, -255)));
+        hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 0).unwrap();
+        let mut expected = get_preset_unsafe("glider");
+        Sparse.advance(&mut expected, &Rule::default(), 1025).unwrap();
+        assert_eq!(u, expected);
+    }
+
+    #[test]
+    fn test_advance_by_large_power_of_two() {
+        let mut hashlife = HashLife::default();
+        let mut u = get_preset_unsafe("glider");
+        hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 62).unwrap();
+        assert_eq!(u.age, 1 << 62);
+        let far = 1_i64 << 60;
+        let expected: HashMap<CartesianP, State> = get_preset_unsafe("glider")
+            .value
+            .into_iter()
+            .map(|(p, s)| (CartesianP::of(p.x + far, p.y - far), s))
+            .collect();
+        assert_eq!(u.value, expected);
+        let mut u = get_preset_unsafe("block");
+        assert_eq!(hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 63), Err(AdvanceErr));
+        assert_eq!(u, get_preset_unsafe("block"));
+        hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 62).unwrap();
+        hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 62).unwrap();
+        assert_eq!(u.age, 1 << 63);
+        assert_eq!(hashlife.advance_by_power_of_two(&mut u, &Rule::default(), 63), Err(AdvanceErr));
+        assert_eq!(hashlife.advance(&mut u, &Rule::default(), 1 << 63), Err(AdvanceErr));
+        assert_eq!(u.age, 1 << 63);
+        assert_eq!(u.value, get_preset_unsafe("block").value);
+    }
+
+    #[test]
+    fn test_advance_too_far() {
+        let mut u = get_preset_unsafe("glider");
+        assert_eq!(
+            HashLife::default().advance(&mut u, &Rule::default(), u64::MAX),
+            Err(AdvanceErr)
+        );
+        assert!(u.age < u64::MAX);
+        assert_eq!(u.value.len(), 5);
+    }
+
+    #[test]
+    fn test_advance_beyond_coordinates() {
+        let far = 1_i64 << 61;
+        let mut u = Universe::from(HashMap::from([
+            (CartesianP::of(far, 0), State::Alive),
+            (CartesianP::of(far, 1), State::Alive),
+            (CartesianP::of(far, 2), State::Alive),
+        ]));
+        HashLife::default().advance(&mut u, &Rule::default(), 3).unwrap();
+        assert_eq!(u.age, 3);
+        assert_eq!(
+            u.value,
+            HashMap::from([
+                (CartesianP::of(far - 1, 1), State::Alive),
+                (CartesianP::of(far, 1), State::Alive),
+                (CartesianP::of(far + 1, 1), State::Alive),
+            ])
+        );
+        assert_eq!(HashLife::default().advance(&mut u, &Rule::default(), 1 << 20), Err(AdvanceErr));
+        assert_eq!(u.age, 3);
+    }
+
+    #[test]
+    fn test_advance_far() {
+        let mut hashlife = HashLife::default();
+        let mut near = get_preset_unsafe("gosper_glider_gun");
+        Sparse.advance(&mut near, &Rule::default(), 316).unwrap();
+        let mut u = get_preset_unsafe("gosper_glider_gun");
+        hashlife.advance(&mut u, &Rule::default(), 1 << 20).unwrap();
+        assert_eq!(u.age, 1 << 20);
+        assert_eq!(u.value.len(), near.value.len() + 5 * ((1 << 20) - 316) / 30);
+    }
+
+    #[test]
+    fn test_gc() {
+        let mut hashlife = HashLife::new(1000);
+        for generations in [10, 100, 200] {
+            assert_same_as_sparse("r_pentomino", &Rule::default(), generations, &mut hashlife);
+            assert!(hashlife.node_count() <= 1000);
+        }
+        assert_same_as_sparse("gosper_glider_gun", &Rule::default(), 300, &mut hashlife);
+        let mut hashlife = HashLife::new(1000);
+        let mut u = get_preset_unsafe("glider");
+        hashlife.advance(&mut u, &Rule::default(), 1 << 30).unwrap();
+        assert_eq!(u.value.len(), 5);
+        assert!(hashlife.node_count() <= 1000);
+    }
+
+    #[test]
+    fn test_advance_topology() {
+        let torus = Rule { topology: Topology::Torus { width: 8, height: 8 }, ..Rule::default() };
+        assert_same_as_sparse("glider", &torus, 40, &mut HashLife::default());
+    }
+
+    #[test]
+    fn test_advance_generations() {
+        let mut brians_brain = Rule::of(&[2], &[]);
+        brians_brain.states = 3;
+        assert_same_as_sparse("r_pentomino", &brians_brain, 30, &mut HashLife::default());
+    }
+
+    #[test]
+    fn test_advance_neighborhood() {
+        let hexagonal = Rule { neighborhood: Neighborhood::Hexagonal, ..Rule::of(&[2], &[3, 4]) };
+        assert_same_as_sparse("r_pentomino", &hexagonal, 30, &mut HashLife::default());
+        let tlife = rule::from_string("B3/S2-i34q").unwrap();
+        assert_same_as_sparse("r_pentomino", &tlife, 30, &mut HashLife::default());
+    }
+
+    #[test]
+    fn test_advance_empty() {
+        let mut hashlife = HashLife::default();
+        let mut u = from_string(vec![String::from("⬜")]).unwrap();
+        hashlife.advance(&mut u, &Rule::default(), 5).unwrap();
+        assert_eq!(u, Universe { value: HashMap::new(), age: 5, ..Default::default() });
+    }
+}
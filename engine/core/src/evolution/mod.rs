@@ -0,0 +1,103 @@
+use std::fmt;
+
+use super::{
+    neighbor::Neighborhood,
+    rule::{Rule, RuleKind},
+    topology::Topology,
+    universe::{iterate, Universe},
+};
+
+pub mod hashlife;
+pub mod tiled;
+
+#[derive(Debug, PartialEq)]
+pub struct AdvanceErr;
+
+impl fmt::Display for AdvanceErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The pattern can't be advanced that far!")
+    }
+}
+
+pub trait Evolution {
+    /// Fails when the age would overflow, or when the jump can't be made in
+    /// any reasonable time, leaving the universe as far as it got.
+    fn advance(
+        &mut self,
+        u: &mut Universe,
+        rule: &Rule,
+        generations: u64,
+    ) -> Result<(), AdvanceErr>;
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Algorithm {
+    Sparse,
+    HashLife,
+    Tiled,
+}
+
+pub struct Sparse;
+
+impl Evolution for Sparse {
+    fn advance(
+        &mut self,
+        u: &mut Universe,
+        rule: &Rule,
+        generations: u64,
+    ) -> Result<(), AdvanceErr> {
+        u.age.checked_add(generations).ok_or(AdvanceErr)?;
+        for _ in 0..generations {
+            iterate(u, rule);
+        }
+        Ok(())
+    }
+}
+
+/// The two state rules on the plane counting the Moore neighborhood, which
+/// are the only ones the faster algorithms know.
+pub fn life_like(rule: &Rule) -> bool {
+    rule.kind == RuleKind::Totalistic
+        && rule.topology == Topology::Plane
+        && rule.states == 2
+        && rule.neighborhood == Neighborhood::default()
+}
+
+pub fn build_evolution(algorithm: Algorithm) -> Box<dyn Evolution> {
+    match algorithm {
+        Algorithm::Sparse => Box::new(Sparse),
+        Algorithm::HashLife => Box::new(hashlife::HashLife::default()),
+        Algorithm::Tiled => Box::new(tiled::Tiled::default()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::preset::get_preset_unsafe;
+
+    use super::*;
+
+    #[test]
+    fn test_sparse() {
+        let mut u = get_preset_unsafe("blinker");
+        assert_eq!(Sparse.advance(&mut u, &Rule::default(), 0), Ok(()));
+        assert_eq!(u, get_preset_unsafe("blinker"));
+        assert_eq!(Sparse.advance(&mut u, &Rule::default(), 2), Ok(()));
+        let mut blinker = get_preset_unsafe("blinker");
+        blinker.age = 2;
+        assert_eq!(u, blinker);
+        assert_eq!(Sparse.advance(&mut u, &Rule::default(), u64::MAX), Err(AdvanceErr));
+        assert_eq!(u, blinker);
+    }
+
+    #[test]
+    fn test_build_evolution() {
+        for algorithm in [Algorithm::Sparse, Algorithm::HashLife, Algorithm::Tiled] {
+            let mut u = get_preset_unsafe("glider");
+            let mut expected = get_preset_unsafe("glider");
+            build_evolution(algorithm).advance(&mut u, &Rule::default(), 12).unwrap();
+            Sparse.advance(&mut expected, &Rule::default(), 12).unwrap();
+            assert_eq!(u, expected);
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cell::State, geometry::coordinate::CartesianP, region, rule::Rule, universe::Universe,
+};
+
+use super::{life_like, AdvanceErr, Evolution, Sparse};
+
+pub const TILE_SIZE: i64 = 64;
+
+/// The rows of a tile from the bottom up, where bit `i` of a row is the cell
+/// `i` columns from the left.
+pub type Tile = [u64; 64];
+
+const EMPTY: Tile = [0; 64];
+
+/// The alive cells packed into tiles, keyed by the coordinates of the tile,
+/// so `(1, -1)` holds the cells from `(64, -64)` to `(127, -1)`. Only the
+/// tiles around the ones that changed in the last generation can change in
+/// the next one, so the others are skipped.
+#[derive(Debug, Clone, Default)]
+pub struct TiledUniverse {
+    pub tiles: HashMap<CartesianP, Tile>,
+    pub age: u64,
+    changed: HashSet<CartesianP>,
+}
+
+impl From<&Universe> for TiledUniverse {
+    fn from(u: &Universe) -> Self {
+        let mut tiles = HashMap::<CartesianP, Tile>::new();
+        for p in u.value.iter().filter(|(_, s)| **s == State::Alive).map(|(p, _)| p) {
+            let key = CartesianP::of(p.x.div_euclid(TILE_SIZE), p.y.div_euclid(TILE_SIZE));
+            let tile = tiles.entry(key).or_insert(EMPTY);
+            tile[p.y.rem_euclid(TILE_SIZE) as usize] |= 1 << p.x.rem_euclid(TILE_SIZE);
+        }
+        let changed = tiles.keys().copied().collect();
+        TiledUniverse { tiles, age: u.age, changed }
+    }
+}
+
+impl From<&TiledUniverse> for Universe {
+    fn from(u: &TiledUniverse) -> Self {
+        let mut value = HashMap::<CartesianP, State>::new();
+        for (key, tile) in &u.tiles {
+            for (row, bits) in tile.iter().enumerate() {
+                for col in (0..TILE_SIZE).filter(|col| bits & 1 << col != 0) {
+                    let p = CartesianP::of(key.x * TILE_SIZE + col, key.y * TILE_SIZE + row as i64);
+                    value.insert(p, State::Alive);
+                }
+            }
+        }
+        Universe { value, age: u.age, ..Default::default() }
+    }
+}
+
+fn counts(values: &[bool]) -> Vec<usize> {
+    values.iter().enumerate().filter(|(_, value)| **value).map(|(n, _)| n).collect()
+}
+
+/// Adds one bit to each of the 64 counters held across the bit planes.
+fn add(sum: &mut [u64; 4], bits: u64) {
+    let mut carry = bits;
+    for plane in sum.iter_mut() {
+        let next = *plane & carry;
+        *plane ^= carry;
+        carry = next;
+    }
+}
+
+/// The bits of the counters equal to `n`.
+fn equal(sum: &[u64; 4], n: usize) -> u64 {
+    sum.iter()
+        .enumerate()
+        .fold(!0, |acc, (bit, plane)| acc & if n >> bit & 1 == 1 { *plane } else { !*plane })
+}
+
+/// The next generation of the tile at `key`, counting the neighbors of a
+/// whole row at once.
+pub fn next_tile(
+    tiles: &HashMap<CartesianP, Tile>,
+    key: &CartesianP,
+    birth: &[usize],
+    survival: &[usize],
+) -> Tile {
+    let get =
+        |dx: i64, dy: i64| tiles.get(&CartesianP::of(key.x + dx, key.y + dy)).unwrap_or(&EMPTY);
+    let (sw, s, se) = (get(-1, -1), get(0, -1), get(1, -1));
+    let (w, c, e) = (get(-1, 0), get(0, 0), get(1, 0));
+    let (nw, n, ne) = (get(-1, 1), get(0, 1), get(1, 1));
+    let row = |r: i64| match r {
+        -1 => (sw[63], s[63], se[63]),
+        64 => (nw[0], n[0], ne[0]),
+        r => (w[r as usize], c[r as usize], e[r as usize]),
+    };
+    let mut result = EMPTY;
+    for (r, next) in result.iter_mut().enumerate() {
+        let r = r as i64;
+        let mut sum = [0; 4];
+        for dy in [-1, 0, 1] {
+            let (west, center, east) = row(r + dy);
+            add(&mut sum, center << 1 | west >> 63);
+            add(&mut sum, center >> 1 | east << 63);
+            if dy != 0 {
+                add(&mut sum, center);
+            }
+        }
+        let alive = c[r as usize];
+        let born = birth.iter().fold(0, |acc, n| acc | equal(&sum, *n));
+        let survives = survival.iter().fold(0, |acc, n| acc | equal(&sum, *n));
+        *next = !alive & born | alive & survives;
+    }
+    result
+}
+
+/// The tiles that may change, which are the ones around the last changes.
+fn candidates(u: &TiledUniverse) -> HashSet<CartesianP> {
+    u.changed
+        .iter()
+        .flat_map(|k| {
+            (-1..=1).flat_map(move |dy| (-1..=1).map(move |dx| CartesianP::of(k.x + dx, k.y + dy)))
+        })
+        .collect()
+}
+
+/// Moves a Life-like rule to the next generation.
+pub fn iterate(u: &mut TiledUniverse, rule: &Rule) {
+    let birth = counts(&rule.birth);
+    let survival = counts(&rule.survival);
+    let results: Vec<(CartesianP, Tile)> = candidates(u)
+        .into_iter()
+        .map(|key| {
+            let next = next_tile(&u.tiles, &key, &birth, &survival);
+            (key, next)
+        })
+        .collect();
+    u.changed.clear();
+    for (key, next) in results {
+        let empty = next == EMPTY;
+        match u.tiles.get(&key) {
+            Some(tile) if *tile == next => continue,
+            None if empty => continue,
+            _ => {}
+        }
+        u.changed.insert(key);
+        if empty {
+            u.tiles.remove(&key);
+        } else {
+            u.tiles.insert(key, next);
+        }
+    }
+    u.age += 1;
+}
+
+/// Writes to `value` the cells of the tiles that differ from what they were.
+fn write_back(
+    value: &mut HashMap<CartesianP, State>,
+    tiles: &HashMap<CartesianP, Tile>,
+    before: HashMap<CartesianP, Tile>,
+) {
+    for (key, old) in before {
+        let new = tiles.get(&key).unwrap_or(&EMPTY);
+        for (row, (old, new)) in old.iter().zip(new).enumerate() {
+            for col in (0..TILE_SIZE).filter(|col| (old ^ new) & 1 << col != 0) {
+                let p = CartesianP::of(key.x * TILE_SIZE + col, key.y * TILE_SIZE + row as i64);
+                if new & 1 << col != 0 {
+                    value.insert(p, State::Alive);
+                } else {
+                    value.remove(&p);
+                }
+            }
+        }
+    }
+}
+
+/// Evolution on bit-packed tiles, which pays off on dense patterns. Rules
+/// other than Life-like ones on the plane fall back to the sparse algorithm.
+/// The tiles are kept between calls along with the mark left on the
+/// universe, so a universe only advanced by this evolution skips its still
+/// tiles and writes back only the cells that changed.
+#[derive(Default)]
+pub struct Tiled {
+    last: Option<(u64, TiledUniverse)>,
+}
+
+impl Evolution for Tiled {
+    fn advance(
+        &mut self,
+        u: &mut Universe,
+        rule: &Rule,
+        generations: u64,
+    ) -> Result<(), AdvanceErr> {
+        if !life_like(rule) {
+            return Sparse.advance(u, rule, generations);
+        }
+        u.age.checked_add(generations).ok_or(AdvanceErr)?;
+        let mut tiled = match self.last.take() {
+            Some((mark, tiled)) if region::is_marked(&u.regions, mark) && tiled.age == u.age => {
+                tiled
+            }
+            _ => TiledUniverse::from(&*u),
+        };
+        let mut before = HashMap::new();
+        for _ in 0..generations {
+            for key in candidates(&tiled) {
+                before.entry(key).or_insert_with(|| *tiled.tiles.get(&key).unwrap_or(&EMPTY));
+            }
+            iterate(&mut tiled, rule);
+        }
+        write_back(&mut u.value, &tiled.tiles, before);
+        u.age = tiled.age;
+        self.last = Some((region::mark(&mut u.regions), tiled));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{preset::get_preset_unsafe, rule, universe::toggle_cell};
+
+    use super::*;
+
+    fn soup(size: i64) -> Universe {
+        let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut value = HashMap::new();
+        for y in -size / 2..size / 2 {
+            for x in -size / 2..size / 2 {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                if seed & 1 == 1 {
+                    value.insert(CartesianP::of(x, y), State::Alive);
+                }
+            }
+        }
+        Universe::from(value)
+    }
+
+    #[test]
+    fn test_from() {
+        let u = Universe::from(HashMap::from([
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(63, 63), State::Alive),
+            (CartesianP::of(64, 0), State::Alive),
+            (CartesianP::of(-1, -1), State::Alive),
+            (CartesianP::of(-64, 5), State::Alive),
+            (CartesianP::of(-65, 5), State::Alive),
+        ]));
+        let tiled = TiledUniverse::from(&u);
+        assert_eq!(tiled.tiles.len(), 5);
+        assert_eq!(tiled.tiles[&CartesianP::of(0, 0)][63], 1 << 63);
+        assert_eq!(tiled.tiles[&CartesianP::of(-1, -1)][63], 1 << 63);
+        assert_eq!(tiled.tiles[&CartesianP::of(-1, 0)][5], 1);
+        assert_eq!(tiled.tiles[&CartesianP::of(-2, 0)][5], 1 << 63);
+        assert_eq!(Universe::from(&tiled), u);
+        for preset in ["block", "glider", "gosper_glider_gun", "puffer1"] {
+            let u = get_preset_unsafe(preset);
+            assert_eq!(Universe::from(&TiledUniverse::from(&u)), u);
+        }
+    }
+
+    #[test]
+    fn test_iterate() {
+        for rule in [Rule::default(), rule::from_string("B36/S23").unwrap()] {
+            for mut u in [soup(100), get_preset_unsafe("gosper_glider_gun")] {
+                let mut tiled = TiledUniverse::from(&u);
+                for _ in 0..40 {
+                    iterate(&mut tiled, &rule);
+                }
+                Sparse.advance(&mut u, &rule, 40).unwrap();
+                assert_eq!(Universe::from(&tiled), u);
+            }
+        }
+    }
+
+    #[test]
+    fn test_iterate_skips_still_tiles() {
+        let mut tiled = TiledUniverse::from(&get_preset_unsafe("block"));
+        assert!(!candidates(&tiled).is_empty());
+        iterate(&mut tiled, &Rule::default());
+        assert!(candidates(&tiled).is_empty());
+        iterate(&mut tiled, &Rule::default());
+        assert_eq!(Universe::from(&tiled).value, get_preset_unsafe("block").value);
+        assert_eq!(tiled.age, 2);
+    }
+
+    #[test]
+    fn test_tiled() {
+        let mut u = get_preset_unsafe("glider");
+        let mut expected = u.clone();
+        Tiled::default().advance(&mut u, &Rule::default(), 300).unwrap();
+        Sparse.advance(&mut expected, &Rule::default(), 300).unwrap();
+        assert_eq!(u, expected);
+        let brians_brain = Rule { states: 3, ..rule::from_string("B2/S").unwrap() };
+        let mut u = get_preset_unsafe("glider");
+        let mut expected = u.clone();
+        Tiled::default().advance(&mut u, &brians_brain, 5).unwrap();
+        Sparse.advance(&mut expected, &brians_brain, 5).unwrap();
+        assert_eq!(u, expected);
+    }
+
+    #[test]
+    fn test_tiled_keeps_tiles() {
+        let rule = Rule::default();
+        let mut tiled = Tiled::default();
+        let mut u = get_preset_unsafe("gosper_glider_gun");
+        let mut expected = u.clone();
+        for step in 0..10 {
+            if step == 5 {
+                toggle_cell(&mut u, CartesianP::of(0, 0));
+                toggle_cell(&mut expected, CartesianP::of(0, 0));
+            }
+            tiled.advance(&mut u, &rule, 7).unwrap();
+            Sparse.advance(&mut expected, &rule, 7).unwrap();
+            assert_eq!(u, expected);
+        }
+        // The tiles of another universe aren't taken for this one's.
+        let mut other = get_preset_unsafe("glider");
+        let mut expected = other.clone();
+        tiled.advance(&mut other, &rule, 7).unwrap();
+        Sparse.advance(&mut expected, &rule, 7).unwrap();
+        assert_eq!(other, expected);
+        let mut block = get_preset_unsafe("block");
+        tiled.advance(&mut block, &rule, 1).unwrap();
+        tiled.advance(&mut block, &rule, 1).unwrap();
+        assert!(tiled.last.as_ref().is_some_and(|(_, tiles)| candidates(tiles).is_empty()));
+        assert_eq!(block.value, get_preset_unsafe("block").value);
+    }
+}
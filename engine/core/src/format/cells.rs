@@ -0,0 +1,143 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::{matrix_to_cartesian, CartesianP, MatrixP},
+    universe::{get_bounds, get_value, Universe},
+};
+
+use super::{centered_cam, InvalidCharacterErr, Pattern};
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidCharacter(InvalidCharacterErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidCharacter(e) => e.fmt(f),
+        }
+    }
+}
+
+pub fn from_string(as_str: &str) -> Result<Pattern, FromStringErr> {
+    let mut pattern = Pattern::default();
+    let mut alive: Vec<MatrixP> = Vec::new();
+    let mut row: u64 = 0;
+    let mut width: u64 = 0;
+    for (i, line) in as_str.lines().enumerate() {
+        let line = line.trim_end();
+        if let Some(comment) = line.strip_prefix('!') {
+            let comment = comment.trim();
+            if let Some(name) = comment.strip_prefix("Name:") {
+                pattern.name = Some(String::from(name.trim()));
+            } else if let Some(author) = comment.strip_prefix("Author:") {
+                pattern.author = Some(String::from(author.trim()));
+            } else {
+                pattern.comments.push(String::from(comment));
+            }
+            continue;
+        }
+        for (j, c) in line.chars().enumerate() {
+            match c {
+                '.' => {}
+                'O' | '*' => alive.push(MatrixP { row, col: j as u64 }),
+                _ => {
+                    return Err(FromStringErr::InvalidCharacter(InvalidCharacterErr {
+                        line: i + 1,
+                        col: j + 1,
+                    }))
+                }
+            }
+        }
+        width = width.max(line.chars().count() as u64);
+        row += 1;
+    }
+    let height = alive.iter().map(|p| p.row + 1).max().unwrap_or(0);
+    let cam = centered_cam(width, height);
+    let value: HashMap<CartesianP, State> =
+        alive.iter().map(|p| (matrix_to_cartesian(p, &cam), State::Alive)).collect();
+    pattern.universe = Universe::from(value);
+    Ok(pattern)
+}
+
+pub fn to_string(u: &Universe) -> String {
+    let mut result = String::new();
+    if let Some(r) = get_bounds(u) {
+        for y in (r.y1..=r.y2).rev() {
+            for x in r.x1..=r.x2 {
+                result.push(match get_value(u, &CartesianP::of(x, y)) {
+                    State::Alive => 'O',
+                    State::Dead | State::Dying(_) => '.',
+                });
+            }
+            result.push('\n');
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{preset::get_preset_unsafe, universe::from_string as from_grid};
+
+    use super::*;
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            from_string("!Name: Glider\n.O.\n..O\nOoO\n"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 4, col: 2 }))
+        );
+        assert_eq!(
+            from_string(".O.\n..O\nOOO!\n"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 3, col: 4 }))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        let glider = from_string(
+            "!Name: Glider\n!Author: Richard K. Guy\n!The smallest spaceship.\n.O.\n..O\nOOO\n",
+        )
+        .unwrap();
+        assert_eq!(
+            glider,
+            Pattern {
+                universe: from_grid(vec![
+                    String::from("⬛⬜⬛"),
+                    String::from("⬛⬛⬜"),
+                    String::from("⬜⬜⬜"),
+                ])
+                .unwrap(),
+                rule: None,
+                name: Some(String::from("Glider")),
+                author: Some(String::from("Richard K. Guy")),
+                comments: vec![String::from("The smallest spaceship.")],
+            }
+        );
+        assert_eq!(from_string(".O\r\n..O\r\nOOO").unwrap().universe, glider.universe);
+        assert_eq!(from_string(".*.\n..*\n***\n").unwrap().universe, glider.universe);
+        assert_eq!(from_string("!Empty\n\n...\n").unwrap().universe, Universe::default());
+        let gap = from_string("O\n\n\n..O\n").unwrap().universe;
+        assert_eq!(gap.value.len(), 2);
+        assert_eq!(get_bounds(&gap).map(|r| (r.x2 - r.x1, r.y2 - r.y1)), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&Universe::default()), "");
+        assert_eq!(to_string(&get_preset_unsafe("glider")), "O.O\n.OO\n.O.\n");
+        assert_eq!(to_string(&get_preset_unsafe("block")), "OO\nOO\n");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for preset in ["block", "blinker", "glider", "r_pentomino", "gosper_glider_gun"] {
+            let u = get_preset_unsafe(preset);
+            let pattern = from_string(&to_string(&u)).unwrap();
+            assert_eq!(to_string(&pattern.universe), to_string(&u));
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::CartesianP,
+    neighbor::Neighborhood,
+    rule::{self, Rule, RuleKind},
+    universe::{get_bounds, get_value, Universe},
+};
+
+use super::{InvalidCharacterErr, Pattern};
+
+pub const HEADER: &str = "#Life 1.05";
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidBlockErr {
+    pub line: usize,
+}
+
+impl fmt::Display for InvalidBlockErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid block at line {}, expected \"#P x y\"!", self.line)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidCharacter(InvalidCharacterErr),
+    InvalidBlock(InvalidBlockErr),
+    InvalidRule(rule::FromStringErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidCharacter(e) => e.fmt(f),
+            FromStringErr::InvalidBlock(e) => e.fmt(f),
+            FromStringErr::InvalidRule(e) => e.fmt(f),
+        }
+    }
+}
+
+fn parse_block(line: &str, line_number: usize) -> Result<(i64, i64), FromStringErr> {
+    let err = || FromStringErr::InvalidBlock(InvalidBlockErr { line: line_number });
+    let values: Vec<i64> =
+        line.split_whitespace().map(|n| n.parse().map_err(|_| err())).collect::<Result<_, _>>()?;
+    match values[..] {
+        [x, y] => Ok((x, y)),
+        _ => Err(err()),
+    }
+}
+
+/// Life 1.05 has blocks of `.` and `*` rows, each placed by a `#P x y` line
+/// at its top left corner, with y pointing down.
+pub fn from_string(as_str: &str) -> Result<Pattern, FromStringErr> {
+    let mut pattern = Pattern::default();
+    let mut value: HashMap<CartesianP, State> = HashMap::new();
+    let mut origin: (i64, i64) = (0, 0);
+    let mut row: i64 = 0;
+    for (i, line) in as_str.lines().enumerate() {
+        let line = line.trim_end();
+        if line.starts_with(HEADER) {
+            continue;
+        }
+        if let Some(directive) = line.strip_prefix('#') {
+            let mut chars = directive.chars();
+            let kind = chars.next();
+            let text = chars.as_str().trim();
+            match kind {
+                Some('P') => {
+                    origin = parse_block(text, i + 1)?;
+                    row = 0;
+                }
+                Some('N') => pattern.rule = Some(Rule::default()),
+                Some('R') => {
+                    pattern.rule =
+                        Some(rule::from_string(text).map_err(FromStringErr::InvalidRule)?)
+                }
+                Some('D') | Some('C') => pattern.comments.push(String::from(text)),
+                _ => {}
+            }
+            continue;
+        }
+        for (j, c) in line.chars().enumerate() {
+            let err =
+                || FromStringErr::InvalidCharacter(InvalidCharacterErr { line: i + 1, col: j + 1 });
+            match c {
+                '.' => {}
+                '*' => {
+                    // A cell past the `i64` coordinates can't be placed.
+                    let x = i64::try_from(j).ok().and_then(|j| origin.0.checked_add(j));
+                    let y = origin.1.checked_add(row).and_then(i64::checked_neg);
+                    let (Some(x), Some(y)) = (x, y) else {
+                        return Err(err());
+                    };
+                    value.insert(CartesianP::of(x, y), State::Alive);
+                }
+                _ => return Err(err()),
+            }
+        }
+        row += 1;
+    }
+    pattern.universe = Universe::from(value);
+    Ok(pattern)
+}
+
+fn digits(values: &[bool]) -> String {
+    (0..values.len()).filter(|n| values[*n]).map(|n| n.to_string()).collect()
+}
+
+pub fn to_string(u: &Universe, rule: &Rule) -> String {
+    let mut result = format!("{HEADER}\n");
+    if rule == &Rule::default() {
+        result.push_str("#N\n");
+    } else if rule.kind != RuleKind::Totalistic
+        || rule.states > 2
+        || rule.neighborhood != Neighborhood::default()
+    {
+        result.push_str(&format!("#R {rule}\n"));
+    } else {
+        result.push_str(&format!(
+            "#R {}/{}{}\n",
+            digits(&rule.survival),
+            digits(&rule.birth),
+            rule.topology
+        ));
+    }
+    if let Some(r) = get_bounds(u) {
+        result.push_str(&format!("#P {} {}\n", r.x1, -r.y2));
+        for y in (r.y1..=r.y2).rev() {
+            let line: String = (r.x1..=r.x2)
+                .map(|x| match get_value(u, &CartesianP::of(x, y)) {
+                    State::Alive => '*',
+                    State::Dead | State::Dying(_) => '.',
+                })
+                .collect();
+            let line = line.trim_end_matches('.');
+            result.push_str(if line.is_empty() { "." } else { line });
+            result.push('\n');
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{preset::get_preset_unsafe, topology::Topology};
+
+    use super::*;
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", InvalidBlockErr { line: 2 }),
+            "Invalid block at line 2, expected \"#P x y\"!"
+        );
+        assert_eq!(
+            from_string("#Life 1.05\n#P 0\n*\n"),
+            Err(FromStringErr::InvalidBlock(InvalidBlockErr { line: 2 }))
+        );
+        assert_eq!(
+            from_string("#Life 1.05\n#P a 0\n*\n"),
+            Err(FromStringErr::InvalidBlock(InvalidBlockErr { line: 2 }))
+        );
+        assert_eq!(
+            from_string("#Life 1.05\n#P 0 0\n.*.\n..O\n"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 4, col: 3 }))
+        );
+        assert!(matches!(from_string("#Life 1.05\n#R 23/9\n"), Err(FromStringErr::InvalidRule(_))));
+        let err =
+            |line, col| Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line, col }));
+        assert_eq!(from_string("#Life 1.05\n#P 9223372036854775807 0\n.**\n"), err(3, 2));
+        assert_eq!(from_string("#Life 1.05\n#P 0 9223372036854775807\n*\n*\n"), err(4, 1));
+        assert_eq!(from_string("#Life 1.05\n#P 0 -9223372036854775808\n*\n"), err(3, 1));
+    }
+
+    #[test]
+    fn test_from_string() {
+        let glider = from_string("#Life 1.05\n#D Glider\n#N\n#P -1 -1\n.*\n..*\n***\n").unwrap();
+        assert_eq!(
+            glider,
+            Pattern {
+                universe: Universe::from(HashMap::from([
+                    (CartesianP::of(0, 1), State::Alive),
+                    (CartesianP::of(1, 0), State::Alive),
+                    (CartesianP::of(-1, -1), State::Alive),
+                    (CartesianP::of(0, -1), State::Alive),
+                    (CartesianP::of(1, -1), State::Alive),
+                ])),
+                rule: Some(Rule::default()),
+                name: None,
+                author: None,
+                comments: vec![String::from("Glider")],
+            }
+        );
+        let blocks = from_string("#Life 1.05\n#R 23/36\n#P 0 0\n**\n#P 10 -5\n.\n*\n").unwrap();
+        assert_eq!(blocks.rule, Some(Rule::of(&[3, 6], &[2, 3])));
+        assert_eq!(
+            blocks.universe,
+            Universe::from(HashMap::from([
+                (CartesianP::of(0, 0), State::Alive),
+                (CartesianP::of(1, 0), State::Alive),
+                (CartesianP::of(10, 4), State::Alive),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&Universe::default(), &Rule::default()), "#Life 1.05\n#N\n");
+        let mut rule = Rule::of(&[3, 6], &[2, 3]);
+        rule.topology = Topology::Torus { width: 8, height: 8 };
+        assert_eq!(
+            to_string(&get_preset_unsafe("glider"), &rule),
+            "#Life 1.05\n#R 23/36:T8,8\n#P -1 -1\n*.*\n.**\n.*\n"
+        );
+        let hexagonal = Rule { neighborhood: Neighborhood::Hexagonal, ..Rule::of(&[2], &[3, 4]) };
+        let text = to_string(&get_preset_unsafe("block"), &hexagonal);
+        assert_eq!(text, "#Life 1.05\n#R B2/S34H\n#P -1 0\n**\n**\n");
+        assert_eq!(from_string(&text).unwrap().rule, Some(hexagonal));
+        let rule30 = Rule { kind: RuleKind::Elementary(30), ..Rule::default() };
+        let text = to_string(&Universe::default(), &rule30);
+        assert_eq!(text, "#Life 1.05\n#R W30\n");
+        assert_eq!(from_string(&text).unwrap().rule, Some(rule30));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for preset in ["block", "blinker", "glider", "r_pentomino", "gosper_glider_gun"] {
+            let u = get_preset_unsafe(preset);
+            let pattern = from_string(&to_string(&u, &Rule::default())).unwrap();
+            assert_eq!(pattern.universe, u);
+            assert_eq!(pattern.rule, Some(Rule::default()));
+        }
+    }
+}
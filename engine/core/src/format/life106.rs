@@ -0,0 +1,150 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{cell::State, geometry::coordinate::CartesianP, universe::Universe};
+
+use super::{InvalidCharacterErr, Pattern};
+
+pub const HEADER: &str = "#Life 1.06";
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidCoordinateErr {
+    pub line: usize,
+}
+
+impl fmt::Display for InvalidCoordinateErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid coordinate at line {}, expected \"x y\"!", self.line)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidCharacter(InvalidCharacterErr),
+    InvalidCoordinate(InvalidCoordinateErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidCharacter(e) => e.fmt(f),
+            FromStringErr::InvalidCoordinate(e) => e.fmt(f),
+        }
+    }
+}
+
+fn parse_coordinate(line: &str, line_number: usize) -> Result<CartesianP, FromStringErr> {
+    if let Some(j) =
+        line.chars().position(|c| !(c == '-' || c.is_ascii_digit() || c.is_whitespace()))
+    {
+        return Err(FromStringErr::InvalidCharacter(InvalidCharacterErr {
+            line: line_number,
+            col: j + 1,
+        }));
+    }
+    let err = || FromStringErr::InvalidCoordinate(InvalidCoordinateErr { line: line_number });
+    let values: Vec<i64> =
+        line.split_whitespace().map(|n| n.parse().map_err(|_| err())).collect::<Result<_, _>>()?;
+    match values[..] {
+        [x, y] => Ok(CartesianP::of(x, -y)),
+        _ => Err(err()),
+    }
+}
+
+/// Life 1.06 lists the coordinates of the alive cells, with y pointing down.
+pub fn from_string(as_str: &str) -> Result<Pattern, FromStringErr> {
+    let mut pattern = Pattern::default();
+    let mut value: HashMap<CartesianP, State> = HashMap::new();
+    for (i, line) in as_str.lines().enumerate() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            if !line.starts_with(HEADER) {
+                pattern.comments.push(String::from(comment.trim()));
+            }
+            continue;
+        }
+        value.insert(parse_coordinate(line, i + 1)?, State::Alive);
+    }
+    pattern.universe = Universe::from(value);
+    Ok(pattern)
+}
+
+pub fn to_string(u: &Universe) -> String {
+    let mut cells: Vec<&CartesianP> =
+        u.value.iter().filter(|(_, s)| **s == State::Alive).map(|(p, _)| p).collect();
+    cells.sort_by_key(|p| (-p.y, p.x));
+    let mut result = format!("{HEADER}\n");
+    for p in cells {
+        result.push_str(&format!("{} {}\n", p.x, -p.y));
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::preset::get_preset_unsafe;
+
+    use super::*;
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", InvalidCoordinateErr { line: 4 }),
+            "Invalid coordinate at line 4, expected \"x y\"!"
+        );
+        assert_eq!(
+            from_string("#Life 1.06\n0 -1\n1 O\n"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 3, col: 3 }))
+        );
+        assert_eq!(
+            from_string("#Life 1.06\n0 -1\n1 0 2\n"),
+            Err(FromStringErr::InvalidCoordinate(InvalidCoordinateErr { line: 3 }))
+        );
+        assert_eq!(
+            from_string("#Life 1.06\n0 1-\n"),
+            Err(FromStringErr::InvalidCoordinate(InvalidCoordinateErr { line: 2 }))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        let glider = from_string("#Life 1.06\n#D Glider\n0 -1\n1 0\n-1 1\n0 1\n1 1\n").unwrap();
+        assert_eq!(
+            glider,
+            Pattern {
+                universe: Universe::from(HashMap::from([
+                    (CartesianP::of(0, 1), State::Alive),
+                    (CartesianP::of(1, 0), State::Alive),
+                    (CartesianP::of(-1, -1), State::Alive),
+                    (CartesianP::of(0, -1), State::Alive),
+                    (CartesianP::of(1, -1), State::Alive),
+                ])),
+                rule: None,
+                name: None,
+                author: None,
+                comments: vec![String::from("D Glider")],
+            }
+        );
+        assert_eq!(
+            from_string("0 -1\n1 0\n-1 1\n0 1\n1 1\n0 1\n").unwrap().universe,
+            glider.universe
+        );
+        assert_eq!(from_string("#Life 1.06\n").unwrap().universe, Universe::default());
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&Universe::default()), "#Life 1.06\n");
+        assert_eq!(to_string(&get_preset_unsafe("block")), "#Life 1.06\n-1 0\n0 0\n-1 1\n0 1\n");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for preset in ["block", "blinker", "glider", "r_pentomino", "gosper_glider_gun"] {
+            let u = get_preset_unsafe(preset);
+            assert_eq!(from_string(&to_string(&u)).unwrap().universe, u);
+        }
+    }
+}
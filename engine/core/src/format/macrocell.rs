@@ -0,0 +1,599 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::CartesianP,
+    rule::{self, Rule},
+    universe::{get_bounds, Universe},
+};
+
+use super::{InvalidCharacterErr, Pattern};
+
+pub const HEADER: &str = "[M2]";
+pub const DEFAULT_CELL_LIMIT: u64 = 1 << 20;
+
+type NodeId = usize;
+
+const EMPTY: NodeId = 0;
+const LEAF_LEVEL: u8 = 3;
+const LEAF_SIZE: usize = 1 << LEAF_LEVEL;
+const MAX_LEVEL: u8 = 62;
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidHeaderErr;
+
+impl fmt::Display for InvalidHeaderErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid header, expected \"{HEADER}\" at the first line!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidNodeErr {
+    pub line: usize,
+}
+
+impl fmt::Display for InvalidNodeErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid node at line {}, expected \"level nw ne sw se\"!", self.line)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TooManyCellsErr {
+    pub population: u64,
+    pub limit: u64,
+}
+
+impl fmt::Display for TooManyCellsErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The pattern has {} cells, more than the limit of {}!",
+            self.population, self.limit
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidHeader(InvalidHeaderErr),
+    InvalidCharacter(InvalidCharacterErr),
+    InvalidNode(InvalidNodeErr),
+    InvalidRule(rule::FromStringErr),
+    TooManyCells(TooManyCellsErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidHeader(e) => e.fmt(f),
+            FromStringErr::InvalidCharacter(e) => e.fmt(f),
+            FromStringErr::InvalidNode(e) => e.fmt(f),
+            FromStringErr::InvalidRule(e) => e.fmt(f),
+            FromStringErr::TooManyCells(e) => e.fmt(f),
+        }
+    }
+}
+
+/// A leaf is an 8x8 square with a byte per row, the lowest bit on the left.
+/// Rules with more than two states have 2x2 squares instead, with the state
+/// of each cell, as Golly writes them.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+enum Node {
+    Leaf([u8; LEAF_SIZE]),
+    Cells([u8; 4]),
+    Branch(u8, [NodeId; 4]),
+}
+
+/// The quadtree of a Macrocell file, where equal squares are stored once, so
+/// that it stays small even for patterns too big to hold cell by cell. The
+/// root is centered on the origin, with y pointing down as in the file.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Macrocell {
+    nodes: Vec<Node>,
+    populations: Vec<u64>,
+    root: NodeId,
+    pub rule: Option<Rule>,
+    pub generation: u64,
+    pub comments: Vec<String>,
+}
+
+impl Default for Macrocell {
+    fn default() -> Self {
+        Macrocell {
+            nodes: vec![Node::Leaf([0; LEAF_SIZE])],
+            populations: vec![0],
+            root: EMPTY,
+            rule: None,
+            generation: 0,
+            comments: Vec::new(),
+        }
+    }
+}
+
+impl Macrocell {
+    fn push(&mut self, node: Node) -> NodeId {
+        let population = match node {
+            Node::Leaf(rows) => rows.iter().map(|row| u64::from(row.count_ones())).sum(),
+            Node::Cells(states) => states.iter().filter(|s| **s != 0).count() as u64,
+            Node::Branch(_, children) => {
+                children.iter().fold(0, |acc: u64, id| acc.saturating_add(self.populations[*id]))
+            }
+        };
+        self.nodes.push(node);
+        self.populations.push(population);
+        self.nodes.len() - 1
+    }
+
+    fn level_of(&self, id: NodeId) -> Option<u8> {
+        match self.nodes[id] {
+            _ if id == EMPTY => None,
+            Node::Leaf(_) => Some(LEAF_LEVEL),
+            Node::Cells(_) => Some(1),
+            Node::Branch(level, _) => Some(level),
+        }
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level_of(self.root).unwrap_or(LEAF_LEVEL)
+    }
+
+    pub fn population(&self) -> u64 {
+        self.populations[self.root]
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    /// Builds the quadtree out of 2x2 leaves when `multistate`, or else out
+    /// of 8x8 ones with the alive cells.
+    pub fn from_universe(u: &Universe, multistate: bool) -> Self {
+        let mut mc = Macrocell::default();
+        let mut level = LEAF_LEVEL;
+        if let Some(r) = get_bounds(u) {
+            let fits = |level: u8| {
+                let half = 1_i64 << (level - 1);
+                r.x1 >= -half && -r.y2 >= -half && r.x2 < half && -r.y1 < half
+            };
+            while !fits(level) {
+                level += 1;
+            }
+        }
+        let cells: Vec<(i64, i64, u8)> = u
+            .value
+            .iter()
+            .filter(|(_, s)| multistate || **s == State::Alive)
+            .map(|(p, s)| (p.x, -p.y, u8::from(*s)))
+            .collect();
+        let half = 1_i64 << (level - 1);
+        let mut index: HashMap<Node, NodeId> = HashMap::new();
+        let leaf_level = if multistate { 1 } else { LEAF_LEVEL };
+        mc.root = mc.build(&mut index, &cells, (-half, -half), level, leaf_level);
+        mc
+    }
+
+    fn build(
+        &mut self,
+        index: &mut HashMap<Node, NodeId>,
+        cells: &[(i64, i64, u8)],
+        (x0, y0): (i64, i64),
+        level: u8,
+        leaf_level: u8,
+    ) -> NodeId {
+        if cells.is_empty() {
+            return EMPTY;
+        }
+        let node = if level == 1 && leaf_level == 1 {
+            let mut states = [0; 4];
+            for (x, y, s) in cells {
+                states[((y - y0) * 2 + (x - x0)) as usize] = *s;
+            }
+            Node::Cells(states)
+        } else if level == LEAF_LEVEL && leaf_level == LEAF_LEVEL {
+            let mut rows = [0; LEAF_SIZE];
+            for (x, y, _) in cells {
+                rows[(y - y0) as usize] |= 1 << (x - x0);
+            }
+            Node::Leaf(rows)
+        } else {
+            let half = 1_i64 << (level - 1);
+            let mut quadrants: [Vec<(i64, i64, u8)>; 4] = Default::default();
+            for (x, y, s) in cells {
+                let i = match (*x < x0 + half, *y < y0 + half) {
+                    (true, true) => 0,
+                    (false, true) => 1,
+                    (true, false) => 2,
+                    (false, false) => 3,
+                };
+                quadrants[i].push((*x, *y, *s));
+            }
+            let [nw, ne, sw, se] = quadrants;
+            let mut build = |cells, origin| self.build(index, cells, origin, level - 1, leaf_level);
+            Node::Branch(
+                level,
+                [
+                    build(&nw, (x0, y0)),
+                    build(&ne, (x0 + half, y0)),
+                    build(&sw, (x0, y0 + half)),
+                    build(&se, (x0 + half, y0 + half)),
+                ],
+            )
+        };
+        if let Some(id) = index.get(&node) {
+            return *id;
+        }
+        let id = self.push(node);
+        index.insert(node, id);
+        id
+    }
+
+    fn collect(&self, id: NodeId, x0: i64, y0: i64, value: &mut HashMap<CartesianP, State>) {
+        if self.populations[id] == 0 {
+            return;
+        }
+        match self.nodes[id] {
+            Node::Leaf(rows) => {
+                for (row, bits) in rows.iter().enumerate() {
+                    for col in 0..LEAF_SIZE {
+                        if bits & (1 << col) != 0 {
+                            let p = CartesianP::of(x0 + col as i64, -(y0 + row as i64));
+                            value.insert(p, State::Alive);
+                        }
+                    }
+                }
+            }
+            Node::Cells(states) => {
+                for (i, s) in states.iter().enumerate().filter(|(_, s)| **s != 0) {
+                    let p = CartesianP::of(x0 + (i % 2) as i64, -(y0 + (i / 2) as i64));
+                    value.insert(p, State::from(*s));
+                }
+            }
+            Node::Branch(level, [nw, ne, sw, se]) => {
+                let half = 1_i64 << (level - 1);
+                self.collect(nw, x0, y0, value);
+                self.collect(ne, x0 + half, y0, value);
+                self.collect(sw, x0, y0 + half, value);
+                self.collect(se, x0 + half, y0 + half, value);
+            }
+        }
+    }
+
+    /// Expands the quadtree into a sparse universe, unless it has more cells
+    /// than `cell_limit`.
+    pub fn to_universe(&self, cell_limit: u64) -> Result<Universe, TooManyCellsErr> {
+        let population = self.population();
+        if population > cell_limit {
+            return Err(TooManyCellsErr { population, limit: cell_limit });
+        }
+        let half = 1_i64 << (self.level() - 1);
+        let mut value = HashMap::new();
+        self.collect(self.root, -half, -half, &mut value);
+        let mut u = Universe::from(value);
+        u.age = self.generation;
+        Ok(u)
+    }
+}
+
+fn parse_leaf(line: &str, line_number: usize) -> Result<Node, FromStringErr> {
+    let mut rows = [0; LEAF_SIZE];
+    let (mut row, mut col) = (0, 0);
+    for (j, c) in line.chars().enumerate() {
+        let err = || {
+            FromStringErr::InvalidCharacter(InvalidCharacterErr { line: line_number, col: j + 1 })
+        };
+        match c {
+            '.' | '*' if row < LEAF_SIZE && col < LEAF_SIZE => {
+                if c == '*' {
+                    rows[row] |= 1 << col;
+                }
+                col += 1;
+            }
+            '$' if row < LEAF_SIZE => {
+                row += 1;
+                col = 0;
+            }
+            _ => return Err(err()),
+        }
+    }
+    Ok(Node::Leaf(rows))
+}
+
+fn parse_branch(mc: &Macrocell, line: &str, line_number: usize) -> Result<Node, FromStringErr> {
+    let err = || FromStringErr::InvalidNode(InvalidNodeErr { line: line_number });
+    let values: Vec<usize> =
+        line.split_whitespace().map(|n| n.parse().map_err(|_| err())).collect::<Result<_, _>>()?;
+    let [level, nw, ne, sw, se] = values[..] else {
+        return Err(err());
+    };
+    if level == 0 || level > usize::from(MAX_LEVEL) {
+        return Err(err());
+    }
+    if level == 1 {
+        let states = [nw, ne, sw, se].map(u8::try_from);
+        return match states {
+            [Ok(nw), Ok(ne), Ok(sw), Ok(se)] => Ok(Node::Cells([nw, ne, sw, se])),
+            _ => Err(err()),
+        };
+    }
+    let level = level as u8;
+    let children = [nw, ne, sw, se];
+    for child in children {
+        if child >= mc.nodes.len() {
+            return Err(err());
+        }
+        if let Some(child_level) = mc.level_of(child) {
+            if child_level != level - 1 {
+                return Err(err());
+            }
+        }
+    }
+    Ok(Node::Branch(level, children))
+}
+
+/// Reads the quadtree as is, without expanding it. The last node is the root.
+pub fn parse(as_str: &str) -> Result<Macrocell, FromStringErr> {
+    let mut mc = Macrocell::default();
+    let mut lines = as_str.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+    match lines.next() {
+        Some((_, line)) if line.trim().starts_with(HEADER) => {}
+        _ => return Err(FromStringErr::InvalidHeader(InvalidHeaderErr)),
+    }
+    for (i, line) in lines {
+        let line = line.trim();
+        if let Some(directive) = line.strip_prefix('#') {
+            let mut chars = directive.chars();
+            let kind = chars.next();
+            let text = chars.as_str().trim();
+            match kind {
+                Some('R') => {
+                    mc.rule = Some(rule::from_string(text).map_err(FromStringErr::InvalidRule)?)
+                }
+                Some('G') => {
+                    mc.generation = text
+                        .parse()
+                        .map_err(|_| FromStringErr::InvalidNode(InvalidNodeErr { line: i + 1 }))?
+                }
+                Some('C') | Some('N') | Some('D') => mc.comments.push(String::from(text)),
+                _ => {}
+            }
+            continue;
+        }
+        let node = if line.starts_with(|c: char| c.is_ascii_digit()) {
+            parse_branch(&mc, line, i + 1)?
+        } else {
+            parse_leaf(line, i + 1)?
+        };
+        mc.root = mc.push(node);
+    }
+    Ok(mc)
+}
+
+pub fn from_string(as_str: &str, cell_limit: u64) -> Result<Pattern, FromStringErr> {
+    let mc = parse(as_str)?;
+    let universe = mc.to_universe(cell_limit).map_err(FromStringErr::TooManyCells)?;
+    Ok(Pattern { universe, rule: mc.rule, comments: mc.comments, ..Pattern::default() })
+}
+
+fn write_node(
+    mc: &Macrocell,
+    id: NodeId,
+    ids: &mut HashMap<NodeId, NodeId>,
+    lines: &mut Vec<String>,
+) {
+    if id == EMPTY || ids.contains_key(&id) {
+        return;
+    }
+    let line = match mc.nodes[id] {
+        Node::Leaf(rows) => {
+            let len = rows.iter().rposition(|bits| *bits != 0).map_or(0, |row| row + 1);
+            rows[..len]
+                .iter()
+                .map(|bits| {
+                    let row: String = (0..LEAF_SIZE)
+                        .map(|col| if bits & (1 << col) != 0 { '*' } else { '.' })
+                        .collect();
+                    format!("{}$", row.trim_end_matches('.'))
+                })
+                .collect()
+        }
+        Node::Cells([nw, ne, sw, se]) => format!("1 {nw} {ne} {sw} {se}"),
+        Node::Branch(level, children) => {
+            for child in children {
+                write_node(mc, child, ids, lines);
+            }
+            let [nw, ne, sw, se] = children.map(|child| ids.get(&child).copied().unwrap_or(EMPTY));
+            format!("{level} {nw} {ne} {sw} {se}")
+        }
+    };
+    lines.push(line);
+    ids.insert(id, lines.len());
+}
+
+/// Writes the nodes reachable from the root, children first.
+pub fn write(mc: &Macrocell) -> String {
+    let mut result = format!("{HEADER} (game of life)\n");
+    if let Some(rule) = &mc.rule {
+        result.push_str(&format!("#R {rule}\n"));
+    }
+    if mc.generation > 0 {
+        result.push_str(&format!("#G {}\n", mc.generation));
+    }
+    for comment in &mc.comments {
+        result.push_str(&format!("#C {comment}\n"));
+    }
+    let mut lines = Vec::new();
+    write_node(mc, mc.root, &mut HashMap::new(), &mut lines);
+    for line in lines {
+        result.push_str(&line);
+        result.push('\n');
+    }
+    result
+}
+
+/// Rules with more than two states get 2x2 leaves, so that none of their
+/// states is lost.
+pub fn to_string(u: &Universe, rule: &Rule) -> String {
+    let mut mc = Macrocell::from_universe(u, rule.states > 2);
+    mc.rule = Some(rule.clone());
+    mc.generation = u.age;
+    write(&mc)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::preset::get_preset_unsafe;
+
+    use super::*;
+
+    const GLIDER: &str = "[M2] (golly 4.2)\n#R B3/S23\n.*$..*$***$\n4 0 0 0 1\n";
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", TooManyCellsErr { population: 5, limit: 4 }),
+            "The pattern has 5 cells, more than the limit of 4!"
+        );
+        assert_eq!(
+            format!("{}", InvalidNodeErr { line: 3 }),
+            "Invalid node at line 3, expected \"level nw ne sw se\"!"
+        );
+        assert_eq!(parse("4 0 0 0 0\n"), Err(FromStringErr::InvalidHeader(InvalidHeaderErr)));
+        assert_eq!(
+            parse("[M2]\n.*$..*$**o$\n"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 2, col: 10 }))
+        );
+        assert_eq!(
+            parse("[M2]\n.........*$\n"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 2, col: 9 }))
+        );
+        assert_eq!(
+            parse("[M2]\n.*$\n4 0 0 0 2\n"),
+            Err(FromStringErr::InvalidNode(InvalidNodeErr { line: 3 }))
+        );
+        assert_eq!(
+            parse("[M2]\n.*$\n4 0 0 0 1\n6 0 0 0 2\n"),
+            Err(FromStringErr::InvalidNode(InvalidNodeErr { line: 4 }))
+        );
+        assert_eq!(
+            parse("[M2]\n.*$\n4 0 0 1\n"),
+            Err(FromStringErr::InvalidNode(InvalidNodeErr { line: 3 }))
+        );
+        assert!(matches!(parse("[M2]\n#R B9/S23\n"), Err(FromStringErr::InvalidRule(_))));
+        assert_eq!(
+            parse("[M2]\n1 0 256 0 0\n"),
+            Err(FromStringErr::InvalidNode(InvalidNodeErr { line: 2 }))
+        );
+        assert_eq!(
+            parse("[M2]\n1 0 1 0 0\n3 1 0 0 0\n"),
+            Err(FromStringErr::InvalidNode(InvalidNodeErr { line: 3 }))
+        );
+        assert_eq!(
+            from_string(GLIDER, 4),
+            Err(FromStringErr::TooManyCells(TooManyCellsErr { population: 5, limit: 4 }))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        let glider = from_string(GLIDER, DEFAULT_CELL_LIMIT).unwrap();
+        assert_eq!(glider.rule, Some(Rule::default()));
+        assert_eq!(
+            glider.universe,
+            Universe::from(HashMap::from([
+                (CartesianP::of(1, 0), State::Alive),
+                (CartesianP::of(2, -1), State::Alive),
+                (CartesianP::of(0, -2), State::Alive),
+                (CartesianP::of(1, -2), State::Alive),
+                (CartesianP::of(2, -2), State::Alive),
+            ]))
+        );
+        assert_eq!(from_string("[M2]\n", 0).unwrap().universe, Universe::default());
+        let generation = from_string("[M2]\n#G 12\n#C Old\n*$\n", 1).unwrap();
+        assert_eq!(generation.universe.age, 12);
+        assert_eq!(generation.comments, vec![String::from("Old")]);
+    }
+
+    #[test]
+    fn test_from_string_multistate() {
+        let text = "[M2] (golly 4.2)\n#R WireWorld\n1 0 1 3 3\n1 2 0 0 0\n2 1 0 0 2\n";
+        let wire = from_string(text, DEFAULT_CELL_LIMIT).unwrap();
+        assert_eq!(wire.rule.map(|r| r.states), Some(4));
+        assert_eq!(
+            wire.universe,
+            Universe::from(HashMap::from([
+                (CartesianP::of(-1, 2), State::Alive),
+                (CartesianP::of(-2, 1), State::Dying(2)),
+                (CartesianP::of(-1, 1), State::Dying(2)),
+                (CartesianP::of(0, 0), State::Dying(1)),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_shared_nodes() {
+        // 4^17 gliders in a 2^20 square, from 18 nodes.
+        let mut text = String::from("[M2]\n.*$..*$***$\n");
+        for level in 4..=20 {
+            let child = level - 3;
+            text.push_str(&format!("{level} {child} {child} {child} {child}\n"));
+        }
+        let mc = parse(&text).unwrap();
+        assert_eq!(mc.level(), 20);
+        assert_eq!(mc.population(), 5 << 34);
+        assert_eq!(mc.node_count(), 18);
+        assert_eq!(
+            mc.to_universe(DEFAULT_CELL_LIMIT),
+            Err(TooManyCellsErr { population: 5 << 34, limit: DEFAULT_CELL_LIMIT })
+        );
+        assert_eq!(write(&mc), format!("[M2] (game of life)\n{}", &text[5..]));
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(
+            to_string(&Universe::default(), &Rule::default()),
+            "[M2] (game of life)\n#R B3/S23\n"
+        );
+        assert_eq!(
+            to_string(&get_preset_unsafe("block"), &Rule::default()),
+            "[M2] (game of life)\n#R B3/S23\n$$$$...**$...**$\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for preset in ["block", "blinker", "glider", "r_pentomino", "gosper_glider_gun", "puffer1"]
+        {
+            let u = get_preset_unsafe(preset);
+            let text = to_string(&u, &Rule::default());
+            let pattern = from_string(&text, DEFAULT_CELL_LIMIT).unwrap();
+            assert_eq!(pattern.universe, u);
+            assert_eq!(write(&parse(&text).unwrap()), text);
+        }
+        let far = Universe::from(HashMap::from([
+            (CartesianP::of(-1_000_000, 7), State::Alive),
+            (CartesianP::of(3, 1 << 40), State::Alive),
+        ]));
+        let mc = Macrocell::from_universe(&far, false);
+        assert_eq!(mc.level(), 41);
+        assert_eq!(mc.to_universe(2), Ok(far.clone()));
+        assert_eq!(Macrocell::from_universe(&far, true).to_universe(2), Ok(far));
+
+        let wireworld = rule::from_string("WireWorld").unwrap();
+        let mut wire = Universe::from(HashMap::from([
+            (CartesianP::of(-3, 0), State::Dying(2)),
+            (CartesianP::of(-2, 0), State::Dying(1)),
+            (CartesianP::of(-1, 0), State::Alive),
+            (CartesianP::of(0, 0), State::Dying(2)),
+            (CartesianP::of(9, -4), State::Dying(2)),
+        ]));
+        wire.age = 3;
+        let text = to_string(&wire, &wireworld);
+        let pattern = from_string(&text, DEFAULT_CELL_LIMIT).unwrap();
+        assert_eq!(pattern.universe, wire);
+        assert_eq!(pattern.rule, Some(wireworld));
+        assert_eq!(write(&parse(&text).unwrap()), text);
+    }
+}
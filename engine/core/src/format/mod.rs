@@ -0,0 +1,151 @@
+use std::fmt;
+
+use super::{geometry::poligon::rect::Rect, rule::Rule, universe::Universe};
+
+pub mod cells;
+pub mod life105;
+pub mod life106;
+pub mod macrocell;
+pub mod rle;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Pattern {
+    pub universe: Universe,
+    pub rule: Option<Rule>,
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub comments: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidCharacterErr {
+    pub line: usize,
+    pub col: usize,
+}
+
+impl fmt::Display for InvalidCharacterErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid character at line {}, column {}!", self.line, self.col)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    Rle(rle::FromStringErr),
+    Cells(cells::FromStringErr),
+    Life105(life105::FromStringErr),
+    Life106(life106::FromStringErr),
+    Macrocell(macrocell::FromStringErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::Rle(e) => e.fmt(f),
+            FromStringErr::Cells(e) => e.fmt(f),
+            FromStringErr::Life105(e) => e.fmt(f),
+            FromStringErr::Life106(e) => e.fmt(f),
+            FromStringErr::Macrocell(e) => e.fmt(f),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Rle,
+    Cells,
+    Life105,
+    Life106,
+    Macrocell,
+}
+
+fn centered_cam(width: u64, height: u64) -> Rect {
+    let width = width as i64;
+    let height = height as i64;
+    let x1 = -(width / 2);
+    let y1 = -(height / 2);
+    Rect { x1, y1, x2: x1 + width - 1, y2: y1 + height - 1 }
+}
+
+/// Guesses the format from the content alone, falling back to RLE, so that
+/// a file can be read whatever its name.
+pub fn detect_format(as_str: &str) -> Format {
+    let mut lines = as_str.lines().map(str::trim).filter(|line| !line.is_empty());
+    let Some(first) = lines.next() else {
+        return Format::Rle;
+    };
+    if first.starts_with(life105::HEADER) {
+        return Format::Life105;
+    }
+    if first.starts_with(life106::HEADER) {
+        return Format::Life106;
+    }
+    if first.starts_with(macrocell::HEADER) {
+        return Format::Macrocell;
+    }
+    if first.starts_with('!') || first.chars().all(|c| c == '.' || c == 'O') {
+        return Format::Cells;
+    }
+    Format::Rle
+}
+
+pub fn from_string(as_str: &str) -> Result<Pattern, FromStringErr> {
+    match detect_format(as_str) {
+        Format::Rle => rle::from_string(as_str).map_err(FromStringErr::Rle),
+        Format::Cells => cells::from_string(as_str).map_err(FromStringErr::Cells),
+        Format::Life105 => life105::from_string(as_str).map_err(FromStringErr::Life105),
+        Format::Life106 => life106::from_string(as_str).map_err(FromStringErr::Life106),
+        Format::Macrocell => macrocell::from_string(as_str, macrocell::DEFAULT_CELL_LIMIT)
+            .map_err(FromStringErr::Macrocell),
+    }
+}
+
+pub fn to_string(format: Format, u: &Universe, rule: &Rule) -> String {
+    match format {
+        Format::Rle => rle::to_string(u, rule),
+        Format::Cells => cells::to_string(u),
+        Format::Life105 => life105::to_string(u, rule),
+        Format::Life106 => life106::to_string(u),
+        Format::Macrocell => macrocell::to_string(u, rule),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::preset::get_preset_unsafe;
+
+    use super::*;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(detect_format(""), Format::Rle);
+        assert_eq!(detect_format("#N Glider\nx = 3, y = 3\nbo$2bo$3o!"), Format::Rle);
+        assert_eq!(detect_format("bo$2bo$3o!"), Format::Rle);
+        assert_eq!(detect_format("!Name: Glider\n.O.\n..O\nOOO\n"), Format::Cells);
+        assert_eq!(detect_format("\n.O.\n..O\nOOO\n"), Format::Cells);
+        assert_eq!(detect_format("#Life 1.05\n#P -1 -1\n.*.\n"), Format::Life105);
+        assert_eq!(detect_format("#Life 1.06\n0 -1\n"), Format::Life106);
+        assert_eq!(detect_format("[M2] (golly 4.2)\n#R B3/S23\n"), Format::Macrocell);
+    }
+
+    #[test]
+    fn test_from_string() {
+        let glider = get_preset_unsafe("glider");
+        for format in
+            [Format::Rle, Format::Cells, Format::Life105, Format::Life106, Format::Macrocell]
+        {
+            let text = to_string(format, &glider, &Rule::default());
+            assert_eq!(detect_format(&text), format);
+            let pattern = from_string(&text).unwrap();
+            assert_eq!(pattern.universe.value.len(), glider.value.len());
+            assert_eq!(
+                rle::to_string(&pattern.universe, &Rule::default()),
+                rle::to_string(&glider, &Rule::default())
+            );
+        }
+        assert_eq!(
+            from_string("!Name: Glider\n.O.\n..x\n").unwrap_err().to_string(),
+            "Invalid character at line 3, column 3!"
+        );
+    }
+}
@@ -0,0 +1,518 @@
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::{matrix_to_cartesian, CartesianP, MatrixP},
+    rule::{self, Rule},
+    universe::{get_bounds, get_value, Universe},
+};
+
+use super::{
+    centered_cam,
+    macrocell::{TooManyCellsErr, DEFAULT_CELL_LIMIT},
+    InvalidCharacterErr, Pattern,
+};
+
+const MAX_LINE_LEN: usize = 70;
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidHeaderErr {
+    pub line: usize,
+}
+
+impl fmt::Display for InvalidHeaderErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid header at line {}, expected \"x = m, y = n, rule = abc\"!", self.line)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidHeader(InvalidHeaderErr),
+    InvalidCharacter(InvalidCharacterErr),
+    InvalidRule(rule::FromStringErr),
+    TooManyCells(TooManyCellsErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidHeader(e) => e.fmt(f),
+            FromStringErr::InvalidCharacter(e) => e.fmt(f),
+            FromStringErr::InvalidRule(e) => e.fmt(f),
+            FromStringErr::TooManyCells(e) => e.fmt(f),
+        }
+    }
+}
+
+struct Header {
+    width: u64,
+    height: u64,
+    rule: Option<Rule>,
+}
+
+fn parse_header(line: &str, line_number: usize) -> Result<Header, FromStringErr> {
+    let err = || FromStringErr::InvalidHeader(InvalidHeaderErr { line: line_number });
+    let mut width: Option<u64> = None;
+    let mut height: Option<u64> = None;
+    // The rule comes last, as Larger than Life rules have commas of their own.
+    let (line, rule_entry) = match line.split_once("rule") {
+        Some((dimensions, rule)) => (dimensions.trim_end().trim_end_matches(','), Some(rule)),
+        None => (line, None),
+    };
+    let rule = match rule_entry {
+        Some(value) => {
+            let value = value.trim_start().strip_prefix('=').ok_or_else(err)?.trim();
+            Some(rule::from_string(value).map_err(FromStringErr::InvalidRule)?)
+        }
+        None => None,
+    };
+    for entry in line.split(',') {
+        let (key, value) = entry.split_once('=').ok_or_else(err)?;
+        let value = value.trim();
+        match key.trim() {
+            "x" => width = Some(value.parse().map_err(|_| err())?),
+            "y" => height = Some(value.parse().map_err(|_| err())?),
+            _ => return Err(err()),
+        }
+    }
+    match (width, height) {
+        (Some(width), Some(height)) => Ok(Header { width, height, rule }),
+        _ => Err(err()),
+    }
+}
+
+pub fn from_string(as_str: &str) -> Result<Pattern, FromStringErr> {
+    let mut pattern = Pattern::default();
+    let mut header: Option<Header> = None;
+    let mut cells: Vec<(MatrixP, State)> = Vec::new();
+    let mut row: u64 = 0;
+    let mut col: u64 = 0;
+    let mut width: u64 = 0;
+    let mut run: Option<u64> = None;
+    let mut prefix: Option<(u8, usize)> = None;
+    let mut body_started = false;
+    'lines: for (i, line) in as_str.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if !body_started {
+            if let Some(comment) = line.strip_prefix('#') {
+                let mut chars = comment.chars();
+                let kind = chars.next();
+                let text = String::from(chars.as_str().trim());
+                match kind {
+                    Some('N') => pattern.name = Some(text),
+                    Some('O') => pattern.author = Some(text),
+                    Some('C') | Some('c') => pattern.comments.push(text),
+                    _ => {}
+                }
+                continue;
+            }
+            if header.is_none() && line.starts_with('x') {
+                header = Some(parse_header(line, i + 1)?);
+                continue;
+            }
+        }
+        body_started = true;
+        for (j, c) in line.chars().enumerate() {
+            let err =
+                || FromStringErr::InvalidCharacter(InvalidCharacterErr { line: i + 1, col: j + 1 });
+            // Rows and columns past an `i64` couldn't be placed in the plane.
+            let shift = |n: u64, count: u64| {
+                n.checked_add(count).filter(|n| i64::try_from(*n).is_ok()).ok_or_else(err)
+            };
+            if let Some(digit) = c.to_digit(10) {
+                let n = run.unwrap_or(0).checked_mul(10).and_then(|n| n.checked_add(digit.into()));
+                run = Some(n.ok_or_else(err)?);
+                continue;
+            }
+            if let Some((p, p_col)) = prefix.take() {
+                if !matches!(c, 'A'..='X') {
+                    return Err(FromStringErr::InvalidCharacter(InvalidCharacterErr {
+                        line: i + 1,
+                        col: p_col,
+                    }));
+                }
+                let n = 25 + u16::from(p - b'p') * 24 + u16::from(c as u8 - b'A');
+                let state = u8::try_from(n).map_err(|_| err())?;
+                let count = run.take().unwrap_or(1);
+                shift(col, count)?;
+                push_cells(&mut cells, row, &mut col, count, state)?;
+                width = width.max(col);
+                continue;
+            }
+            if matches!(c, 'p'..='y') {
+                prefix = Some((c as u8, j + 1));
+                continue;
+            }
+            let count = run.take().unwrap_or(1);
+            match c {
+                'b' | '.' => col = shift(col, count)?,
+                'o' | 'A'..='X' => {
+                    let state = if c == 'o' { 1 } else { c as u8 - b'A' + 1 };
+                    shift(col, count)?;
+                    push_cells(&mut cells, row, &mut col, count, state)?;
+                    width = width.max(col);
+                }
+                '$' => {
+                    row = shift(row, count)?;
+                    col = 0;
+                }
+                '!' => break 'lines,
+                c if c.is_whitespace() => {}
+                _ => return Err(err()),
+            }
+        }
+    }
+    let height = cells.iter().map(|(p, _)| p.row + 1).max().unwrap_or(0);
+    let cam = match &header {
+        Some(h) => centered_cam(h.width.max(width), h.height.max(height)),
+        None => centered_cam(width, height),
+    };
+    let value: HashMap<CartesianP, State> =
+        cells.iter().map(|(p, state)| (matrix_to_cartesian(p, &cam), *state)).collect();
+    pattern.universe = Universe::from(value);
+    pattern.rule = header.and_then(|h| h.rule);
+    Ok(pattern)
+}
+
+/// Fails before allocating the cells of a pattern larger than the limit.
+fn push_cells(
+    cells: &mut Vec<(MatrixP, State)>,
+    row: u64,
+    col: &mut u64,
+    count: u64,
+    n: u8,
+) -> Result<(), FromStringErr> {
+    let population = (cells.len() as u64).saturating_add(count);
+    if population > DEFAULT_CELL_LIMIT {
+        let limit = DEFAULT_CELL_LIMIT;
+        return Err(FromStringErr::TooManyCells(TooManyCellsErr { population, limit }));
+    }
+    let state = State::from(n);
+    cells.extend((*col..*col + count).map(|col| (MatrixP { row, col }, state)));
+    *col += count;
+    Ok(())
+}
+
+/// Multistate RLE numbers the states from 1, with `A` for the alive cells
+/// and the `p` to `y` prefixes once the 24 single letters run out.
+fn tag(state: State, multistate: bool) -> String {
+    let n = u8::from(state);
+    if n == 0 {
+        return String::from(if multistate { "." } else { "b" });
+    }
+    if !multistate {
+        return String::from("o");
+    }
+    if n <= 24 {
+        return char::from(b'A' + n - 1).to_string();
+    }
+    let prefix = char::from(b'p' + (n - 25) / 24);
+    let letter = char::from(b'A' + (n - 25) % 24);
+    format!("{prefix}{letter}")
+}
+
+fn push_run(runs: &mut Vec<String>, count: u64, tag: &str) {
+    if count == 0 {
+        return;
+    }
+    if count == 1 {
+        runs.push(tag.to_string());
+    } else {
+        runs.push(format!("{count}{tag}"));
+    }
+}
+
+pub fn to_string(u: &Universe, rule: &Rule) -> String {
+    let bounds = get_bounds(u);
+    let (width, height) = match bounds {
+        Some(r) => (r.x2 - r.x1 + 1, r.y2 - r.y1 + 1),
+        None => (0, 0),
+    };
+    let multistate = rule.states > 2;
+    let mut result = format!("x = {width}, y = {height}, rule = {rule}\n");
+    let mut runs: Vec<String> = Vec::new();
+    if let Some(r) = bounds {
+        let mut pending_rows: u64 = 0;
+        for y in (r.y1..=r.y2).rev() {
+            let mut pending: Option<(State, u64)> = None;
+            for x in r.x1..=r.x2 {
+                let state = get_value(u, &CartesianP::of(x, y));
+                pending = match pending {
+                    Some((s, count)) if s == state => Some((s, count + 1)),
+                    Some((s, count)) => {
+                        if state != State::Dead && s == State::Dead {
+                            push_run(&mut runs, pending_rows, "$");
+                            pending_rows = 0;
+                        }
+                        push_run(&mut runs, count, &tag(s, multistate));
+                        Some((state, 1))
+                    }
+                    None => {
+                        if state != State::Dead {
+                            push_run(&mut runs, pending_rows, "$");
+                            pending_rows = 0;
+                        }
+                        Some((state, 1))
+                    }
+                };
+            }
+            if let Some((s, count)) = pending {
+                if s != State::Dead {
+                    push_run(&mut runs, count, &tag(s, multistate));
+                }
+            }
+            pending_rows += 1;
+        }
+    }
+    runs.push(String::from("!"));
+    let mut line = String::new();
+    for token in runs {
+        if line.len() + token.len() > MAX_LINE_LEN {
+            result.push_str(&line);
+            result.push('\n');
+            line.clear();
+        }
+        line.push_str(&token);
+    }
+    result.push_str(&line);
+    result.push('\n');
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::poligon::rect::Rect,
+        preset::get_preset_unsafe,
+        topology::{Topology, Twist},
+        universe::from_string as from_grid,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", InvalidHeaderErr { line: 2 }),
+            "Invalid header at line 2, expected \"x = m, y = n, rule = abc\"!"
+        );
+        assert_eq!(
+            format!("{}", InvalidCharacterErr { line: 3, col: 7 }),
+            "Invalid character at line 3, column 7!"
+        );
+        assert_eq!(
+            from_string("x = 3\nbo$2bo$3o!"),
+            Err(FromStringErr::InvalidHeader(InvalidHeaderErr { line: 1 }))
+        );
+        assert_eq!(
+            from_string("#N Glider\nx = 3, y = a\nbo$2bo$3o!"),
+            Err(FromStringErr::InvalidHeader(InvalidHeaderErr { line: 2 }))
+        );
+        assert_eq!(
+            from_string("x = 3, y = 3, z = 1\nbo$2bo$3o!"),
+            Err(FromStringErr::InvalidHeader(InvalidHeaderErr { line: 1 }))
+        );
+        assert_eq!(
+            from_string("x = 3, y = 3, rule = B9/S23\nbo$2bo$3o!"),
+            Err(FromStringErr::InvalidRule(rule::FromStringErr::InvalidNeighbors(
+                rule::InvalidNeighborsErr
+            )))
+        );
+        assert_eq!(
+            from_string("x = 3, y = 3\nbo$2bo$\n3oz!"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 3, col: 3 }))
+        );
+        assert_eq!(
+            from_string("99999999999999999999999o!"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 1, col: 20 }))
+        );
+        assert_eq!(
+            from_string("9223372036854775807b2o!"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 1, col: 22 }))
+        );
+        assert_eq!(
+            from_string("o18446744073709551615$o!"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 1, col: 22 }))
+        );
+        assert_eq!(
+            from_string("30000000o!"),
+            Err(FromStringErr::TooManyCells(TooManyCellsErr {
+                population: 30000000,
+                limit: DEFAULT_CELL_LIMIT
+            }))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        let glider = from_string(
+            "#N Glider\n#O Richard K. Guy\n#C The smallest spaceship.\n#C www.conwaylife.com/wiki/index.php?title=Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!",
+        )
+        .unwrap();
+        assert_eq!(
+            glider,
+            Pattern {
+                universe: from_grid(vec![
+                    String::from("⬛⬜⬛"),
+                    String::from("⬛⬛⬜"),
+                    String::from("⬜⬜⬜"),
+                ])
+                .unwrap(),
+                rule: Some(Rule::default()),
+                name: Some(String::from("Glider")),
+                author: Some(String::from("Richard K. Guy")),
+                comments: vec![
+                    String::from("The smallest spaceship."),
+                    String::from("www.conwaylife.com/wiki/index.php?title=Glider"),
+                ],
+            }
+        );
+        assert_eq!(from_string("x=3,y=3\nbo$2bo$3o!").unwrap().universe, glider.universe);
+        assert_eq!(from_string("bo$2bo$3o!").unwrap().universe, glider.universe);
+        assert_eq!(
+            from_string("x = 3, y = 3\nb\no$2b\no$\n3\no!").unwrap().universe,
+            glider.universe
+        );
+        assert_eq!(from_string("x = 3, y = 3\nbo$2bo$3o!\n3o!").unwrap().universe, glider.universe);
+        assert_eq!(
+            from_string("x = 3, y = 3, rule = 23/36\n!").unwrap().rule,
+            Some(Rule::of(&[3, 6], &[2, 3]))
+        );
+        assert_eq!(from_string("x = 0, y = 0\n!").unwrap().universe, Universe::default());
+    }
+
+    #[test]
+    fn test_from_string_larger_than_life() {
+        let pattern = from_string("x = 2, y = 1, rule = R5,C0,M1,S34..58,B34..45,NM\n2o!").unwrap();
+        assert_eq!(pattern.universe.value.len(), 2);
+        let rule = pattern.rule.unwrap();
+        assert_eq!(rule.to_string(), "R5,C0,M0,S33..57,B34..45,NM");
+        assert_eq!(
+            to_string(&pattern.universe, &rule),
+            "x = 2, y = 1, rule = R5,C0,M0,S33..57,B34..45,NM\n2o!\n"
+        );
+    }
+
+    #[test]
+    fn test_from_string_golly_topology() {
+        let pattern = from_string("x = 3, y = 1, rule = B3/S23:T20,0\n3o!").unwrap();
+        let rule = pattern.rule.unwrap();
+        assert_eq!(rule.topology, Topology::Torus { width: 20, height: 0 });
+        assert_eq!(to_string(&pattern.universe, &rule), "x = 3, y = 1, rule = B3/S23:T20,0\n3o!\n");
+        let pattern = from_string("x = 3, y = 1, rule = B3/S23:K20,10*\n3o!").unwrap();
+        assert_eq!(
+            pattern.rule.unwrap().topology,
+            Topology::KleinBottle { width: 20, height: 10, twist: Twist::Vertical }
+        );
+    }
+
+    #[test]
+    fn test_from_string_multi_digit_runs() {
+        let line = from_string("x = 12, y = 3\n$12o$!").unwrap().universe;
+        assert_eq!(line.value.len(), 12);
+        assert_eq!(get_bounds(&line), Some(Rect::of(-6, 0, 5, 0)));
+        let gap = from_string("x = 12, y = 12\no10$11bo!").unwrap().universe;
+        assert_eq!(
+            gap,
+            Universe::from(HashMap::from([
+                (CartesianP::of(-6, 5), State::Alive),
+                (CartesianP::of(5, -5), State::Alive),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(
+            to_string(&Universe::default(), &Rule::default()),
+            "x = 0, y = 0, rule = B3/S23\n!\n"
+        );
+        assert_eq!(
+            to_string(&get_preset_unsafe("glider"), &Rule::default()),
+            "x = 3, y = 3, rule = B3/S23\nobo$b2o$bo!\n"
+        );
+        assert_eq!(
+            to_string(&get_preset_unsafe("block"), &Rule::of(&[3, 6], &[2, 3])),
+            "x = 2, y = 2, rule = B36/S23\n2o$2o!\n"
+        );
+        assert_eq!(
+            to_string(
+                &Universe::from(HashMap::from([
+                    (CartesianP::of(0, 0), State::Alive),
+                    (CartesianP::of(2, -3), State::Alive),
+                ])),
+                &Rule::default()
+            ),
+            "x = 3, y = 4, rule = B3/S23\no3$2bo!\n"
+        );
+    }
+
+    #[test]
+    fn test_to_string_line_length() {
+        let u = get_preset_unsafe("gosper_glider_gun");
+        let rle = to_string(&u, &Rule::default());
+        assert_eq!(
+            rle,
+            "x = 36, y = 9, rule = B3/S23\n24bo$22bobo$12b2o6b2o12b2o$11bo3bo4b2o12b2o$2o8bo5bo3b2o$2o8bo3bob2o4b\nobo$10bo5bo7bo$11bo3bo$12b2o!\n"
+        );
+        assert!(rle.lines().all(|line| line.len() <= MAX_LINE_LEN));
+        let mut checkerboard = HashMap::new();
+        for x in 0..100 {
+            for y in 0..3 {
+                if (x + y) % 2 == 0 {
+                    checkerboard.insert(CartesianP::of(x, y), State::Alive);
+                }
+            }
+        }
+        let checkerboard = Universe::from(checkerboard);
+        let rle = to_string(&checkerboard, &Rule::default());
+        assert!(rle.lines().all(|line| line.len() <= MAX_LINE_LEN));
+        assert_eq!(from_string(&rle).unwrap().universe.value.len(), checkerboard.value.len());
+    }
+
+    #[test]
+    fn test_multistate() {
+        let mut rule = Rule::of(&[2], &[]);
+        rule.states = 30;
+        let u = Universe::from(HashMap::from([
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(1, 0), State::Dying(1)),
+            (CartesianP::of(2, 0), State::Dying(1)),
+            (CartesianP::of(4, 0), State::Dying(25)),
+            (CartesianP::of(0, -2), State::Dying(28)),
+        ]));
+        let rle = to_string(&u, &rule);
+        assert_eq!(rle, "x = 5, y = 3, rule = B2/S/C30\nA2B.pB2$pE!\n");
+        let pattern = from_string(&rle).unwrap();
+        assert_eq!(pattern.rule.as_ref(), Some(&rule));
+        assert_eq!(to_string(&pattern.universe, &rule), rle);
+        assert_eq!(from_string("3pA!").unwrap().universe.value.len(), 3);
+        assert_eq!(
+            from_string("pZ!"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 1, col: 1 }))
+        );
+        assert_eq!(
+            from_string("yP!"),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr { line: 1, col: 2 }))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for preset in
+            ["block", "blinker", "boat", "glider", "r_pentomino", "gosper_glider_gun", "puffer1"]
+        {
+            let u = get_preset_unsafe(preset);
+            let pattern = from_string(&to_string(&u, &Rule::default())).unwrap();
+            assert_eq!(
+                to_string(&pattern.universe, &Rule::default()),
+                to_string(&u, &Rule::default())
+            );
+            assert_eq!(pattern.universe.value.len(), u.value.len());
+        }
+    }
+}
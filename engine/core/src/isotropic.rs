@@ -0,0 +1,256 @@
+use crate::{
+    geometry::coordinate::CartesianP,
+    neighbor::{offsets, Neighborhood},
+    rule::{FromStringErr, InvalidLetterErr, InvalidNeighborsErr},
+};
+
+/// The Hensel letters of each number of neighbors, in Golly's order.
+pub const LETTERS: [&str; 9] =
+    ["", "ce", "ceaikn", "ceaiknjqry", "ceaiknjqrytwz", "ceaiknjqry", "ceaikn", "ce", ""];
+
+/// One configuration of each letter up to 4 neighbors, with the bits of the
+/// neighbors from the top left to the bottom right, as in `neighbor::offsets`.
+/// Above 4, each letter is the complement of the same letter below.
+const REPRESENTATIVES: [&[u8]; 5] = [
+    &[],
+    &[0b1000_0000, 0b0100_0000],
+    &[0b1010_0000, 0b0101_0000, 0b1100_0000, 0b0001_1000, 0b1000_1000, 0b0010_0100],
+    &[
+        0b1010_0100,
+        0b0101_1000,
+        0b1101_0000,
+        0b1110_0000,
+        0b0100_1100,
+        0b1011_0000,
+        0b0111_0000,
+        0b0110_0100,
+        0b1001_1000,
+        0b1000_1100,
+    ],
+    &[
+        0b1010_0101,
+        0b0101_1010,
+        0b1111_0000,
+        0b1011_1000,
+        0b1100_1100,
+        0b1110_0100,
+        0b0101_1100,
+        0b0110_1100,
+        0b1101_1000,
+        0b1010_1100,
+        0b1001_1100,
+        0b0111_0100,
+        0b0011_1100,
+    ],
+];
+
+/// A rule that looks at the arrangement of the 8 neighbors and not only at
+/// their number, indexed by the configuration of `neighbor::configuration_from_model`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Isotropic {
+    pub birth: [bool; 256],
+    pub survival: [bool; 256],
+}
+
+fn transform(configuration: u8, f: impl Fn(&CartesianP) -> CartesianP) -> u8 {
+    let moore = offsets(&Neighborhood::default());
+    moore.iter().enumerate().filter(|(i, _)| configuration & (1 << i) != 0).fold(
+        0,
+        |acc, (_, p)| {
+            let target = f(p);
+            acc | (1 << moore.iter().position(|o| o == &target).unwrap_or(0))
+        },
+    )
+}
+
+/// The 8 rotations and reflections of a configuration.
+pub fn symmetries(configuration: u8) -> Vec<u8> {
+    let mut result = Vec::new();
+    let mut current = configuration;
+    for _ in 0..4 {
+        result.push(current);
+        result.push(transform(current, |p| CartesianP::of(-p.x, p.y)));
+        current = transform(current, |p| CartesianP::of(p.y, -p.x));
+    }
+    result
+}
+
+fn representative(neighbors: u8, letter: char) -> Option<u8> {
+    let index = LETTERS.get(usize::from(neighbors))?.find(letter)?;
+    if neighbors <= 4 {
+        Some(REPRESENTATIVES[usize::from(neighbors)][index])
+    } else {
+        Some(!REPRESENTATIVES[usize::from(8 - neighbors)][index])
+    }
+}
+
+/// The number of alive neighbors of a configuration and its Hensel letter,
+/// `None` for 0 and 8 neighbors, which have a single configuration.
+pub fn class_of(configuration: u8) -> (u8, Option<char>) {
+    let neighbors = configuration.count_ones() as u8;
+    let letter = LETTERS[usize::from(neighbors)].chars().find(|letter| {
+        representative(neighbors, *letter).is_some_and(|r| symmetries(r).contains(&configuration))
+    });
+    (neighbors, letter)
+}
+
+/// Parses one side of a Hensel rule, such as `2-a3` or `12ce`, where the
+/// letters after a number keep only those arrangements, or remove them after
+/// a `-`.
+pub fn from_string(as_str: &str) -> Result<[bool; 256], FromStringErr> {
+    let mut values = [false; 256];
+    let mut chars = as_str.chars().peekable();
+    while let Some(c) = chars.next() {
+        let neighbors = match c.to_digit(10) {
+            Some(n) if n <= 8 => n as u8,
+            _ => return Err(FromStringErr::InvalidNeighbors(InvalidNeighborsErr)),
+        };
+        let negated = chars.next_if_eq(&'-').is_some();
+        let mut letters = String::new();
+        while let Some(letter) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+            if representative(neighbors, letter).is_none() {
+                return Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors, letter }));
+            }
+            letters.push(letter);
+        }
+        if negated && letters.is_empty() {
+            return Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors, letter: '-' }));
+        }
+        for configuration in 0..=255u8 {
+            match class_of(configuration) {
+                (n, _) if n != neighbors => {}
+                (_, Some(letter)) if !letters.is_empty() => {
+                    if letters.contains(letter) != negated {
+                        values[usize::from(configuration)] = true;
+                    }
+                }
+                _ => values[usize::from(configuration)] = true,
+            }
+        }
+    }
+    Ok(values)
+}
+
+/// Writes one side of a Hensel rule, with the shortest of the kept or the
+/// removed letters for each number of neighbors.
+pub fn to_string(values: &[bool; 256]) -> String {
+    let mut result = String::new();
+    for (neighbors, letters) in LETTERS.iter().enumerate() {
+        let (mut kept, mut removed) = (String::new(), String::new());
+        let mut any = false;
+        for configuration in 0..=255u8 {
+            let (n, letter) = class_of(configuration);
+            if usize::from(n) != neighbors {
+                continue;
+            }
+            let value = values[usize::from(configuration)];
+            any |= value;
+            if let Some(letter) = letter {
+                let target = if value { &mut kept } else { &mut removed };
+                if !target.contains(letter) {
+                    target.push(letter);
+                }
+            }
+        }
+        if !any {
+            continue;
+        }
+        let order = |s: &String| letters.chars().filter(|c| s.contains(*c)).collect::<String>();
+        result.push_str(&neighbors.to_string());
+        if removed.is_empty() {
+            continue;
+        }
+        if kept.len() <= removed.len() {
+            result.push_str(&order(&kept));
+        } else {
+            result.push('-');
+            result.push_str(&order(&removed));
+        }
+    }
+    result
+}
+
+/// The numbers of neighbors for which every configuration is set, if there is
+/// no other, so the rule can be written in the usual notation.
+pub fn totalistic(values: &[bool; 256]) -> Option<Vec<u8>> {
+    let mut result = Vec::new();
+    for neighbors in 0..=8u8 {
+        let mut set = (0..=255u8)
+            .filter(|c| c.count_ones() as u8 == neighbors)
+            .map(|c| values[usize::from(c)]);
+        let first = set.next().unwrap_or(false);
+        if set.any(|value| value != first) {
+            return None;
+        }
+        if first {
+            result.push(neighbors);
+        }
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_class_of() {
+        let mut sizes = std::collections::HashMap::new();
+        for configuration in 0..=255u8 {
+            *sizes.entry(class_of(configuration)).or_insert(0) += 1;
+        }
+        assert_eq!(sizes.len(), 51);
+        assert_eq!(sizes[&(0, None)], 1);
+        assert_eq!(sizes[&(8, None)], 1);
+        assert_eq!(sizes[&(2, Some('i'))], 2);
+        assert_eq!(sizes[&(2, Some('k'))], 8);
+        assert_eq!(sizes[&(4, Some('c'))], 1);
+        assert_eq!(sizes[&(4, Some('z'))], 4);
+        assert_eq!(sizes[&(5, Some('r'))], 8);
+        assert_eq!(class_of(0b0100_0010), (2, Some('i')));
+        assert_eq!(class_of(0b0010_0001), (2, Some('c')));
+        assert_eq!(class_of(0b0000_0111), (3, Some('i')));
+        assert_eq!(class_of(0b0100_0111), (4, Some('t')));
+        assert_eq!(class_of(0b1111_1000), (5, Some('i')));
+        assert_eq!(class_of(0b0111_1111), (7, Some('c')));
+        assert_eq!(class_of(0b1111_1111), (8, None));
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(from_string("9"), Err(FromStringErr::InvalidNeighbors(InvalidNeighborsErr)));
+        assert_eq!(
+            from_string("2-z"),
+            Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors: 2, letter: 'z' }))
+        );
+        assert_eq!(
+            from_string("2-"),
+            Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors: 2, letter: '-' }))
+        );
+        let all = from_string("2").unwrap();
+        assert_eq!(from_string("2ceaikn").unwrap(), all);
+        assert_eq!(from_string("2-a").unwrap(), from_string("2ceikn").unwrap());
+        assert_eq!(all.iter().filter(|v| **v).count(), 28);
+        assert_eq!(from_string("3-i").unwrap().iter().filter(|v| **v).count(), 52);
+        assert_eq!(from_string("").unwrap(), [false; 256]);
+    }
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&[false; 256]), "");
+        for hensel in ["2-a3", "12-i", "3", "2ce4kn8", "05-cekainyq", "4ceaiknjqr"] {
+            let canonical = to_string(&from_string(hensel).unwrap());
+            assert_eq!(from_string(&canonical).unwrap(), from_string(hensel).unwrap());
+        }
+        assert_eq!(to_string(&from_string("2ceaikn3-i").unwrap()), "23-i");
+        assert_eq!(to_string(&from_string("4ceaiknjqr").unwrap()), "4-ytwz");
+        assert_eq!(to_string(&from_string("5nj").unwrap()), "5nj");
+    }
+
+    #[test]
+    fn test_totalistic() {
+        assert_eq!(totalistic(&from_string("23").unwrap()), Some(vec![2, 3]));
+        assert_eq!(totalistic(&from_string("2ceaikn3cekainyqjr").unwrap()), Some(vec![2, 3]));
+        assert_eq!(totalistic(&from_string("2-a3").unwrap()), None);
+    }
+}
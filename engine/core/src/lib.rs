@@ -0,0 +1,15 @@
+pub mod analysis;
+pub mod cell;
+pub mod evolution;
+pub mod format;
+pub mod geometry;
+pub mod isotropic;
+pub mod margolus;
+pub mod neighbor;
+pub mod preset;
+pub mod region;
+pub mod render;
+pub mod rule;
+pub mod table;
+pub mod topology;
+pub mod universe;
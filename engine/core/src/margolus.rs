@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    cell::State,
+    geometry::coordinate::CartesianP,
+    rule::{FromStringErr, InvalidFormatErr, UnsupportedB0Err},
+    topology::{normalize, Topology},
+    universe::{get_value, set_value, Universe},
+};
+
+/// The next state of each 2x2 block, where the top left, top right, bottom
+/// left and bottom right cells are the bits 1, 2, 4 and 8, as in MCell.
+pub type Block = [u8; 16];
+
+pub const CRITTERS: Block = [15, 14, 13, 3, 11, 5, 6, 1, 7, 9, 10, 2, 12, 4, 8, 0];
+
+pub const BBM: Block = [0, 8, 4, 3, 2, 5, 9, 7, 1, 6, 10, 11, 12, 13, 14, 15];
+
+const BUILTINS: [(&str, Block); 2] = [("Critters", CRITTERS), ("BBM", BBM)];
+
+pub fn builtin(name: &str) -> Option<Block> {
+    BUILTINS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, b)| *b)
+}
+
+pub fn name(b: &Block) -> Option<&'static str> {
+    BUILTINS.iter().find(|(_, block)| block == b).map(|(n, _)| *n)
+}
+
+pub fn to_string(b: &Block) -> String {
+    let values: Vec<String> = b.iter().map(|n| n.to_string()).collect();
+    format!("MS,D{}", values.join(";"))
+}
+
+/// Rules that fill empty blocks have to empty full ones, so that they can run
+/// with every other generation complemented and the plane stays empty.
+fn complemented(b: &Block) -> bool {
+    b[0] == 15
+}
+
+/// Parses the `MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15` notation of MCell.
+pub fn from_string(as_str: &str) -> Result<Block, FromStringErr> {
+    let err = || FromStringErr::InvalidFormat(InvalidFormatErr);
+    let values = as_str.get(4..).filter(|_| as_str[..4].eq_ignore_ascii_case("MS,D"));
+    let values: Vec<u8> = values
+        .ok_or_else(err)?
+        .split(';')
+        .map(|n| n.trim().parse::<u8>().ok().filter(|n| *n < 16).ok_or_else(err))
+        .collect::<Result<_, _>>()?;
+    let b: Block = values.try_into().map_err(|_| err())?;
+    if b[0] != 0 && !(complemented(&b) && b[15] == 0) {
+        return Err(FromStringErr::UnsupportedB0(UnsupportedB0Err));
+    }
+    Ok(b)
+}
+
+/// The blocks applied at generation `age`, which complement their result on
+/// even generations and their input on odd ones for the rules that fill empty
+/// blocks.
+fn at(b: &Block, age: u64) -> Block {
+    let mut result = *b;
+    if complemented(b) {
+        for (i, n) in result.iter_mut().enumerate() {
+            *n = if age.is_multiple_of(2) { 15 - b[i] } else { b[15 - i] };
+        }
+    }
+    result
+}
+
+/// The table that undoes `b`, if no two blocks lead to the same one.
+pub fn inverse(b: &Block) -> Option<Block> {
+    let mut result = [16; 16];
+    for (i, n) in b.iter().enumerate() {
+        result[usize::from(*n)] = i as u8;
+    }
+    result.iter().all(|n| *n < 16).then_some(result)
+}
+
+/// Replaces each block of the partition by its entry in `b`. The partition
+/// starts at even coordinates, or at odd ones when `offset` is 1, so finite
+/// topologies need an even width and height, and no twist, for each block to
+/// wrap onto a single one.
+fn apply(u: &mut Universe, b: &Block, t: &Topology, offset: i64) {
+    let origin = |p: &CartesianP| {
+        CartesianP::of(p.x - (p.x - offset).rem_euclid(2), p.y - (p.y - offset).rem_euclid(2))
+    };
+    let origins: HashSet<CartesianP> = u.value.keys().map(origin).collect();
+    let mut value = HashMap::<CartesianP, State>::new();
+    for o in origins {
+        let cells = [
+            CartesianP::of(o.x, o.y + 1),
+            CartesianP::of(o.x + 1, o.y + 1),
+            CartesianP::of(o.x, o.y),
+            CartesianP::of(o.x + 1, o.y),
+        ]
+        .map(|p| normalize(t, &p));
+        let block = cells
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_some_and(|p| get_value(u, &p) != State::Dead))
+            .fold(0, |acc, (i, _)| acc | 1 << i);
+        for (i, p) in cells.iter().enumerate() {
+            if let (Some(p), true) = (p, b[block] & 1 << i != 0) {
+                value.insert(*p, State::Alive);
+            }
+        }
+    }
+    set_value(u, value);
+}
+
+/// Moves to the next generation, on the partition of its parity.
+pub fn iterate(u: &mut Universe, b: &Block, t: &Topology) {
+    apply(u, &at(b, u.age), t, (u.age % 2) as i64);
+    u.age += 1;
+}
+
+/// Goes back to the previous generation, which only reversible rules can do
+/// without keeping it, and not before the first one.
+pub fn step_backward(u: &mut Universe, b: &Block, t: &Topology) -> bool {
+    if u.age == 0 {
+        return false;
+    }
+    let Some(inverse) = inverse(&at(b, u.age - 1)) else {
+        return false;
+    };
+    u.age -= 1;
+    apply(u, &inverse, t, (u.age % 2) as i64);
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use crate::universe::from_string as universe_from_string;
+
+    use super::*;
+
+    fn cells(rows: &[&str]) -> Universe {
+        universe_from_string(rows.iter().map(|r| String::from(*r)).collect()).unwrap()
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(from_string("MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15"), Ok(BBM));
+        assert_eq!(from_string("ms,d15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0"), Ok(CRITTERS));
+        assert_eq!(from_string(&to_string(&CRITTERS)), Ok(CRITTERS));
+        assert_eq!(builtin("critters"), Some(CRITTERS));
+        assert_eq!(builtin("BBM"), Some(BBM));
+        assert_eq!(builtin("Tron"), None);
+        assert_eq!(name(&BBM), Some("BBM"));
+        assert_eq!(name(&[0; 16]), None);
+        let err = Err(FromStringErr::InvalidFormat(InvalidFormatErr));
+        assert_eq!(from_string("MS,D0;1;2"), err);
+        assert_eq!(from_string("MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;16"), err);
+        assert_eq!(from_string("MS,"), err);
+        assert_eq!(
+            from_string("MS,D1;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15"),
+            Err(FromStringErr::UnsupportedB0(UnsupportedB0Err))
+        );
+        assert_eq!(
+            from_string("MS,D15;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15"),
+            Err(FromStringErr::UnsupportedB0(UnsupportedB0Err))
+        );
+    }
+
+    #[test]
+    fn test_inverse() {
+        assert_eq!(inverse(&BBM), Some(BBM));
+        let critters = inverse(&CRITTERS).unwrap();
+        assert!((0..16).all(|i| usize::from(critters[usize::from(CRITTERS[i])]) == i));
+        assert_eq!(inverse(&[0; 16]), None);
+    }
+
+    #[test]
+    fn test_iterate_bbm() {
+        let mut u = cells(&["⬜⬛", "⬛⬛"]);
+        let ball = |u: &Universe| u.value.keys().copied().collect::<Vec<CartesianP>>();
+        assert_eq!(ball(&u), vec![CartesianP::of(-1, 0)]);
+        iterate(&mut u, &BBM, &Topology::Plane);
+        assert_eq!(ball(&u), vec![CartesianP::of(-2, 1)]);
+        iterate(&mut u, &BBM, &Topology::Plane);
+        assert_eq!(ball(&u), vec![CartesianP::of(-3, 2)]);
+        assert_eq!(u.age, 2);
+        assert!(step_backward(&mut u, &BBM, &Topology::Plane));
+        assert!(step_backward(&mut u, &BBM, &Topology::Plane));
+        assert_eq!(u, cells(&["⬜⬛", "⬛⬛"]));
+        assert!(!step_backward(&mut u, &BBM, &Topology::Plane));
+        assert!(!step_backward(&mut Universe { age: 1, ..u }, &[0; 16], &Topology::Plane));
+    }
+
+    #[test]
+    fn test_iterate_rotation() {
+        // Turning each block clockwise moves its cells without making any.
+        let mut rotation: Block = [0; 16];
+        for (i, n) in rotation.iter_mut().enumerate() {
+            let turn = [(1, 2), (2, 8), (8, 4), (4, 1)];
+            *n = turn.iter().filter(|(from, _)| i & from != 0).fold(0, |acc, (_, to)| acc | to);
+        }
+        let soup = cells(&[
+            "⬜⬛⬜⬜⬛⬛⬜⬛",
+            "⬛⬜⬜⬛⬜⬛⬛⬜",
+            "⬜⬜⬛⬛⬛⬜⬜⬛",
+            "⬛⬛⬜⬜⬛⬜⬛⬜",
+            "⬜⬛⬛⬜⬜⬜⬛⬛",
+            "⬛⬜⬛⬜⬜⬛⬜⬜",
+        ]);
+        let t = Topology::Torus { width: 8, height: 6 };
+        let mut u = soup.clone();
+        for _ in 0..20 {
+            iterate(&mut u, &rotation, &t);
+            assert_eq!(u.value.len(), soup.value.len());
+        }
+    }
+
+    #[test]
+    fn test_iterate_critters() {
+        let soup = cells(&[
+            "⬜⬛⬜⬜⬛⬛⬜⬛",
+            "⬛⬜⬜⬛⬜⬛⬛⬜",
+            "⬜⬜⬛⬛⬛⬜⬜⬛",
+            "⬛⬛⬜⬜⬛⬜⬛⬜",
+            "⬜⬛⬛⬜⬜⬜⬛⬛",
+            "⬛⬜⬛⬜⬜⬛⬜⬜",
+        ]);
+        for t in [Topology::Plane, Topology::Torus { width: 12, height: 10 }] {
+            let mut u = soup.clone();
+            for _ in 0..50 {
+                iterate(&mut u, &CRITTERS, &t);
+            }
+            assert_eq!(u.age, 50);
+            assert_ne!(u, soup);
+            while step_backward(&mut u, &CRITTERS, &t) {}
+            assert_eq!(u, soup);
+        }
+        let mut empty = Universe::default();
+        iterate(&mut empty, &CRITTERS, &Topology::Plane);
+        iterate(&mut empty, &CRITTERS, &Topology::Plane);
+        assert_eq!(empty.value, HashMap::new());
+    }
+}
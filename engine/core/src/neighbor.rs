@@ -0,0 +1,276 @@
+use crate::{
+    cell::State,
+    geometry::coordinate::CartesianP,
+    topology::{normalize, Topology},
+    universe::Universe,
+};
+
+use super::universe::get_value;
+
+pub const MAX_RANGE: u8 = 7;
+
+/// The cells counted around each cell. The hexagonal neighborhood is a Moore
+/// one skewed by leaving out the top right and bottom left corners.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Neighborhood {
+    Moore { range: u8 },
+    VonNeumann { range: u8 },
+    Hexagonal,
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Neighborhood::Moore { range: 1 }
+    }
+}
+
+/// The offsets of the neighbors, without the cell itself, from top left to
+/// bottom right.
+pub fn offsets(n: &Neighborhood) -> Vec<CartesianP> {
+    let range = match n {
+        Neighborhood::Moore { range } | Neighborhood::VonNeumann { range } => i64::from(*range),
+        Neighborhood::Hexagonal => 1,
+    };
+    let mut result = Vec::new();
+    for y in (-range..=range).rev() {
+        for x in -range..=range {
+            let included = match n {
+                Neighborhood::Moore { .. } => true,
+                Neighborhood::VonNeumann { .. } => x.abs() + y.abs() <= range,
+                Neighborhood::Hexagonal => x != y,
+            };
+            if included && (x, y) != (0, 0) {
+                result.push(CartesianP::of(x, y));
+            }
+        }
+    }
+    result
+}
+
+fn number_of_alive(neighbors: impl Iterator<Item = State>) -> u8 {
+    neighbors.filter(|neighbor| neighbor == &State::Alive).count() as u8
+}
+
+fn get_value_in_topology(u: &Universe, t: &Topology, p: &CartesianP) -> State {
+    match normalize(t, p) {
+        Some(p) => get_value(u, &p),
+        None => State::Dead,
+    }
+}
+
+pub fn number_of_alive_from_model(
+    u: &Universe,
+    p: &CartesianP,
+    offsets: &[CartesianP],
+    t: &Topology,
+) -> u8 {
+    number_of_alive(
+        offsets.iter().map(|o| get_value_in_topology(u, t, &CartesianP::of(p.x + o.x, p.y + o.y))),
+    )
+}
+
+/// The states of the neighbors, in the order of the offsets.
+pub fn states_from_model(
+    u: &Universe,
+    p: &CartesianP,
+    offsets: &[CartesianP],
+    t: &Topology,
+) -> Vec<State> {
+    offsets
+        .iter()
+        .map(|o| get_value_in_topology(u, t, &CartesianP::of(p.x + o.x, p.y + o.y)))
+        .collect()
+}
+
+/// The alive neighbors among the 8 closest as bits, from the top left to the
+/// bottom right, for the isotropic rules.
+pub fn configuration_from_model(u: &Universe, p: &CartesianP, t: &Topology) -> u8 {
+    offsets(&Neighborhood::default()).iter().enumerate().fold(0, |acc, (i, o)| {
+        match get_value_in_topology(u, t, &CartesianP::of(p.x + o.x, p.y + o.y)) {
+            State::Alive => acc | (1 << i),
+            _ => acc,
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_number_of_alive() {
+        assert_eq!(
+            number_of_alive(
+                [
+                    State::Dead,
+                    State::Dead,
+                    State::Dead,
+                    State::Dead,
+                    State::Dead,
+                    State::Dead,
+                    State::Dead,
+                    State::Dead,
+                ]
+                .into_iter()
+            ),
+            0
+        );
+        assert_eq!(
+            number_of_alive(
+                [
+                    State::Alive,
+                    State::Alive,
+                    State::Alive,
+                    State::Alive,
+                    State::Alive,
+                    State::Alive,
+                    State::Alive,
+                    State::Alive,
+                ]
+                .into_iter()
+            ),
+            8
+        );
+        assert_eq!(
+            number_of_alive(
+                [
+                    State::Alive,
+                    State::Dead,
+                    State::Alive,
+                    State::Dead,
+                    State::Alive,
+                    State::Dead,
+                    State::Alive,
+                    State::Dead
+                ]
+                .into_iter()
+            ),
+            4
+        );
+        assert_eq!(
+            number_of_alive(
+                [
+                    State::Dead,
+                    State::Alive,
+                    State::Dead,
+                    State::Alive,
+                    State::Dead,
+                    State::Alive,
+                    State::Dead,
+                    State::Alive
+                ]
+                .into_iter()
+            ),
+            4
+        );
+    }
+
+    #[test]
+    fn test_number_of_alive_from_model() {
+        let u = Universe::from(std::collections::HashMap::from([
+            (CartesianP::of(-2, 0), State::Alive),
+            (CartesianP::of(2, 1), State::Alive),
+            (CartesianP::of(0, -1), State::Alive),
+        ]));
+        let p = CartesianP::of(2, 0);
+        let moore = offsets(&Neighborhood::default());
+        assert_eq!(number_of_alive_from_model(&u, &p, &moore, &Topology::Plane), 1);
+        assert_eq!(
+            number_of_alive_from_model(&u, &p, &moore, &Topology::Bounded { width: 5, height: 3 }),
+            1
+        );
+        assert_eq!(
+            number_of_alive_from_model(&u, &p, &moore, &Topology::Torus { width: 5, height: 3 }),
+            2
+        );
+        assert_eq!(
+            number_of_alive_from_model(&u, &p, &moore, &Topology::Torus { width: 5, height: 0 }),
+            2
+        );
+        assert_eq!(
+            number_of_alive_from_model(
+                &u,
+                &CartesianP::of(0, 1),
+                &moore,
+                &Topology::Torus { width: 5, height: 3 }
+            ),
+            1
+        );
+        assert_eq!(
+            number_of_alive_from_model(
+                &u,
+                &CartesianP::of(0, 1),
+                &moore,
+                &Topology::Torus { width: 5, height: 0 }
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn test_offsets() {
+        assert_eq!(offsets(&Neighborhood::default()).len(), 8);
+        assert_eq!(
+            offsets(&Neighborhood::VonNeumann { range: 1 }),
+            vec![
+                CartesianP::of(0, 1),
+                CartesianP::of(-1, 0),
+                CartesianP::of(1, 0),
+                CartesianP::of(0, -1)
+            ]
+        );
+        assert_eq!(
+            offsets(&Neighborhood::Hexagonal),
+            vec![
+                CartesianP::of(-1, 1),
+                CartesianP::of(0, 1),
+                CartesianP::of(-1, 0),
+                CartesianP::of(1, 0),
+                CartesianP::of(0, -1),
+                CartesianP::of(1, -1)
+            ]
+        );
+        assert_eq!(offsets(&Neighborhood::Moore { range: 5 }).len(), 120);
+        assert_eq!(offsets(&Neighborhood::VonNeumann { range: 2 }).len(), 12);
+        assert_eq!(offsets(&Neighborhood::Moore { range: MAX_RANGE }).len(), 224);
+    }
+
+    #[test]
+    fn test_configuration_from_model() {
+        let u = Universe::from(std::collections::HashMap::from([
+            (CartesianP::of(-1, 1), State::Alive),
+            (CartesianP::of(-1, 0), State::Alive),
+            (CartesianP::of(0, -1), State::Alive),
+            (CartesianP::of(1, -1), State::Dying(1)),
+        ]));
+        assert_eq!(
+            configuration_from_model(&u, &CartesianP::of(0, 0), &Topology::Plane),
+            0b0100_1001
+        );
+        assert_eq!(
+            configuration_from_model(
+                &u,
+                &CartesianP::of(1, 0),
+                &Topology::Torus { width: 3, height: 3 }
+            ),
+            0b0011_0100
+        );
+    }
+
+    #[test]
+    fn test_number_of_alive_in_neighborhood() {
+        let u = Universe::from(std::collections::HashMap::from([
+            (CartesianP::of(1, 1), State::Alive),
+            (CartesianP::of(-1, 1), State::Alive),
+            (CartesianP::of(0, -1), State::Alive),
+            (CartesianP::of(0, -2), State::Alive),
+        ]));
+        let p = CartesianP::of(0, 0);
+        let count = |n| number_of_alive_from_model(&u, &p, &offsets(&n), &Topology::Plane);
+        assert_eq!(count(Neighborhood::default()), 3);
+        assert_eq!(count(Neighborhood::VonNeumann { range: 1 }), 1);
+        assert_eq!(count(Neighborhood::Hexagonal), 2);
+        assert_eq!(count(Neighborhood::VonNeumann { range: 2 }), 4);
+        assert_eq!(count(Neighborhood::Moore { range: 2 }), 4);
+    }
+}
@@ -0,0 +1,301 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use super::{
+    format::rle,
+    geometry::coordinate::MatrixP,
+    rule::Rule,
+    table::wireworld,
+    universe::{from_string, from_string_with, Anchor, GridFormat, Universe},
+};
+
+#[derive(Clone)]
+pub struct PresetDiscover {
+    pub name: String,
+    pub year: u16,
+}
+
+#[derive(Clone)]
+pub struct Preset {
+    pub name: String,
+    pub id: String,
+    pub discover: PresetDiscover,
+}
+
+#[derive(Clone)]
+pub struct PresetSubGroup {
+    pub name: String,
+    pub id: String,
+    pub items: Vec<Preset>,
+}
+
+pub struct PresetGroupInfo {
+    pub name: String,
+    pub id: String,
+}
+
+pub struct PresetGroup {
+    pub info: PresetGroupInfo,
+    pub sub_groups: Vec<PresetSubGroup>,
+}
+
+static PRESETS: LazyLock<HashMap<String, Universe>> = LazyLock::new(|| {
+    HashMap::from([
+        (
+            String::from("boat"),
+            from_string(vec![
+                String::from("⬜⬜⬛"),
+                String::from("⬜⬛⬜"),
+                String::from("⬛⬜⬜"),
+            ])
+            .unwrap(),
+        ),
+        (
+            String::from("block"),
+            from_string(vec![
+                String::from("⬜⬜"),
+                String::from("⬜⬜"),
+            ])
+            .unwrap(),
+        ),
+        (
+            String::from("blinker"),
+            from_string(vec![
+                String::from("⬜"),
+                String::from("⬜"),
+                String::from("⬜"),
+            ])
+            .unwrap(),
+        ),
+        (
+            String::from("r_pentomino"),
+            from_string(vec![
+                String::from("⬛⬜⬜"),
+                String::from("⬜⬜⬛"),
+                String::from("⬛⬜⬛"),
+            ])
+            .unwrap(),
+        ),
+        (
+            String::from("glider"),
+            from_string(vec![
+                String::from("⬜⬛⬜"),
+                String::from("⬛⬜⬜"),
+                String::from("⬛⬜⬛"),
+            ])
+            .unwrap(),
+        ),
+        (
+            String::from("gosper_glider_gun"),
+            from_string_with(
+                vec![
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜"),
+                    String::from("⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬛⬜⬜⬛⬛⬛⬛⬜⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+                ],
+                &GridFormat { anchor: Anchor::At(MatrixP::of(3, 18)), ..GridFormat::default() },
+            )
+            .unwrap(),
+        ),
+        (
+            String::from("puffer1"),
+            from_string(vec![
+                String::from("⬛⬜⬜⬜⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬜⬜⬜⬛"),
+                String::from("⬜⬛⬛⬜⬛⬛⬛⬛⬛⬜⬜⬜⬛⬛⬛⬜⬜⬜⬛⬛⬛⬛⬛⬜⬛⬛⬜"),
+                String::from("⬛⬛⬛⬜⬛⬛⬛⬛⬜⬜⬛⬜⬛⬛⬛⬜⬛⬜⬜⬛⬛⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬛⬜⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬛⬜⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬜⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬜⬛⬛"),
+            ])
+            .unwrap(),
+        ),
+        (
+            String::from("wireworld_diode"),
+            rle::from_string(
+                "x = 17, y = 7, rule = WireWorld\n.BA2C5.2C$C4.6C.5C$.4C5.2C2$.BA2C7.2C$C4.7C.4C$.4C7.2C!",
+            )
+            .unwrap()
+            .universe,
+        ),
+        (
+            String::from("wireworld_clock"),
+            rle::from_string("x = 14, y = 3, rule = WireWorld\n.BA2C$C4.9C$.4C!").unwrap().universe,
+        ),
+        (
+            String::from("wireworld_xor"),
+            rle::from_string(
+                "x = 26, y = 9, rule = WireWorld\n.BA3C$C5.13C$.5C13.C$18.4C$18.C2.5C$18.4C$.BA6C10.C$C8.10C$.8C!",
+            )
+            .unwrap()
+            .universe,
+        ),
+    ])
+});
+
+pub fn get_preset_unsafe(preset: &str) -> Universe {
+    PRESETS.get(preset).cloned().unwrap()
+}
+
+pub fn get_preset(preset: &String) -> Option<Universe> {
+    PRESETS.get(preset).cloned()
+}
+
+/// The rule a preset is meant for, when it isn't a Life pattern.
+pub fn get_preset_rule(preset: &str) -> Option<Rule> {
+    preset.starts_with("wireworld_").then(wireworld)
+}
+
+pub fn get_preset_groups() -> Vec<PresetGroup> {
+    vec![
+        PresetGroup {
+            info: PresetGroupInfo {
+                name: String::from("StillLife"),
+                id: String::from("stillLife"),
+            },
+            sub_groups: vec![
+                PresetSubGroup {
+                    name: String::from("Ship"),
+                    id: String::from("ship"),
+                    items: vec![],
+                },
+                PresetSubGroup {
+                    name: String::from("Boat"),
+                    id: String::from("boat"),
+                    items: vec![Preset {
+                        name: String::from("Boat"),
+                        id: String::from("boat"),
+                        discover: PresetDiscover { name: String::from("JHC group"), year: 1970 },
+                    }],
+                },
+                PresetSubGroup {
+                    name: String::from("Loaf"),
+                    id: String::from("loaf"),
+                    items: vec![],
+                },
+                PresetSubGroup {
+                    name: String::from("General"),
+                    id: String::from("general"),
+                    items: vec![Preset {
+                        name: String::from("Block"),
+                        id: String::from("block"),
+                        discover: PresetDiscover { name: String::from("John Conway"), year: 1969 },
+                    }],
+                },
+            ],
+        },
+        PresetGroup {
+            info: PresetGroupInfo {
+                name: String::from("Oscillators"),
+                id: String::from("oscillators"),
+            },
+            sub_groups: vec![PresetSubGroup {
+                name: String::from("General"),
+                id: String::from("general"),
+                items: vec![Preset {
+                    name: String::from("Blinker"),
+                    id: String::from("blinker"),
+                    discover: PresetDiscover { name: String::from("John Conway"), year: 1969 },
+                }],
+            }],
+        },
+        PresetGroup {
+            info: PresetGroupInfo {
+                name: String::from("Methuselahs"),
+                id: String::from("methuselahs"),
+            },
+            sub_groups: vec![PresetSubGroup {
+                name: String::from("General"),
+                id: String::from("general"),
+                items: vec![Preset {
+                    name: String::from("R-Pentomino"),
+                    id: String::from("r_pentomino"),
+                    discover: PresetDiscover { name: String::from("John Conway"), year: 1969 },
+                }],
+            }],
+        },
+        PresetGroup {
+            info: PresetGroupInfo {
+                name: String::from("Spaceships"),
+                id: String::from("spaceships"),
+            },
+            sub_groups: vec![PresetSubGroup {
+                name: String::from("General"),
+                id: String::from("general"),
+                items: vec![Preset {
+                    name: String::from("Glider"),
+                    id: String::from("glider"),
+                    discover: PresetDiscover { name: String::from("Richard K. Guy"), year: 1969 },
+                }],
+            }],
+        },
+        PresetGroup {
+            info: PresetGroupInfo {
+                name: String::from("Glider gun"),
+                id: String::from("gliderGun"),
+            },
+            sub_groups: vec![PresetSubGroup {
+                name: String::from("General"),
+                id: String::from("general"),
+                items: vec![Preset {
+                    name: String::from("Gosper glider gun"),
+                    id: String::from("gosper_glider_gun"),
+                    discover: PresetDiscover { name: String::from("Bill Gosper"), year: 1970 },
+                }],
+            }],
+        },
+        PresetGroup {
+            info: PresetGroupInfo { name: String::from("Puffer"), id: String::from("puffer") },
+            sub_groups: vec![PresetSubGroup {
+                name: String::from("General"),
+                id: String::from("general"),
+                items: vec![Preset {
+                    name: String::from("Puffer 1"),
+                    id: String::from("puffer1"),
+                    discover: PresetDiscover { name: String::from("Bill Gosper"), year: 1971 },
+                }],
+            }],
+        },
+        PresetGroup {
+            info: PresetGroupInfo {
+                name: String::from("Wireworld"),
+                id: String::from("wireworld"),
+            },
+            sub_groups: vec![PresetSubGroup {
+                name: String::from("General"),
+                id: String::from("general"),
+                items: vec![
+                    Preset {
+                        name: String::from("Diode"),
+                        id: String::from("wireworld_diode"),
+                        discover: PresetDiscover {
+                            name: String::from("Brian Silverman"),
+                            year: 1987,
+                        },
+                    },
+                    Preset {
+                        name: String::from("Clock"),
+                        id: String::from("wireworld_clock"),
+                        discover: PresetDiscover {
+                            name: String::from("Brian Silverman"),
+                            year: 1987,
+                        },
+                    },
+                    Preset {
+                        name: String::from("XOR gate"),
+                        id: String::from("wireworld_xor"),
+                        discover: PresetDiscover {
+                            name: String::from("Brian Silverman"),
+                            year: 1987,
+                        },
+                    },
+                ],
+            }],
+        },
+    ]
+}
@@ -0,0 +1,299 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::{cell::State, geometry::coordinate::CartesianP, neighbor::Neighborhood, rule::Rule};
+
+pub const CHUNK_SIZE: i64 = 32;
+
+pub type Chunk = HashMap<CartesianP, State>;
+
+/// Chunks that didn't change are shared between generations, so comparing
+/// them is often comparing pointers.
+pub type Chunks = HashMap<CartesianP, Arc<Chunk>>;
+
+pub fn chunk_of(p: &CartesianP) -> CartesianP {
+    CartesianP::of(p.x.div_euclid(CHUNK_SIZE), p.y.div_euclid(CHUNK_SIZE))
+}
+
+pub fn split(value: &HashMap<CartesianP, State>) -> Chunks {
+    let mut result = HashMap::<CartesianP, Chunk>::new();
+    for (p, s) in value {
+        result.entry(chunk_of(p)).or_default().insert(*p, *s);
+    }
+    result.into_iter().map(|(c, chunk)| (c, Arc::new(chunk))).collect()
+}
+
+fn same(a: Option<&Arc<Chunk>>, b: Option<&Arc<Chunk>>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => Arc::ptr_eq(a, b) || a == b,
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+/// How many chunks away the neighbors of a cell can be.
+pub fn reach(n: &Neighborhood) -> i64 {
+    let range = match n {
+        Neighborhood::Moore { range } | Neighborhood::VonNeumann { range } => i64::from(*range),
+        Neighborhood::Hexagonal => 1,
+    };
+    (range + CHUNK_SIZE - 1) / CHUNK_SIZE
+}
+
+pub fn around(c: &CartesianP, reach: i64) -> impl Iterator<Item = CartesianP> + '_ {
+    (-reach..=reach)
+        .flat_map(move |dy| (-reach..=reach).map(move |dx| CartesianP::of(c.x + dx, c.y + dy)))
+}
+
+/// The cells of the last generation computed by chunk, and the chunks that
+/// changed in it, marked while computing it or by `edit`. A chunk with no
+/// change around it comes out the same, and one whose surroundings are back
+/// to what they were a generation earlier comes out as it was then, so still
+/// lifes and blinkers left behind by a pattern cost nothing. Other changes to
+/// the cells must drop the regions, as `universe::set_value` does.
+#[derive(Default)]
+pub struct Regions {
+    rule: Option<Rule>,
+    population: usize,
+    chunks: Chunks,
+    /// The chunks that changed in the last generation, with what they were
+    /// before it, unless that's unknown.
+    before: Option<HashMap<CartesianP, Option<Arc<Chunk>>>>,
+    /// The chunks that aren't the same as two generations ago, unless that's
+    /// unknown.
+    changed_twice: Option<HashSet<CartesianP>>,
+    /// The chunks edited since the last generation, which don't come from
+    /// the one before it.
+    edited: HashSet<CartesianP>,
+    /// Left by `mark` until the cells change.
+    mark: Option<u64>,
+}
+
+/// The cache is rebuilt after a clone, which keeps the history light.
+impl Clone for Regions {
+    fn clone(&self) -> Self {
+        Regions::default()
+    }
+}
+
+impl fmt::Debug for Regions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Regions({} chunks)", self.chunks.len())
+    }
+}
+
+pub struct Plan {
+    /// The chunks to compute.
+    pub compute: HashSet<CartesianP>,
+    /// The chunks that come out as they were a generation ago.
+    repeat: HashSet<CartesianP>,
+}
+
+/// Drops the regions of cells changed by other means, leaving a new mark
+/// that lasts until the cells change again, so an evolution keeping them in
+/// its own form can tell they didn't.
+pub fn mark(regions: &mut Regions) -> u64 {
+    static MARKS: AtomicU64 = AtomicU64::new(0);
+    let mark = MARKS.fetch_add(1, Ordering::Relaxed);
+    *regions = Regions { mark: Some(mark), ..Default::default() };
+    mark
+}
+
+pub fn is_marked(regions: &Regions, mark: u64) -> bool {
+    regions.mark == Some(mark)
+}
+
+/// The chunks the next generation of `value` has to compute, which are all
+/// of them when the regions don't know `value` under `rule`.
+pub fn plan(
+    regions: &mut Regions,
+    value: &HashMap<CartesianP, State>,
+    rule: &Rule,
+    reach: i64,
+) -> Plan {
+    regions.mark = None;
+    // A different population means the cells were replaced behind our back.
+    if regions.rule.as_ref() != Some(rule) || regions.population != value.len() {
+        *regions = Regions {
+            rule: Some(rule.clone()),
+            population: value.len(),
+            chunks: split(value),
+            ..Default::default()
+        };
+    }
+    let Some(before) = &regions.before else {
+        let compute = regions.chunks.keys().flat_map(|c| around(c, reach)).collect();
+        return Plan { compute, repeat: HashSet::new() };
+    };
+    let near: HashSet<CartesianP> = before.keys().flat_map(|c| around(c, reach)).collect();
+    let (repeat, compute) = near.into_iter().partition(|c| {
+        regions.changed_twice.as_ref().is_some_and(|t| around(c, reach).all(|d| !t.contains(&d)))
+    });
+    Plan { compute, repeat }
+}
+
+/// The cells whose neighbors may be in `chunks`.
+pub fn cells_around<'a>(
+    regions: &'a Regions,
+    chunks: &HashSet<CartesianP>,
+    reach: i64,
+) -> impl Iterator<Item = &'a CartesianP> {
+    let near: HashSet<CartesianP> = chunks.iter().flat_map(|c| around(c, reach)).collect();
+    near.into_iter().filter_map(|c| regions.chunks.get(&c)).flat_map(|chunk| chunk.keys())
+}
+
+/// Moves `value` and the regions to the next generation, given the cells
+/// computed for the chunks of `plan`. Only the chunks that changed touch
+/// `value`.
+pub fn update(
+    regions: &mut Regions,
+    value: &mut HashMap<CartesianP, State>,
+    plan: Plan,
+    computed: HashMap<CartesianP, State>,
+) {
+    let mut computed = split(&computed);
+    let previous = |c: &CartesianP| match regions.before.as_ref().and_then(|b| b.get(c)) {
+        Some(chunk) => chunk.clone(),
+        None => regions.chunks.get(c).cloned(),
+    };
+    let next: Vec<(CartesianP, Option<Arc<Chunk>>)> = plan
+        .compute
+        .into_iter()
+        .map(|c| (c, computed.remove(&c)))
+        .chain(plan.repeat.into_iter().map(|c| (c, previous(&c))))
+        .collect();
+    let changed_twice = regions.before.as_ref().map(|_| {
+        next.iter()
+            .filter(|(c, chunk)| !same(chunk.as_ref(), previous(c).as_ref()))
+            .map(|(c, _)| *c)
+            .chain(regions.edited.iter().copied())
+            .collect()
+    });
+    let mut before = HashMap::new();
+    for (c, chunk) in next {
+        if same(regions.chunks.get(&c), chunk.as_ref()) {
+            continue;
+        }
+        let current = match chunk {
+            Some(chunk) => {
+                value.extend(chunk.iter());
+                regions.chunks.insert(c, chunk)
+            }
+            None => regions.chunks.remove(&c),
+        };
+        before.insert(c, current);
+    }
+    // The cells left in a chunk that changed are the ones it lost.
+    for (c, chunk) in before.iter().filter_map(|(c, chunk)| chunk.as_ref().map(|chunk| (c, chunk)))
+    {
+        let now = regions.chunks.get(c);
+        for p in chunk.keys().filter(|p| now.is_none_or(|now| !now.contains_key(p))) {
+            value.remove(p);
+        }
+    }
+    regions.before = Some(before);
+    regions.changed_twice = changed_twice;
+    regions.edited.clear();
+    regions.population = value.len();
+}
+
+/// Keeps the regions in step with the cell at `p` set to `s`.
+pub fn edit(regions: &mut Regions, p: &CartesianP, s: State) {
+    regions.mark = None;
+    if regions.rule.is_none() {
+        return;
+    }
+    let c = chunk_of(p);
+    let current = regions.chunks.get(&c).cloned();
+    if let Some(before) = &mut regions.before {
+        before.entry(c).or_insert(current);
+    }
+    if let Some(changed_twice) = &mut regions.changed_twice {
+        changed_twice.insert(c);
+    }
+    regions.edited.insert(c);
+    let chunk = Arc::make_mut(regions.chunks.entry(c).or_default());
+    let was = match s {
+        State::Dead => chunk.remove(p),
+        _ => chunk.insert(*p, s),
+    };
+    if chunk.is_empty() {
+        regions.chunks.remove(&c);
+    }
+    regions.population =
+        regions.population + usize::from(s != State::Dead) - usize::from(was.is_some());
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        preset::get_preset_unsafe,
+        universe::{iterate, set_cell},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let value = HashMap::from([
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(CHUNK_SIZE - 1, CHUNK_SIZE - 1), State::Dying(1)),
+            (CartesianP::of(-1, CHUNK_SIZE), State::Alive),
+        ]);
+        let chunks = split(&value);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[&CartesianP::of(0, 0)].len(), 2);
+        assert_eq!(chunks[&CartesianP::of(-1, 1)][&CartesianP::of(-1, CHUNK_SIZE)], State::Alive);
+        assert_eq!(reach(&Neighborhood::default()), 1);
+        assert_eq!(reach(&Neighborhood::Moore { range: CHUNK_SIZE as u8 + 1 }), 2);
+    }
+
+    #[test]
+    fn test_plan() {
+        let rule = Rule::default();
+        let value = HashMap::from([(CartesianP::of(0, 0), State::Alive)]);
+        let mut regions = Regions::default();
+        let work = plan(&mut regions, &value, &rule, 1);
+        assert_eq!(work.compute.len(), 9);
+        let mut u = get_preset_unsafe("block");
+        iterate(&mut u, &rule);
+        let work = plan(&mut u.regions, &u.value, &rule, 1);
+        assert!(work.compute.is_empty());
+        assert!(work.repeat.is_empty());
+        set_cell(&mut u, CartesianP::of(CHUNK_SIZE * 3, 0), State::Alive);
+        let work = plan(&mut u.regions, &u.value, &rule, 1);
+        assert_eq!(work.compute.len(), 9);
+        // Cells changed behind the regions' back, they start over.
+        u.value.insert(CartesianP::of(CHUNK_SIZE * 6, 0), State::Alive);
+        let work = plan(&mut u.regions, &u.value, &rule, 1);
+        assert_eq!(work.compute.len(), 34);
+        assert_eq!(
+            plan(&mut u.regions, &u.value, &Rule::of(&[3], &[2, 3, 4]), 1).compute.len(),
+            34
+        );
+    }
+
+    #[test]
+    fn test_plan_still() {
+        let mut u = get_preset_unsafe("blinker");
+        for (x, y) in [(40, 40), (41, 40), (40, 41), (41, 41)] {
+            u.value.insert(CartesianP::of(x, y), State::Alive);
+        }
+        let rule = Rule::default();
+        iterate(&mut u, &rule);
+        assert!(!plan(&mut u.regions, &u.value, &rule, 1).compute.is_empty());
+        iterate(&mut u, &rule);
+        let work = plan(&mut u.regions, &u.value, &rule, 1);
+        assert!(work.compute.is_empty());
+        assert!(!work.repeat.is_empty());
+        let mut next = u.clone();
+        iterate(&mut next, &rule);
+        iterate(&mut u, &rule);
+        assert_eq!(u, next);
+    }
+}
@@ -0,0 +1,357 @@
+use super::{
+    cell::State,
+    geometry::{
+        coordinate::cartesian_to_matrix,
+        poligon::rect::{get_length, Rect, RectF64},
+    },
+    neighbor::Neighborhood,
+    topology::{get_spans, Topology},
+    universe::Universe,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RenderSettings {
+    pub cam: Rect,
+    pub dim: u16,
+    pub gap: u8,
+}
+
+/// Returns the alive and dying cells in the camera, each with its state so
+/// that the decaying stages of Generations rules and the colors of colored
+/// rules can be told apart.
+pub fn get_values_to_render(u: &Universe, s: &RenderSettings) -> Vec<(RectF64, State)> {
+    get_values_to_render_with(u, s, &Neighborhood::default())
+}
+
+/// Same as `get_values_to_render`, but the hexagonal neighborhood shears the
+/// rows around the middle one by half a cell each, so that the six neighbors
+/// of a cell surround it.
+pub fn get_values_to_render_with(
+    u: &Universe,
+    s: &RenderSettings,
+    n: &Neighborhood,
+) -> Vec<(RectF64, State)> {
+    let dim = f64::from(s.dim);
+    let len = get_length(&s.cam) as f64;
+    let cell_size = dim / len;
+    let middle_row = ((len - 1.0) / 2.0).floor();
+    let mut values_to_render: Vec<(RectF64, State)> = u
+        .value
+        .iter()
+        .filter(|value| {
+            value.0.x >= s.cam.x1
+                && value.0.x <= s.cam.x2
+                && value.0.y >= s.cam.y1
+                && value.0.y <= s.cam.y2
+        })
+        .filter(|value| value.1 != &State::Dead)
+        .map(|value| {
+            let arr_index = cartesian_to_matrix(value.0, &s.cam);
+            let gap = f64::from(s.gap);
+            let row = arr_index.row as f64;
+            let col = match n {
+                Neighborhood::Hexagonal => arr_index.col as f64 + (middle_row - row) / 2.0,
+                _ => arr_index.col as f64,
+            };
+            let rect = RectF64 {
+                x1: col * cell_size + gap,
+                y1: row * cell_size + gap,
+                x2: col * cell_size + cell_size - gap,
+                y2: row * cell_size + cell_size - gap,
+            };
+            (rect, *value.1)
+        })
+        .collect();
+    values_to_render
+        .sort_by(|a, b| a.0.y1.partial_cmp(&b.0.y1).unwrap_or(std::cmp::Ordering::Greater));
+    values_to_render
+        .sort_by(|a, b| a.0.x1.partial_cmp(&b.0.x1).unwrap_or(std::cmp::Ordering::Greater));
+    values_to_render
+}
+
+/// Draws the row at y = 0 of each generation under the one before, so that an
+/// elementary rule shows as a space-time diagram. `rows` holds the earlier
+/// generations, oldest first, and only the ones leading to `u` without a gap
+/// are drawn. Once the camera is full, the oldest rows scroll out at the top.
+pub fn get_space_time_to_render(
+    rows: &[Universe],
+    u: &Universe,
+    s: &RenderSettings,
+) -> Vec<(RectF64, State)> {
+    let len = get_length(&s.cam);
+    let cell_size = f64::from(s.dim) / len as f64;
+    let gap = f64::from(s.gap);
+    let mut generations = vec![u];
+    for row in rows.iter().rev() {
+        if generations.len() as u64 == len || row.age + generations.len() as u64 != u.age {
+            break;
+        }
+        generations.push(row);
+    }
+    generations.reverse();
+    let mut values_to_render: Vec<(RectF64, State)> = generations
+        .iter()
+        .enumerate()
+        .flat_map(|(row, generation)| {
+            generation
+                .value
+                .iter()
+                .filter(|(p, s)| p.y == 0 && **s != State::Dead)
+                .map(move |(p, s)| (row as f64, p, *s))
+        })
+        .filter(|(_, p, _)| p.x >= s.cam.x1 && p.x <= s.cam.x2)
+        .map(|(row, p, state)| {
+            let col = (p.x - s.cam.x1) as f64;
+            let rect = RectF64 {
+                x1: col * cell_size + gap,
+                y1: row * cell_size + gap,
+                x2: col * cell_size + cell_size - gap,
+                y2: row * cell_size + cell_size - gap,
+            };
+            (rect, state)
+        })
+        .collect();
+    values_to_render
+        .sort_by(|a, b| a.0.y1.partial_cmp(&b.0.y1).unwrap_or(std::cmp::Ordering::Greater));
+    values_to_render
+        .sort_by(|a, b| a.0.x1.partial_cmp(&b.0.x1).unwrap_or(std::cmp::Ordering::Greater));
+    values_to_render
+}
+
+/// The part of the canvas covered by a finite topology, or `None` when the
+/// whole canvas is inside it. An unbounded axis covers the whole canvas.
+pub fn get_topology_to_render(t: &Topology, s: &RenderSettings) -> Option<RectF64> {
+    let (xs, ys) = get_spans(t);
+    if xs.is_none() && ys.is_none() {
+        return None;
+    }
+    let dim = f64::from(s.dim);
+    let len = get_length(&s.cam) as f64;
+    let cell_size = dim / len;
+    let clamp = |value: f64| value.clamp(0.0, dim);
+    let (x1, x2) = xs.map_or((0.0, dim), |(x1, x2)| {
+        (clamp((x1 - s.cam.x1) as f64 * cell_size), clamp((x2 - s.cam.x1 + 1) as f64 * cell_size))
+    });
+    let (y1, y2) = ys.map_or((0.0, dim), |(y1, y2)| {
+        (clamp((s.cam.y2 - y2) as f64 * cell_size), clamp((s.cam.y2 - y1 + 1) as f64 * cell_size))
+    });
+    Some(RectF64 { x1, y1, x2, y2 })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use crate::{geometry::coordinate::CartesianP, universe::from_string};
+
+    use super::*;
+
+    fn get_universe() -> Universe {
+        from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render() {
+        let u = get_universe();
+        let s = RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1000, gap: 0 };
+        assert_eq!(
+            get_values_to_render(&u, &s),
+            vec![
+                (RectF64 { x1: 0.0, y1: 0.0, x2: 100.0, y2: 100.0 }, State::Alive),
+                (RectF64 { x1: 0.0, y1: 900.0, x2: 100.0, y2: 1000.0 }, State::Alive),
+                (RectF64 { x1: 100.0, y1: 100.0, x2: 200.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 100.0, y1: 800.0, x2: 200.0, y2: 900.0 }, State::Alive),
+                (RectF64 { x1: 800.0, y1: 100.0, x2: 900.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 800.0, y1: 800.0, x2: 900.0, y2: 900.0 }, State::Alive),
+                (RectF64 { x1: 900.0, y1: 0.0, x2: 1000.0, y2: 100.0 }, State::Alive),
+                (RectF64 { x1: 900.0, y1: 900.0, x2: 1000.0, y2: 1000.0 }, State::Alive),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_gap() {
+        let u = get_universe();
+        let cam = Rect::of(-5, -5, 4, 4);
+        let s_gap1 = RenderSettings { cam, dim: 1000, gap: 1 };
+        let s_gap2 = RenderSettings { cam, dim: 1000, gap: 2 };
+        assert_eq!(
+            get_values_to_render(&u, &s_gap1),
+            vec![
+                (RectF64 { x1: 1.0, y1: 1.0, x2: 99.0, y2: 99.0 }, State::Alive),
+                (RectF64 { x1: 1.0, y1: 901.0, x2: 99.0, y2: 999.0 }, State::Alive),
+                (RectF64 { x1: 101.0, y1: 101.0, x2: 199.0, y2: 199.0 }, State::Alive),
+                (RectF64 { x1: 101.0, y1: 801.0, x2: 199.0, y2: 899.0 }, State::Alive),
+                (RectF64 { x1: 801.0, y1: 101.0, x2: 899.0, y2: 199.0 }, State::Alive),
+                (RectF64 { x1: 801.0, y1: 801.0, x2: 899.0, y2: 899.0 }, State::Alive),
+                (RectF64 { x1: 901.0, y1: 1.0, x2: 999.0, y2: 99.0 }, State::Alive),
+                (RectF64 { x1: 901.0, y1: 901.0, x2: 999.0, y2: 999.0 }, State::Alive),
+            ]
+        );
+        assert_eq!(
+            get_values_to_render(&u, &s_gap2),
+            vec![
+                (RectF64 { x1: 2.0, y1: 2.0, x2: 98.0, y2: 98.0 }, State::Alive),
+                (RectF64 { x1: 2.0, y1: 902.0, x2: 98.0, y2: 998.0 }, State::Alive),
+                (RectF64 { x1: 102.0, y1: 102.0, x2: 198.0, y2: 198.0 }, State::Alive),
+                (RectF64 { x1: 102.0, y1: 802.0, x2: 198.0, y2: 898.0 }, State::Alive),
+                (RectF64 { x1: 802.0, y1: 102.0, x2: 898.0, y2: 198.0 }, State::Alive),
+                (RectF64 { x1: 802.0, y1: 802.0, x2: 898.0, y2: 898.0 }, State::Alive),
+                (RectF64 { x1: 902.0, y1: 2.0, x2: 998.0, y2: 98.0 }, State::Alive),
+                (RectF64 { x1: 902.0, y1: 902.0, x2: 998.0, y2: 998.0 }, State::Alive),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_cam() {
+        let u = get_universe();
+        let s_cam_minus1 = RenderSettings { cam: Rect::of(-6, -5, 3, 4), dim: 1000, gap: 0 };
+        let s_cam_plus1 = RenderSettings { cam: Rect::of(-4, -5, 5, 4), dim: 1000, gap: 0 };
+        assert_eq!(
+            get_values_to_render(&u, &s_cam_minus1),
+            vec![
+                (RectF64 { x1: 100.0, y1: 0.0, x2: 200.0, y2: 100.0 }, State::Alive),
+                (RectF64 { x1: 100.0, y1: 900.0, x2: 200.0, y2: 1000.0 }, State::Alive),
+                (RectF64 { x1: 200.0, y1: 100.0, x2: 300.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 200.0, y1: 800.0, x2: 300.0, y2: 900.0 }, State::Alive),
+                (RectF64 { x1: 900.0, y1: 100.0, x2: 1000.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 900.0, y1: 800.0, x2: 1000.0, y2: 900.0 }, State::Alive),
+            ]
+        );
+        assert_eq!(
+            get_values_to_render(&u, &s_cam_plus1),
+            vec![
+                (RectF64 { x1: 0.0, y1: 100.0, x2: 100.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 0.0, y1: 800.0, x2: 100.0, y2: 900.0 }, State::Alive),
+                (RectF64 { x1: 700.0, y1: 100.0, x2: 800.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 700.0, y1: 800.0, x2: 800.0, y2: 900.0 }, State::Alive),
+                (RectF64 { x1: 800.0, y1: 0.0, x2: 900.0, y2: 100.0 }, State::Alive),
+                (RectF64 { x1: 800.0, y1: 900.0, x2: 900.0, y2: 1000.0 }, State::Alive),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_float_cell_size() {
+        let u = get_universe();
+        let s = RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 996, gap: 0 };
+        assert_eq!(
+            get_values_to_render(&u, &s),
+            vec![
+                (RectF64 { x1: 0.0, y1: 0.0, x2: 99.6, y2: 99.6 }, State::Alive),
+                (RectF64 { x1: 0.0, y1: 896.4, x2: 99.6, y2: 996.0 }, State::Alive),
+                (RectF64 { x1: 99.6, y1: 99.6, x2: 199.2, y2: 199.2 }, State::Alive),
+                (RectF64 { x1: 99.6, y1: 796.8, x2: 199.2, y2: 896.4 }, State::Alive),
+                (RectF64 { x1: 796.8, y1: 99.6, x2: 896.4, y2: 199.2 }, State::Alive),
+                (RectF64 { x1: 796.8, y1: 796.8, x2: 896.4, y2: 896.4 }, State::Alive),
+                (RectF64 { x1: 896.4, y1: 0.0, x2: 996.0, y2: 99.6 }, State::Alive),
+                (RectF64 { x1: 896.4, y1: 896.4, x2: 996.0, y2: 996.0 }, State::Alive)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_states() {
+        let u = Universe::from(HashMap::from([
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(1, 0), State::Dying(1)),
+            (CartesianP::of(2, 0), State::Dead),
+        ]));
+        let s = RenderSettings { cam: Rect::of(0, 0, 3, 3), dim: 400, gap: 0 };
+        assert_eq!(
+            get_values_to_render(&u, &s),
+            vec![
+                (RectF64 { x1: 0.0, y1: 300.0, x2: 100.0, y2: 400.0 }, State::Alive),
+                (RectF64 { x1: 100.0, y1: 300.0, x2: 200.0, y2: 400.0 }, State::Dying(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_hexagonal() {
+        let u = Universe::from(HashMap::from([
+            (CartesianP::of(0, 1), State::Alive),
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(0, -1), State::Alive),
+        ]));
+        let s = RenderSettings { cam: Rect::of(-2, -2, 2, 2), dim: 500, gap: 0 };
+        assert_eq!(
+            get_values_to_render_with(&u, &s, &Neighborhood::Hexagonal),
+            vec![
+                (RectF64 { x1: 150.0, y1: 300.0, x2: 250.0, y2: 400.0 }, State::Alive),
+                (RectF64 { x1: 200.0, y1: 200.0, x2: 300.0, y2: 300.0 }, State::Alive),
+                (RectF64 { x1: 250.0, y1: 100.0, x2: 350.0, y2: 200.0 }, State::Alive),
+            ]
+        );
+        assert_eq!(
+            get_values_to_render_with(&u, &s, &Neighborhood::VonNeumann { range: 1 }),
+            get_values_to_render(&u, &s)
+        );
+    }
+
+    #[test]
+    fn test_render_space_time() {
+        let row = |xs: &[i64], age: u64| Universe {
+            value: xs.iter().map(|x| (CartesianP::of(*x, 0), State::Alive)).collect(),
+            age,
+            ..Default::default()
+        };
+        let rows = vec![row(&[1], 0), row(&[0], 1), row(&[-1, 1], 2), row(&[0, 5], 3)];
+        let u = row(&[-2, 2], 4);
+        let s = RenderSettings { cam: Rect::of(-2, -2, 1, 1), dim: 400, gap: 0 };
+        assert_eq!(
+            get_space_time_to_render(&rows[..2], &row(&[-1, 1], 2), &s),
+            vec![
+                (RectF64 { x1: 100.0, y1: 200.0, x2: 200.0, y2: 300.0 }, State::Alive),
+                (RectF64 { x1: 200.0, y1: 100.0, x2: 300.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 300.0, y1: 0.0, x2: 400.0, y2: 100.0 }, State::Alive),
+                (RectF64 { x1: 300.0, y1: 200.0, x2: 400.0, y2: 300.0 }, State::Alive),
+            ]
+        );
+        assert_eq!(
+            get_space_time_to_render(&rows, &u, &s),
+            vec![
+                (RectF64 { x1: 0.0, y1: 300.0, x2: 100.0, y2: 400.0 }, State::Alive),
+                (RectF64 { x1: 100.0, y1: 100.0, x2: 200.0, y2: 200.0 }, State::Alive),
+                (RectF64 { x1: 200.0, y1: 0.0, x2: 300.0, y2: 100.0 }, State::Alive),
+                (RectF64 { x1: 200.0, y1: 200.0, x2: 300.0, y2: 300.0 }, State::Alive),
+                (RectF64 { x1: 300.0, y1: 100.0, x2: 400.0, y2: 200.0 }, State::Alive),
+            ]
+        );
+        assert_eq!(
+            get_space_time_to_render(&rows[..2], &u, &s),
+            vec![(RectF64 { x1: 0.0, y1: 0.0, x2: 100.0, y2: 100.0 }, State::Alive)]
+        );
+    }
+
+    #[test]
+    fn test_topology_to_render() {
+        let s = RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1000, gap: 0 };
+        assert_eq!(get_topology_to_render(&Topology::Plane, &s), None);
+        assert_eq!(
+            get_topology_to_render(&Topology::Torus { width: 4, height: 2 }, &s),
+            Some(RectF64 { x1: 300.0, y1: 400.0, x2: 700.0, y2: 600.0 })
+        );
+        assert_eq!(
+            get_topology_to_render(&Topology::Bounded { width: 100, height: 6 }, &s),
+            Some(RectF64 { x1: 0.0, y1: 200.0, x2: 1000.0, y2: 800.0 })
+        );
+        assert_eq!(
+            get_topology_to_render(&Topology::Torus { width: 4, height: 0 }, &s),
+            Some(RectF64 { x1: 300.0, y1: 0.0, x2: 700.0, y2: 1000.0 })
+        );
+    }
+}
@@ -0,0 +1,851 @@
+use std::{fmt, sync::Arc};
+
+use super::{
+    isotropic::{self, Isotropic},
+    margolus::{self, Block},
+    neighbor::{offsets, Neighborhood, MAX_RANGE},
+    table::{self, Table},
+    topology::{self, Topology},
+};
+
+pub const MAX_STATES: u8 = 255;
+
+/// How the next state of a cell is found, which makes the modes of a rule
+/// exclusive.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum RuleKind {
+    /// By the number of alive neighbors, through `birth` and `survival`.
+    #[default]
+    Totalistic,
+    /// By the configuration of the neighbors, which replaces the counts.
+    Isotropic(Box<Isotropic>),
+    /// By a rule table, which replaces the counts.
+    Table(Arc<Table>),
+    /// The row at y = 0 alone, by its Wolfram number.
+    Elementary(u8),
+    /// With `states - 1` colors of alive cells instead of dying stages, where
+    /// newborns take theirs from their parents.
+    Colored,
+    /// By 2x2 blocks on a partition that shifts every generation.
+    Margolus(Block),
+}
+
+/// A Life-like rule when `states` is 2, or a Generations rule where a cell
+/// that doesn't survive goes through `states - 2` dying stages. `birth` and
+/// `survival` are indexed by the number of alive neighbors, unless the `kind`
+/// of the rule replaces them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rule {
+    pub birth: Vec<bool>,
+    pub survival: Vec<bool>,
+    pub states: u8,
+    pub neighborhood: Neighborhood,
+    pub kind: RuleKind,
+    pub topology: Topology,
+}
+
+fn counts(values: &[u8]) -> Vec<bool> {
+    let len = values.iter().map(|n| usize::from(*n) + 1).max().unwrap_or(0).max(9);
+    let mut result = vec![false; len];
+    for n in values {
+        result[usize::from(*n)] = true;
+    }
+    result
+}
+
+impl Rule {
+    pub fn of(birth: &[u8], survival: &[u8]) -> Self {
+        Rule {
+            birth: counts(birth),
+            survival: counts(survival),
+            states: 2,
+            neighborhood: Neighborhood::default(),
+            kind: RuleKind::Totalistic,
+            topology: Topology::Plane,
+        }
+    }
+}
+
+impl Default for Rule {
+    fn default() -> Self {
+        Rule::of(&[3], &[2, 3])
+    }
+}
+
+fn digits_to_string(values: &[bool]) -> String {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value)
+        .map(|(n, _)| n.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+fn ranges_to_string(values: &[bool]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut n = 0;
+    while n < values.len() {
+        if !values[n] {
+            n += 1;
+            continue;
+        }
+        let start = n;
+        while n + 1 < values.len() && values[n + 1] {
+            n += 1;
+        }
+        ranges.push(format!("{start}..{n}"));
+        n += 1;
+    }
+    ranges.join(",")
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let isotropic = match &self.kind {
+            RuleKind::Totalistic => None,
+            RuleKind::Isotropic(i) => Some(i),
+            RuleKind::Table(t) => return write!(f, "{}{}", t.name, self.topology),
+            RuleKind::Elementary(n) => return write!(f, "W{n}{}", self.topology),
+            RuleKind::Margolus(b) => {
+                let name =
+                    margolus::name(b).map(String::from).unwrap_or_else(|| margolus::to_string(b));
+                return write!(f, "{name}{}", self.topology);
+            }
+            RuleKind::Colored => {
+                let name = if self.states == 3 { "Immigration" } else { "QuadLife" };
+                return write!(f, "{name}{}", self.topology);
+            }
+        };
+        match self.neighborhood {
+            Neighborhood::Moore { range } | Neighborhood::VonNeumann { range } if range > 1 => {
+                write!(
+                    f,
+                    "R{range},C{},M0,S{},B{},N{}",
+                    if self.states > 2 { self.states } else { 0 },
+                    ranges_to_string(&self.survival),
+                    ranges_to_string(&self.birth),
+                    if matches!(self.neighborhood, Neighborhood::VonNeumann { .. }) {
+                        'N'
+                    } else {
+                        'M'
+                    }
+                )?;
+            }
+            _ => {
+                match isotropic {
+                    Some(i) => write!(
+                        f,
+                        "B{}/S{}",
+                        isotropic::to_string(&i.birth),
+                        isotropic::to_string(&i.survival)
+                    )?,
+                    None => write!(
+                        f,
+                        "B{}/S{}",
+                        digits_to_string(&self.birth),
+                        digits_to_string(&self.survival)
+                    )?,
+                }
+                if self.states > 2 {
+                    write!(f, "/C{}", self.states)?;
+                }
+                match self.neighborhood {
+                    Neighborhood::VonNeumann { .. } => write!(f, "V")?,
+                    Neighborhood::Hexagonal => write!(f, "H")?,
+                    Neighborhood::Moore { .. } => {}
+                }
+            }
+        }
+        write!(f, "{}", self.topology)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidFormatErr;
+
+impl fmt::Display for InvalidFormatErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The rule must be in the \"B3/S23\", \"23/3\", \"345/2/4\" or \"R5,C0,M1,S34..58,B34..45,NM\" notation!"
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidNeighborsErr;
+
+impl fmt::Display for InvalidNeighborsErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The number of neighbors must be a digit from 0 to 8!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidLetterErr {
+    pub neighbors: u8,
+    pub letter: char,
+}
+
+impl fmt::Display for InvalidLetterErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The letters after {} must be among \"{}\"!",
+            self.neighbors,
+            isotropic::LETTERS[usize::from(self.neighbors)]
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidCountErr {
+    pub max: usize,
+}
+
+impl fmt::Display for InvalidCountErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The number of neighbors must be from 0 to {} in this neighborhood!", self.max)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidRangeErr;
+
+impl fmt::Display for InvalidRangeErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The range must be from 1 to {MAX_RANGE}!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidStatesErr;
+
+impl fmt::Display for InvalidStatesErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The number of states must be from 2 to {MAX_STATES}!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidWolframErr;
+
+impl fmt::Display for InvalidWolframErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The Wolfram rule number must be from 0 to 255!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct UnsupportedB0Err;
+
+impl fmt::Display for UnsupportedB0Err {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Rules with birth on 0 neighbors are not supported!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BlockTopologyErr;
+
+impl fmt::Display for BlockTopologyErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Block rules need an even width and height, and no twist, on a finite topology!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidFormat(InvalidFormatErr),
+    InvalidNeighbors(InvalidNeighborsErr),
+    InvalidLetter(InvalidLetterErr),
+    InvalidCount(InvalidCountErr),
+    InvalidRange(InvalidRangeErr),
+    InvalidStates(InvalidStatesErr),
+    InvalidWolfram(InvalidWolframErr),
+    UnsupportedB0(UnsupportedB0Err),
+    BlockTopology(BlockTopologyErr),
+    InvalidTopology(topology::FromStringErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidFormat(e) => e.fmt(f),
+            FromStringErr::InvalidNeighbors(e) => e.fmt(f),
+            FromStringErr::InvalidLetter(e) => e.fmt(f),
+            FromStringErr::InvalidCount(e) => e.fmt(f),
+            FromStringErr::InvalidRange(e) => e.fmt(f),
+            FromStringErr::InvalidStates(e) => e.fmt(f),
+            FromStringErr::InvalidWolfram(e) => e.fmt(f),
+            FromStringErr::UnsupportedB0(e) => e.fmt(f),
+            FromStringErr::BlockTopology(e) => e.fmt(f),
+            FromStringErr::InvalidTopology(e) => e.fmt(f),
+        }
+    }
+}
+
+fn parse_digits(as_str: &str) -> Result<Vec<u8>, FromStringErr> {
+    as_str
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(n) if n <= 8 => Ok(n as u8),
+            _ => Err(FromStringErr::InvalidNeighbors(InvalidNeighborsErr)),
+        })
+        .collect()
+}
+
+fn parse_states(as_str: &str) -> Result<u8, FromStringErr> {
+    match as_str.parse::<u8>() {
+        Ok(states) if states >= 2 => Ok(states),
+        _ => Err(FromStringErr::InvalidStates(InvalidStatesErr)),
+    }
+}
+
+/// Parses the `S/B/C` notations, either prefixed or in that order, with an
+/// optional `H` or `V` suffix for the hexagonal and von Neumann neighborhoods,
+/// and Hensel letters after the numbers of neighbors for isotropic rules.
+fn parse_life_like(as_str: &str) -> Result<Rule, FromStringErr> {
+    let (as_str, neighborhood) = match as_str.chars().last().map(|c| c.to_ascii_uppercase()) {
+        Some('H') => (&as_str[..as_str.len() - 1], Neighborhood::Hexagonal),
+        Some('V') => (&as_str[..as_str.len() - 1], Neighborhood::VonNeumann { range: 1 }),
+        _ => (as_str, Neighborhood::default()),
+    };
+    let parts: Vec<&str> = as_str.split('/').collect();
+    if parts.len() != 2 && parts.len() != 3 {
+        return Err(FromStringErr::InvalidFormat(InvalidFormatErr));
+    }
+    let prefix = |part: &str| part.chars().next().map(|c| c.to_ascii_uppercase());
+    let prefixed = |part: &&str| matches!(prefix(part), Some('B' | 'S' | 'C' | 'G'));
+    let (mut birth, mut survival, mut states) = (None, None, None);
+    if parts.iter().all(prefixed) {
+        for part in &parts {
+            let slot = match prefix(part) {
+                Some('B') => &mut birth,
+                Some('S') => &mut survival,
+                _ => &mut states,
+            };
+            if slot.replace(&part[1..]).is_some() {
+                return Err(FromStringErr::InvalidFormat(InvalidFormatErr));
+            }
+        }
+    } else if !parts.iter().any(prefixed) {
+        survival = Some(parts[0]);
+        birth = Some(parts[1]);
+        states = parts.get(2).copied();
+    }
+    let (Some(birth), Some(survival)) = (birth, survival) else {
+        return Err(FromStringErr::InvalidFormat(InvalidFormatErr));
+    };
+    let states = match states {
+        Some(states) => parse_states(states)?,
+        None => 2,
+    };
+    let hensel = |part: &str| part.chars().any(|c| c.is_ascii_alphabetic());
+    if !hensel(birth) && !hensel(survival) {
+        return Ok(Rule {
+            states,
+            neighborhood,
+            ..Rule::of(&parse_digits(birth)?, &parse_digits(survival)?)
+        });
+    }
+    if neighborhood != Neighborhood::default() {
+        return Err(FromStringErr::InvalidFormat(InvalidFormatErr));
+    }
+    let isotropic = Isotropic {
+        birth: isotropic::from_string(birth)?,
+        survival: isotropic::from_string(survival)?,
+    };
+    match (isotropic::totalistic(&isotropic.birth), isotropic::totalistic(&isotropic.survival)) {
+        (Some(birth), Some(survival)) => Ok(Rule { states, ..Rule::of(&birth, &survival) }),
+        _ => Ok(Rule {
+            states,
+            kind: RuleKind::Isotropic(Box::new(isotropic)),
+            ..Rule::of(&[], &[])
+        }),
+    }
+}
+
+fn parse_range(as_str: &str) -> Result<Vec<u8>, FromStringErr> {
+    let err = || FromStringErr::InvalidFormat(InvalidFormatErr);
+    let parse = |n: &str| n.trim().parse::<u8>().map_err(|_| err());
+    match as_str.split_once("..") {
+        Some((min, max)) => Ok((parse(min)?..=parse(max)?).collect()),
+        None if as_str.trim().is_empty() => Ok(Vec::new()),
+        None => Ok(vec![parse(as_str)?]),
+    }
+}
+
+/// Parses the Larger than Life notation of Golly, where `M1` counts the cell
+/// itself, which is the same as surviving on one neighbor less.
+fn parse_larger_than_life(as_str: &str) -> Result<Rule, FromStringErr> {
+    let err = || FromStringErr::InvalidFormat(InvalidFormatErr);
+    let (mut range, mut states, mut middle, mut kind) = (None, None, None, None);
+    let (mut birth, mut survival): (Option<Vec<u8>>, Option<Vec<u8>>) = (None, None);
+    let mut last = ' ';
+    for token in as_str.split(',') {
+        let token = token.trim();
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            let values = match last {
+                'B' => birth.as_mut(),
+                'S' => survival.as_mut(),
+                _ => None,
+            };
+            values.ok_or_else(err)?.extend(parse_range(token)?);
+            continue;
+        }
+        let mut chars = token.chars();
+        last = chars.next().map(|c| c.to_ascii_uppercase()).ok_or_else(err)?;
+        let value = chars.as_str();
+        let replaced = match last {
+            'R' => range.replace(value).is_some(),
+            'C' => states.replace(value).is_some(),
+            'M' => middle.replace(value).is_some(),
+            'N' => kind.replace(value).is_some(),
+            'B' => birth.replace(parse_range(value)?).is_some(),
+            'S' => survival.replace(parse_range(value)?).is_some(),
+            _ => return Err(err()),
+        };
+        if replaced {
+            return Err(err());
+        }
+    }
+    let (Some(range), Some(birth), Some(survival)) = (range, birth, survival) else {
+        return Err(err());
+    };
+    let range = match range.parse::<u8>() {
+        Ok(range) if (1..=MAX_RANGE).contains(&range) => range,
+        _ => return Err(FromStringErr::InvalidRange(InvalidRangeErr)),
+    };
+    let states = match states {
+        Some("0") | None => 2,
+        Some(states) => parse_states(states)?,
+    };
+    let survival = match middle {
+        Some("1") => survival.iter().filter(|n| **n > 0).map(|n| n - 1).collect(),
+        Some("0") | None => survival,
+        Some(_) => return Err(err()),
+    };
+    let neighborhood = match kind.map(|k| k.to_ascii_uppercase()).as_deref() {
+        Some("M") | None => Neighborhood::Moore { range },
+        Some("N") => Neighborhood::VonNeumann { range },
+        Some(_) => return Err(err()),
+    };
+    Ok(Rule { states, neighborhood, ..Rule::of(&birth, &survival) })
+}
+
+/// Conway's rule with two colors for Immigration and four for QuadLife.
+fn parse_colored(as_str: &str) -> Option<Rule> {
+    let states = match as_str.to_ascii_lowercase().as_str() {
+        "immigration" => 3,
+        "quadlife" => 5,
+        _ => return None,
+    };
+    Some(Rule { states, kind: RuleKind::Colored, ..Rule::default() })
+}
+
+fn parse_margolus(as_str: &str) -> Result<Rule, FromStringErr> {
+    Ok(Rule { kind: RuleKind::Margolus(margolus::from_string(as_str)?), ..Rule::default() })
+}
+
+/// Parses the `W110` notation of Golly for the elementary rules.
+fn parse_elementary(as_str: &str) -> Result<Rule, FromStringErr> {
+    let n = &as_str[1..];
+    if n.is_empty() || !n.chars().all(|c| c.is_ascii_digit()) {
+        return Err(FromStringErr::InvalidFormat(InvalidFormatErr));
+    }
+    let n = n.parse::<u8>().map_err(|_| FromStringErr::InvalidWolfram(InvalidWolframErr))?;
+    Ok(Rule { kind: RuleKind::Elementary(n), ..Rule::default() })
+}
+
+pub fn from_string(as_str: &str) -> Result<Rule, FromStringErr> {
+    let (as_str, topology) = match as_str.trim().split_once(':') {
+        Some((rule, suffix)) => {
+            (rule, topology::from_string(suffix).map_err(FromStringErr::InvalidTopology)?)
+        }
+        None => (as_str.trim(), Topology::Plane),
+    };
+    if let Some(rule) = table::builtin(as_str).or_else(|| parse_colored(as_str)) {
+        return Ok(Rule { topology, ..rule });
+    }
+    let larger_than_life = as_str.starts_with(['R', 'r']) && as_str.contains(',');
+    let block = as_str.get(..3).is_some_and(|prefix| prefix.eq_ignore_ascii_case("MS,"));
+    let rule = if let Some(b) = margolus::builtin(as_str) {
+        Rule { kind: RuleKind::Margolus(b), ..Rule::default() }
+    } else if larger_than_life {
+        parse_larger_than_life(as_str)?
+    } else if block {
+        parse_margolus(as_str)?
+    } else if as_str.starts_with(['W', 'w']) {
+        parse_elementary(as_str)?
+    } else {
+        parse_life_like(as_str)?
+    };
+    let max = offsets(&rule.neighborhood).len();
+    let beyond = |values: &[bool]| values.iter().skip(max + 1).any(|value| *value);
+    if beyond(&rule.birth) || beyond(&rule.survival) {
+        return Err(FromStringErr::InvalidCount(InvalidCountErr { max }));
+    }
+    let (xs, ys) = topology::get_spans(&topology);
+    let b0 = match &rule.kind {
+        RuleKind::Isotropic(i) => i.birth[0],
+        RuleKind::Elementary(n) => n % 2 == 1 && xs.is_none(),
+        _ => rule.birth[0],
+    };
+    if b0 {
+        return Err(FromStringErr::UnsupportedB0(UnsupportedB0Err));
+    }
+    // The partition has to wrap onto itself, which a twist or an odd size
+    // would shift by one cell.
+    let odd_size = [xs, ys].into_iter().flatten().any(|(start, end)| (end - start) % 2 == 0);
+    let twisted = matches!(topology, Topology::KleinBottle { .. });
+    if matches!(rule.kind, RuleKind::Margolus(_)) && (odd_size || twisted) {
+        return Err(FromStringErr::BlockTopology(BlockTopologyErr));
+    }
+    Ok(Rule { topology, ..rule })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rule() {
+        assert_eq!(
+            Rule::default(),
+            Rule {
+                birth: vec![false, false, false, true, false, false, false, false, false],
+                survival: vec![false, false, true, true, false, false, false, false, false],
+                states: 2,
+                neighborhood: Neighborhood::Moore { range: 1 },
+                kind: RuleKind::Totalistic,
+                topology: Topology::Plane,
+            }
+        );
+        assert_eq!(format!("{}", Rule::default()), "B3/S23");
+        assert_eq!(format!("{}", Rule::of(&[3, 6], &[2, 3])), "B36/S23");
+        assert_eq!(format!("{}", Rule::of(&[2], &[])), "B2/S");
+        assert_eq!(
+            format!(
+                "{}",
+                Rule { topology: Topology::Torus { width: 100, height: 80 }, ..Rule::default() }
+            ),
+            "B3/S23:T100,80"
+        );
+        assert_eq!(format!("{}", Rule { states: 3, ..Rule::of(&[2], &[]) }), "B2/S/C3");
+        assert_eq!(
+            format!(
+                "{}",
+                Rule {
+                    states: 4,
+                    topology: Topology::Torus { width: 10, height: 10 },
+                    ..Rule::of(&[2], &[3, 4, 5])
+                }
+            ),
+            "B2/S345/C4:T10,10"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                Rule { neighborhood: Neighborhood::Hexagonal, ..Rule::of(&[2], &[3, 4]) }
+            ),
+            "B2/S34H"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                Rule {
+                    states: 3,
+                    neighborhood: Neighborhood::VonNeumann { range: 1 },
+                    ..Rule::of(&[1], &[])
+                }
+            ),
+            "B1/S/C3V"
+        );
+        let bosco = Rule {
+            neighborhood: Neighborhood::Moore { range: 5 },
+            ..Rule::of(&(34..=45).collect::<Vec<u8>>(), &(33..=57).collect::<Vec<u8>>())
+        };
+        assert_eq!(format!("{bosco}"), "R5,C0,M0,S33..57,B34..45,NM");
+        let tlife = from_string("B3/S2-i34q").unwrap();
+        assert_eq!(format!("{tlife}"), "B3/S2-i34q");
+        assert_eq!(
+            format!("{}", Rule { states: 3, ..from_string("B2-a/S12").unwrap() }),
+            "B2-a/S12/C3"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                Rule {
+                    states: 5,
+                    neighborhood: Neighborhood::VonNeumann { range: 2 },
+                    topology: Topology::Torus { width: 50, height: 50 },
+                    ..Rule::of(&[3, 4, 7], &[2])
+                }
+            ),
+            "R2,C5,M0,S2..2,B3..4,7..7,NN:T50,50"
+        );
+    }
+
+    #[test]
+    fn test_from_string_builtin() {
+        assert_eq!(from_string("WireWorld"), Ok(table::wireworld()));
+        let torus = from_string("wireworld:T40,30").unwrap();
+        assert_eq!(torus.topology, Topology::Torus { width: 40, height: 30 });
+        assert_eq!(torus.to_string(), "WireWorld:T40,30");
+    }
+
+    #[test]
+    fn test_from_string_colored() {
+        let immigration = from_string("Immigration").unwrap();
+        assert_eq!(immigration, Rule { states: 3, kind: RuleKind::Colored, ..Rule::default() });
+        assert_eq!(immigration.to_string(), "Immigration");
+        let quadlife = from_string("quadlife:T20,20").unwrap();
+        assert_eq!(quadlife.states, 5);
+        assert_eq!(quadlife.kind, RuleKind::Colored);
+        assert_eq!(quadlife.to_string(), "QuadLife:T20,20");
+    }
+
+    #[test]
+    fn test_from_string_margolus() {
+        let critters = Rule { kind: RuleKind::Margolus(margolus::CRITTERS), ..Rule::default() };
+        assert_eq!(from_string("Critters"), Ok(critters.clone()));
+        assert_eq!(from_string("MS,D15;14;13;3;11;5;6;1;7;9;10;2;12;4;8;0"), Ok(critters));
+        let bbm = from_string("bbm:T20,20").unwrap();
+        assert_eq!(bbm.kind, RuleKind::Margolus(margolus::BBM));
+        assert_eq!(bbm.to_string(), "BBM:T20,20");
+        let tron = from_string("MS,D15;1;2;3;4;5;6;7;8;9;10;11;12;13;14;0").unwrap();
+        assert_eq!(tron.to_string(), "MS,D15;1;2;3;4;5;6;7;8;9;10;11;12;13;14;0");
+        assert_eq!(from_string("MS,D0;1"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        let err = Err(FromStringErr::BlockTopology(BlockTopologyErr));
+        assert_eq!(from_string("Critters:T21,20"), err);
+        assert_eq!(from_string("MS,D0;8;4;3;2;5;9;7;1;6;10;11;12;13;14;15:P20,5"), err);
+        assert_eq!(from_string("BBM:K20,20*"), err);
+        assert_eq!(from_string("Critters:K8,6"), err);
+        assert!(from_string("BBM:P20,20").is_ok());
+        assert!(from_string("Critters:T20,0").is_ok());
+        assert!(from_string("Critters:T0,21").is_err());
+        assert!(from_string("B3/S23:T21,21").is_ok());
+    }
+
+    #[test]
+    fn test_from_string_elementary() {
+        let rule30 = Rule { kind: RuleKind::Elementary(30), ..Rule::default() };
+        assert_eq!(from_string("W30"), Ok(rule30.clone()));
+        assert_eq!(from_string("w30"), Ok(rule30.clone()));
+        assert_eq!(rule30.to_string(), "W30");
+        let rule1 = from_string("W1:T64,1").unwrap();
+        assert_eq!(rule1.kind, RuleKind::Elementary(1));
+        assert_eq!(rule1.to_string(), "W1:T64,1");
+        assert_eq!(from_string("W1"), Err(FromStringErr::UnsupportedB0(UnsupportedB0Err)));
+        assert_eq!(from_string("W256"), Err(FromStringErr::InvalidWolfram(InvalidWolframErr)));
+        assert_eq!(from_string("W"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("W1a"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(
+            format!("{}", InvalidWolframErr),
+            "The Wolfram rule number must be from 0 to 255!"
+        );
+    }
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", InvalidFormatErr),
+            "The rule must be in the \"B3/S23\", \"23/3\", \"345/2/4\" or \"R5,C0,M1,S34..58,B34..45,NM\" notation!"
+        );
+        assert_eq!(
+            format!("{}", InvalidCountErr { max: 6 }),
+            "The number of neighbors must be from 0 to 6 in this neighborhood!"
+        );
+        assert_eq!(format!("{}", InvalidRangeErr), "The range must be from 1 to 7!");
+        assert_eq!(
+            format!("{}", InvalidLetterErr { neighbors: 2, letter: 'z' }),
+            "The letters after 2 must be among \"ceaikn\"!"
+        );
+        assert_eq!(
+            from_string("B2z/S23"),
+            Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors: 2, letter: 'z' }))
+        );
+        assert_eq!(
+            from_string("B3cekainyqjr/S2-3"),
+            Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors: 2, letter: '-' }))
+        );
+        assert_eq!(from_string("B2a/S23H"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("B0/S2a3"), Err(FromStringErr::UnsupportedB0(UnsupportedB0Err)));
+        assert_eq!(format!("{}", InvalidStatesErr), "The number of states must be from 2 to 255!");
+        assert_eq!(
+            format!("{}", InvalidNeighborsErr),
+            "The number of neighbors must be a digit from 0 to 8!"
+        );
+        assert_eq!(
+            format!("{}", UnsupportedB0Err),
+            "Rules with birth on 0 neighbors are not supported!"
+        );
+        assert_eq!(from_string(""), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("B3S23"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("B3/S2/3"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("B3/23"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("B3/B23"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("B3/C3"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("2/3/4/5"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("/2/1"), Err(FromStringErr::InvalidStates(InvalidStatesErr)));
+        assert_eq!(from_string("/2/256"), Err(FromStringErr::InvalidStates(InvalidStatesErr)));
+        assert_eq!(from_string("B2/S/C"), Err(FromStringErr::InvalidStates(InvalidStatesErr)));
+        assert_eq!(
+            from_string("B9/S23"),
+            Err(FromStringErr::InvalidNeighbors(InvalidNeighborsErr))
+        );
+        assert_eq!(
+            from_string("Bx/S23"),
+            Err(FromStringErr::InvalidNeighbors(InvalidNeighborsErr))
+        );
+        assert_eq!(
+            from_string("23/3x"),
+            Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors: 3, letter: 'x' }))
+        );
+        assert_eq!(from_string("B03/S23"), Err(FromStringErr::UnsupportedB0(UnsupportedB0Err)));
+        assert_eq!(
+            from_string("B7/S2H"),
+            Err(FromStringErr::InvalidCount(InvalidCountErr { max: 6 }))
+        );
+        assert_eq!(
+            from_string("B2/S5V"),
+            Err(FromStringErr::InvalidCount(InvalidCountErr { max: 4 }))
+        );
+        assert_eq!(
+            from_string("R1,C0,M0,S2..3,B3..9,NM"),
+            Err(FromStringErr::InvalidCount(InvalidCountErr { max: 8 }))
+        );
+        assert_eq!(
+            from_string("R8,C0,M0,S2..3,B3..4,NM"),
+            Err(FromStringErr::InvalidRange(InvalidRangeErr))
+        );
+        assert_eq!(
+            from_string("R0,C0,M0,S2..3,B3..4,NM"),
+            Err(FromStringErr::InvalidRange(InvalidRangeErr))
+        );
+        assert_eq!(
+            from_string("R2,C1,M0,S2..3,B3..4,NM"),
+            Err(FromStringErr::InvalidStates(InvalidStatesErr))
+        );
+        assert_eq!(
+            from_string("R2,C0,M0,S2..3,NM"),
+            Err(FromStringErr::InvalidFormat(InvalidFormatErr))
+        );
+        assert_eq!(
+            from_string("R2,C0,M2,S2..3,B3..4,NM"),
+            Err(FromStringErr::InvalidFormat(InvalidFormatErr))
+        );
+        assert_eq!(
+            from_string("R2,C0,M0,S2..3,B3..4,NC"),
+            Err(FromStringErr::InvalidFormat(InvalidFormatErr))
+        );
+        assert_eq!(
+            from_string("R2,C0,M0,S2..3,B3..4,B5,NM"),
+            Err(FromStringErr::InvalidFormat(InvalidFormatErr))
+        );
+        assert_eq!(
+            from_string("R2,C0,M0,S2..x,B3..4,NM"),
+            Err(FromStringErr::InvalidFormat(InvalidFormatErr))
+        );
+        assert_eq!(
+            from_string("R2,C0,M0,S2..3,B0..4,NM"),
+            Err(FromStringErr::UnsupportedB0(UnsupportedB0Err))
+        );
+        assert_eq!(
+            from_string("B3/S23:Q10,10"),
+            Err(FromStringErr::InvalidTopology(topology::FromStringErr::InvalidFormat(
+                topology::InvalidFormatErr
+            )))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(from_string("B3/S23"), Ok(Rule::default()));
+        assert_eq!(from_string("b3/s23"), Ok(Rule::default()));
+        assert_eq!(from_string("S23/B3"), Ok(Rule::default()));
+        assert_eq!(from_string("23/3"), Ok(Rule::default()));
+        assert_eq!(from_string(" B3/S23 "), Ok(Rule::default()));
+        assert_eq!(from_string("B36/S23"), Ok(Rule::of(&[3, 6], &[2, 3])));
+        assert_eq!(from_string("23/36"), Ok(Rule::of(&[3, 6], &[2, 3])));
+        assert_eq!(from_string("B3678/S34678"), Ok(Rule::of(&[3, 6, 7, 8], &[3, 4, 6, 7, 8])));
+        assert_eq!(from_string("B2/S"), Ok(Rule::of(&[2], &[])));
+        assert_eq!(from_string("/2"), Ok(Rule::of(&[2], &[])));
+        assert_eq!(from_string("B3/S012345678"), Ok(Rule::of(&[3], &[0, 1, 2, 3, 4, 5, 6, 7, 8])));
+        assert_eq!(
+            from_string("B36/S23:T100,80"),
+            Ok(Rule {
+                topology: Topology::Torus { width: 100, height: 80 },
+                ..Rule::of(&[3, 6], &[2, 3])
+            })
+        );
+        assert_eq!(from_string("/2/3"), Ok(Rule { states: 3, ..Rule::of(&[2], &[]) }));
+        assert_eq!(from_string("B2/S/C3"), Ok(Rule { states: 3, ..Rule::of(&[2], &[]) }));
+        assert_eq!(from_string("345/2/4"), Ok(Rule { states: 4, ..Rule::of(&[2], &[3, 4, 5]) }));
+        assert_eq!(from_string("S345/B2/G4"), Ok(Rule { states: 4, ..Rule::of(&[2], &[3, 4, 5]) }));
+        assert_eq!(from_string("23/3/2"), Ok(Rule::default()));
+        assert_eq!(
+            from_string("/2/3:T10,10"),
+            Ok(Rule {
+                states: 3,
+                topology: Topology::Torus { width: 10, height: 10 },
+                ..Rule::of(&[2], &[])
+            })
+        );
+        assert_eq!(
+            from_string("B2/S34H"),
+            Ok(Rule { neighborhood: Neighborhood::Hexagonal, ..Rule::of(&[2], &[3, 4]) })
+        );
+        assert_eq!(
+            from_string("345/2/4h"),
+            Ok(Rule {
+                states: 4,
+                neighborhood: Neighborhood::Hexagonal,
+                ..Rule::of(&[2], &[3, 4, 5])
+            })
+        );
+        assert_eq!(
+            from_string("B1/S1V:T10,10"),
+            Ok(Rule {
+                neighborhood: Neighborhood::VonNeumann { range: 1 },
+                topology: Topology::Torus { width: 10, height: 10 },
+                ..Rule::of(&[1], &[1])
+            })
+        );
+        let bosco = Rule {
+            neighborhood: Neighborhood::Moore { range: 5 },
+            ..Rule::of(&(34..=45).collect::<Vec<u8>>(), &(33..=57).collect::<Vec<u8>>())
+        };
+        assert_eq!(from_string("R5,C0,M1,S34..58,B34..45,NM"), Ok(bosco.clone()));
+        assert_eq!(from_string("r5,c0,m0,s33..57,b34..45,nm"), Ok(bosco.clone()));
+        assert_eq!(from_string(&bosco.to_string()), Ok(bosco));
+        assert_eq!(from_string("R1,C0,M0,S2..3,B3..3,NM"), Ok(Rule::default()));
+        assert_eq!(from_string("B3cekainyqjr/S2ceaikn3"), Ok(Rule::default()));
+        assert_eq!(
+            from_string("2ceaikn3/3-"),
+            Err(FromStringErr::InvalidLetter(InvalidLetterErr { neighbors: 3, letter: '-' }))
+        );
+        let tlife = from_string("B3/S2-i34q").unwrap();
+        assert!(matches!(
+            &tlife.kind,
+            RuleKind::Isotropic(i) if i.survival.iter().filter(|v| **v).count() == 26 + 56 + 4
+        ));
+        assert_eq!(from_string("2-i34q/3"), Ok(tlife));
+        assert_eq!(from_string("R1,C0,M1,S3..4,B3,NM"), Ok(Rule::default()));
+        assert_eq!(
+            from_string("R2,C5,S2,B3..4,7,NN:T50,50"),
+            Ok(Rule {
+                states: 5,
+                neighborhood: Neighborhood::VonNeumann { range: 2 },
+                topology: Topology::Torus { width: 50, height: 50 },
+                ..Rule::of(&[3, 4, 7], &[2])
+            })
+        );
+        assert_eq!(
+            from_string("23/3:P30,20"),
+            Ok(Rule { topology: Topology::Bounded { width: 30, height: 20 }, ..Rule::default() })
+        );
+    }
+}
@@ -0,0 +1,587 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, LazyLock},
+};
+
+use crate::{
+    geometry::coordinate::CartesianP,
+    neighbor::Neighborhood,
+    rule::{Rule, RuleKind},
+};
+
+/// Bounds both the combinations of a single line and the compiled table, as
+/// unbound variables multiply quickly.
+pub const MAX_TRANSITIONS: usize = 1 << 22;
+
+/// The Golly table of Wireworld, with electron heads as 1, tails as 2 and
+/// copper as 3.
+pub const WIREWORLD: &str = "@RULE WireWorld
+# Electrons flowing along copper wires.
+
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:permute
+var a={0,1,2,3}
+var b={0,1,2,3}
+var c={0,1,2,3}
+var d={0,1,2,3}
+var e={0,1,2,3}
+var f={0,1,2,3}
+var g={0,1,2,3}
+var h={0,1,2,3}
+var i={0,2,3}
+var j={i}
+var k={i}
+var l={i}
+var m={i}
+var n={i}
+var o={i}
+1,a,b,c,d,e,f,g,h,2 # head to tail
+2,a,b,c,d,e,f,g,h,3 # tail to copper
+3,1,i,j,k,l,m,n,o,1
+3,1,1,i,j,k,l,m,n,1
+
+@COLORS
+0 48 48 48
+1 0 128 255
+2 255 255 255
+3 255 128 0
+";
+
+/// How the neighbors of a transition can be rearranged and still match it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Symmetries {
+    None,
+    Rotate4,
+    Rotate8,
+    Reflect,
+    Rotate4Reflect,
+    Rotate8Reflect,
+    Permute,
+}
+
+/// A Golly rule table, compiled into the next state of every arrangement of a
+/// cell and its neighbors that some transition matches. The others stay as
+/// they are. Editing a cell goes through the states in the order of the
+/// `palette`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Table {
+    pub name: String,
+    pub states: u8,
+    pub neighborhood: Neighborhood,
+    pub symmetries: Symmetries,
+    pub transitions: HashMap<u128, u8>,
+    pub colors: HashMap<u8, [u8; 3]>,
+    pub palette: Vec<u8>,
+}
+
+impl From<Table> for Rule {
+    fn from(t: Table) -> Self {
+        Rule {
+            states: t.states,
+            neighborhood: t.neighborhood,
+            kind: RuleKind::Table(Arc::new(t)),
+            ..Rule::of(&[], &[])
+        }
+    }
+}
+
+static WIREWORLD_TABLE: LazyLock<Arc<Table>> = LazyLock::new(|| {
+    let table = from_string(WIREWORLD).unwrap();
+    Arc::new(Table { palette: vec![0, 3, 1, 2], ..table })
+});
+
+/// Wireworld, where copper is drawn before the electrons.
+pub fn wireworld() -> Rule {
+    Rule {
+        states: WIREWORLD_TABLE.states,
+        neighborhood: WIREWORLD_TABLE.neighborhood,
+        kind: RuleKind::Table(Arc::clone(&WIREWORLD_TABLE)),
+        ..Rule::of(&[], &[])
+    }
+}
+
+/// The rules known by name, without their table.
+pub fn builtin(name: &str) -> Option<Rule> {
+    name.eq_ignore_ascii_case("WireWorld").then(wireworld)
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MissingSectionErr {
+    pub section: &'static str,
+}
+
+impl fmt::Display for MissingSectionErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The rule must have a \"@{}\" section!", self.section)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidLineErr {
+    pub line: usize,
+}
+
+impl fmt::Display for InvalidLineErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid line {} in the rule!", self.line)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TooManyTransitionsErr {
+    pub line: usize,
+}
+
+impl fmt::Display for TooManyTransitionsErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "The transitions up to line {} expand to more than {MAX_TRANSITIONS} cases!",
+            self.line
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    MissingSection(MissingSectionErr),
+    InvalidLine(InvalidLineErr),
+    TooManyTransitions(TooManyTransitionsErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::MissingSection(e) => e.fmt(f),
+            FromStringErr::InvalidLine(e) => e.fmt(f),
+            FromStringErr::TooManyTransitions(e) => e.fmt(f),
+        }
+    }
+}
+
+/// The offsets of the neighbors in the order of the transitions, clockwise
+/// from the north.
+pub fn neighbors(n: &Neighborhood) -> Vec<CartesianP> {
+    let (x, y) = match n {
+        Neighborhood::VonNeumann { range: 1 } => (vec![0, 1, 0, -1], vec![1, 0, -1, 0]),
+        Neighborhood::Moore { range: 1 } => {
+            (vec![0, 1, 1, 1, 0, -1, -1, -1], vec![1, 1, 0, -1, -1, -1, 0, 1])
+        }
+        _ => (Vec::new(), Vec::new()),
+    };
+    x.into_iter().zip(y).map(|(x, y)| CartesianP::of(x, y)).collect()
+}
+
+/// For each arrangement, the index of the neighbor moved to each position.
+fn arrangements(symmetries: Symmetries, len: usize) -> Vec<Vec<usize>> {
+    let rotate = |r: usize| (0..len).map(|i| (i + r) % len).collect::<Vec<usize>>();
+    let reflect = |a: &Vec<usize>| (0..len).map(|i| a[(len - i) % len]).collect::<Vec<usize>>();
+    let rotations = |step: usize| (0..len).step_by(step).map(rotate).collect::<Vec<_>>();
+    match symmetries {
+        Symmetries::None | Symmetries::Permute => vec![rotate(0)],
+        Symmetries::Rotate4 => rotations(len / 4),
+        Symmetries::Rotate8 => rotations(1),
+        Symmetries::Reflect => vec![rotate(0), reflect(&rotate(0))],
+        Symmetries::Rotate4Reflect | Symmetries::Rotate8Reflect => {
+            let step = if symmetries == Symmetries::Rotate4Reflect { len / 4 } else { 1 };
+            let rotated = rotations(step);
+            let reflected = rotated.iter().map(reflect).collect::<Vec<_>>();
+            rotated.into_iter().chain(reflected).collect()
+        }
+    }
+}
+
+fn key(center: u8, neighbors: &[u8], symmetries: Symmetries) -> u128 {
+    let mut neighbors = neighbors.to_vec();
+    if symmetries == Symmetries::Permute {
+        neighbors.sort_unstable();
+    }
+    neighbors.iter().fold(u128::from(center), |acc, n| acc << 8 | u128::from(*n))
+}
+
+/// The next state of a cell, which keeps its own if no transition matches.
+pub fn next(t: &Table, center: u8, neighbors: &[u8]) -> u8 {
+    t.transitions.get(&key(center, neighbors, t.symmetries)).copied().unwrap_or(center)
+}
+
+/// Splits on the commas outside of braces, or into single characters when
+/// there is no comma, as in the compact transitions of tables up to 10 states.
+fn tokens(line: &str) -> Vec<&str> {
+    if !line.contains(',') {
+        return line.char_indices().map(|(i, c)| &line[i..i + c.len_utf8()]).collect();
+    }
+    let mut result = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in line.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                result.push(line[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(line[start..].trim());
+    result
+}
+
+/// The states of a number, a variable or a set in braces of either.
+fn values(token: &str, variables: &HashMap<String, Vec<u8>>, states: u8) -> Option<Vec<u8>> {
+    if let Some(set) = token.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        let mut result = Vec::new();
+        for item in set.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            result.extend(values(item, variables, states)?);
+        }
+        return Some(result);
+    }
+    match token.parse::<u8>() {
+        Ok(n) if n < states => Some(vec![n]),
+        Ok(_) => None,
+        Err(_) => variables.get(token).cloned(),
+    }
+}
+
+fn parse_neighborhood(as_str: &str) -> Option<Neighborhood> {
+    match as_str.to_ascii_lowercase().as_str() {
+        "moore" => Some(Neighborhood::Moore { range: 1 }),
+        "vonneumann" => Some(Neighborhood::VonNeumann { range: 1 }),
+        _ => None,
+    }
+}
+
+fn parse_symmetries(as_str: &str) -> Option<Symmetries> {
+    match as_str.to_ascii_lowercase().as_str() {
+        "none" => Some(Symmetries::None),
+        "rotate4" => Some(Symmetries::Rotate4),
+        "rotate8" => Some(Symmetries::Rotate8),
+        "reflect" => Some(Symmetries::Reflect),
+        "rotate4reflect" => Some(Symmetries::Rotate4Reflect),
+        "rotate8reflect" => Some(Symmetries::Rotate8Reflect),
+        "permute" => Some(Symmetries::Permute),
+        _ => None,
+    }
+}
+
+/// `@COLORS` either gives one state its color, as in `1 255 0 0`, or a
+/// gradient over all the live states, as in `0 0 255 255 0 0`.
+fn parse_colors(line: &str, states: u8) -> Option<Vec<(u8, [u8; 3])>> {
+    let numbers: Vec<u8> =
+        line.split_whitespace().map(|n| n.parse().ok()).collect::<Option<_>>()?;
+    match numbers[..] {
+        [state, r, g, b] if state < states => Some(vec![(state, [r, g, b])]),
+        [r1, g1, b1, r2, g2, b2] => {
+            let last = i32::from(states.max(3) - 2);
+            let mix = |from: u8, to: u8, k: u8| {
+                (i32::from(from) + (i32::from(to) - i32::from(from)) * i32::from(k - 1) / last)
+                    as u8
+            };
+            Some(
+                (1..states)
+                    .map(|k| (k, [mix(r1, r2, k), mix(g1, g2, k), mix(b1, b2, k)]))
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+enum Section {
+    Table,
+    Colors,
+    Other,
+}
+
+/// Reads the `@RULE`, `@TABLE` and `@COLORS` sections of a Golly `.rule`
+/// file. As in Golly, the first transition that matches wins, and a variable
+/// used more than once in a transition takes the same state everywhere.
+pub fn from_string(as_str: &str) -> Result<Table, FromStringErr> {
+    let mut name: Option<String> = None;
+    let mut section = Section::Other;
+    let mut has_table = false;
+    let (mut states, mut neighborhood, mut symmetries) = (None, None, None);
+    let mut variables: HashMap<String, Vec<u8>> = HashMap::new();
+    let mut transitions: HashMap<u128, u8> = HashMap::new();
+    let mut color_lines: Vec<(usize, &str)> = Vec::new();
+    for (i, line) in as_str.lines().enumerate() {
+        let invalid = || FromStringErr::InvalidLine(InvalidLineErr { line: i + 1 });
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('@') {
+            let (kind, value) = header.split_once(char::is_whitespace).unwrap_or((header, ""));
+            section = match kind {
+                "RULE" => {
+                    name = Some(String::from(value.trim())).filter(|name| !name.is_empty());
+                    Section::Other
+                }
+                "TABLE" => {
+                    has_table = true;
+                    Section::Table
+                }
+                "COLORS" => Section::Colors,
+                _ => Section::Other,
+            };
+            continue;
+        }
+        match section {
+            Section::Other => continue,
+            Section::Colors => {
+                color_lines.push((i + 1, line));
+                continue;
+            }
+            Section::Table => {}
+        }
+        if let Some((directive, value)) = line.split_once(':') {
+            let value = value.trim();
+            match directive.trim() {
+                "n_states" => {
+                    states = Some(value.parse::<u8>().ok().filter(|n| *n >= 2).ok_or_else(invalid)?)
+                }
+                "neighborhood" => {
+                    neighborhood = Some(parse_neighborhood(value).ok_or_else(invalid)?)
+                }
+                "symmetries" => symmetries = Some(parse_symmetries(value).ok_or_else(invalid)?),
+                _ => return Err(invalid()),
+            }
+            continue;
+        }
+        let (Some(states), Some(neighborhood), Some(symmetries)) =
+            (states, neighborhood, symmetries)
+        else {
+            return Err(invalid());
+        };
+        let len = neighbors(&neighborhood).len();
+        if let Some(variable) = line.strip_prefix("var ") {
+            let (variable, set) = variable.split_once('=').ok_or_else(invalid)?;
+            let set = values(set.trim(), &variables, states).ok_or_else(invalid)?;
+            variables.insert(String::from(variable.trim()), set);
+            continue;
+        }
+        let tokens = tokens(line);
+        if tokens.len() != len + 2 {
+            return Err(invalid());
+        }
+        // Each variable is a single slot however many times it is used, while
+        // each number or set in braces is a slot of its own.
+        let mut slots: Vec<Vec<u8>> = Vec::new();
+        let mut named: HashMap<&str, usize> = HashMap::new();
+        let mut inputs: Vec<usize> = Vec::new();
+        for token in &tokens[..len + 1] {
+            if let Some(slot) = named.get(token) {
+                inputs.push(*slot);
+                continue;
+            }
+            slots.push(values(token, &variables, states).ok_or_else(invalid)?);
+            if variables.contains_key(*token) {
+                named.insert(token, slots.len() - 1);
+            }
+            inputs.push(slots.len() - 1);
+        }
+        let output = match named.get(tokens[len + 1]) {
+            Some(slot) => *slot,
+            None => match tokens[len + 1].parse::<u8>() {
+                Ok(n) if n < states => {
+                    slots.push(vec![n]);
+                    slots.len() - 1
+                }
+                _ => return Err(invalid()),
+            },
+        };
+        if matches!(symmetries, Symmetries::Rotate8 | Symmetries::Rotate8Reflect) && len != 8 {
+            return Err(invalid());
+        }
+        let arrangements = arrangements(symmetries, len);
+        let too_many = || FromStringErr::TooManyTransitions(TooManyTransitionsErr { line: i + 1 });
+        let combinations =
+            slots.iter().fold(arrangements.len(), |acc, s| acc.saturating_mul(s.len()));
+        if combinations > MAX_TRANSITIONS {
+            return Err(too_many());
+        }
+        let mut indices = vec![0; slots.len()];
+        for _ in 0..combinations / arrangements.len() {
+            let value = |slot: usize| slots[slot][indices[slot]];
+            let around: Vec<u8> = inputs[1..].iter().map(|slot| value(*slot)).collect();
+            for arrangement in &arrangements {
+                let arranged: Vec<u8> = arrangement.iter().map(|j| around[*j]).collect();
+                transitions
+                    .entry(key(value(inputs[0]), &arranged, symmetries))
+                    .or_insert(value(output));
+            }
+            for (slot, index) in indices.iter_mut().enumerate() {
+                *index += 1;
+                if *index < slots[slot].len() {
+                    break;
+                }
+                *index = 0;
+            }
+        }
+        if transitions.len() > MAX_TRANSITIONS {
+            return Err(too_many());
+        }
+    }
+    let Some(name) = name else {
+        return Err(FromStringErr::MissingSection(MissingSectionErr { section: "RULE" }));
+    };
+    let (true, Some(states), Some(neighborhood), Some(symmetries)) =
+        (has_table, states, neighborhood, symmetries)
+    else {
+        return Err(FromStringErr::MissingSection(MissingSectionErr { section: "TABLE" }));
+    };
+    let mut colors = HashMap::new();
+    for (line, text) in color_lines {
+        let parsed = parse_colors(text, states)
+            .ok_or(FromStringErr::InvalidLine(InvalidLineErr { line }))?;
+        colors.extend(parsed);
+    }
+    let palette = (0..states).collect();
+    Ok(Table { name, states, neighborhood, symmetries, transitions, colors, palette })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", MissingSectionErr { section: "TABLE" }),
+            "The rule must have a \"@TABLE\" section!"
+        );
+        assert_eq!(format!("{}", InvalidLineErr { line: 4 }), "Invalid line 4 in the rule!");
+        assert_eq!(
+            from_string("@TABLE\nn_states:2\nneighborhood:Moore\nsymmetries:none\n"),
+            Err(FromStringErr::MissingSection(MissingSectionErr { section: "RULE" }))
+        );
+        assert_eq!(
+            from_string("@RULE Empty\n"),
+            Err(FromStringErr::MissingSection(MissingSectionErr { section: "TABLE" }))
+        );
+        let invalid = |line| Err(FromStringErr::InvalidLine(InvalidLineErr { line }));
+        let header = "@RULE Test\n@TABLE\nn_states:3\nneighborhood:vonNeumann\nsymmetries:none\n";
+        assert_eq!(from_string(&format!("{header}0,1,0,0,0,3\n")), invalid(6));
+        assert_eq!(from_string(&format!("{header}0,1,0,0,1\n")), invalid(6));
+        assert_eq!(from_string(&format!("{header}0,x,0,0,0,1\n")), invalid(6));
+        assert_eq!(from_string(&format!("{header}var a={{0,1}}\n0,1,0,0,0,a\n")), invalid(7));
+        assert_eq!(from_string(&format!("{header}radius:1\n")), invalid(6));
+        assert_eq!(
+            from_string(&format!(
+                "{}0,1,0,0,0,1\n",
+                header.replace("symmetries:none", "symmetries:rotate8")
+            )),
+            invalid(6)
+        );
+        assert_eq!(from_string("@RULE Test\n@TABLE\n0,1,0,0,0,1\n"), invalid(3));
+        assert_eq!(from_string(&header.replace("n_states:3", "n_states:1")), invalid(3));
+        assert_eq!(
+            from_string(&header.replace("neighborhood:vonNeumann", "neighborhood:hexagonal")),
+            invalid(4)
+        );
+        assert_eq!(from_string(&format!("{header}@COLORS\n1 255 0\n")), invalid(7));
+        assert_eq!(from_string(&format!("{header}@COLORS\n3 255 0 0\n")), invalid(7));
+        let all = "@RULE Big\n@TABLE\nn_states:255\nneighborhood:Moore\nsymmetries:none\nvar a={0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31}\nvar b={a}\nvar c={a}\nvar d={a}\nvar e={a}\n0,a,b,c,d,e,0,0,0,1\n";
+        assert_eq!(
+            from_string(all),
+            Err(FromStringErr::TooManyTransitions(TooManyTransitionsErr { line: 11 }))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        let wireworld = from_string(WIREWORLD).unwrap();
+        assert_eq!(wireworld.name, "WireWorld");
+        assert_eq!(wireworld.states, 4);
+        assert_eq!(wireworld.neighborhood, Neighborhood::Moore { range: 1 });
+        assert_eq!(wireworld.symmetries, Symmetries::Permute);
+        assert_eq!(wireworld.colors.len(), 4);
+        assert_eq!(wireworld.colors[&1], [0, 128, 255]);
+        assert_eq!(wireworld.palette, vec![0, 1, 2, 3]);
+        assert_eq!(next(&wireworld, 1, &[0, 3, 3, 0, 0, 0, 0, 0]), 2);
+        assert_eq!(next(&wireworld, 2, &[1, 1, 1, 1, 1, 1, 1, 1]), 3);
+        assert_eq!(next(&wireworld, 3, &[0, 0, 0, 0, 0, 1, 0, 0]), 1);
+        assert_eq!(next(&wireworld, 3, &[1, 0, 0, 0, 0, 1, 0, 2]), 1);
+        assert_eq!(next(&wireworld, 3, &[1, 0, 0, 0, 1, 1, 0, 0]), 3);
+        assert_eq!(next(&wireworld, 3, &[0, 2, 3, 3, 0, 0, 0, 0]), 3);
+        assert_eq!(next(&wireworld, 0, &[1, 1, 1, 0, 0, 0, 0, 0]), 0);
+        let compact =
+            "@RULE Compact\n@TABLE\nn_states:2\nneighborhood:vonNeumann\nsymmetries:none\n010001\n";
+        assert_eq!(next(&from_string(compact).unwrap(), 0, &[1, 0, 0, 0]), 1);
+        assert_eq!(next(&from_string(compact).unwrap(), 0, &[0, 1, 0, 0]), 0);
+        let gradient = "@RULE Gradient\n@TABLE\nn_states:4\nneighborhood:Moore\nsymmetries:none\n@COLORS\n0 0 0 0 0 0\n0 1 2 3\n";
+        assert_eq!(
+            from_string(gradient).unwrap().colors,
+            HashMap::from([(0, [1, 2, 3]), (1, [0, 0, 0]), (2, [0, 0, 0]), (3, [0, 0, 0])])
+        );
+        let gradient = gradient.replace("0 0 0 0 0 0", "0 0 255 255 0 0");
+        let colors = from_string(&gradient).unwrap().colors;
+        assert_eq!((colors[&1], colors[&2], colors[&3]), ([0, 0, 255], [127, 0, 128], [255, 0, 0]));
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let header = "@RULE Test\n@TABLE\nn_states:3\nneighborhood:vonNeumann\n";
+        let table = |symmetries: &str, transitions: &str| {
+            from_string(&format!("{header}symmetries:{symmetries}\n{transitions}")).unwrap()
+        };
+        let none = table("none", "0,1,2,0,0,1\n");
+        assert_eq!(next(&none, 0, &[1, 2, 0, 0]), 1);
+        assert_eq!(next(&none, 0, &[0, 1, 2, 0]), 0);
+        let rotate4 = table("rotate4", "0,1,2,0,0,1\n");
+        assert_eq!(rotate4.transitions.len(), 4);
+        assert_eq!(next(&rotate4, 0, &[0, 1, 2, 0]), 1);
+        assert_eq!(next(&rotate4, 0, &[2, 0, 0, 1]), 1);
+        assert_eq!(next(&rotate4, 0, &[2, 1, 0, 0]), 0);
+        let reflect = table("reflect", "0,1,2,0,0,1\n");
+        assert_eq!(next(&reflect, 0, &[1, 0, 0, 2]), 1);
+        assert_eq!(next(&reflect, 0, &[0, 1, 2, 0]), 0);
+        assert_eq!(table("rotate4reflect", "0,1,2,0,0,1\n").transitions.len(), 8);
+        assert_eq!(table("permute", "0,1,2,0,0,1\n").transitions.len(), 1);
+        let first = table("none", "0,1,0,0,0,2\n0,1,0,0,0,1\n");
+        assert_eq!(next(&first, 0, &[1, 0, 0, 0]), 2);
+        let bound = table("none", "var a={1,2}\n0,a,a,0,0,a\n");
+        assert_eq!(bound.transitions.len(), 2);
+        assert_eq!(next(&bound, 0, &[2, 2, 0, 0]), 2);
+        assert_eq!(next(&bound, 0, &[1, 2, 0, 0]), 0);
+        let unbound = table("none", "0,{1,2},{1,2},0,0,1\n");
+        assert_eq!(unbound.transitions.len(), 4);
+        assert_eq!(next(&unbound, 0, &[1, 2, 0, 0]), 1);
+        let moore = "@RULE Test\n@TABLE\nn_states:2\nneighborhood:Moore\n";
+        let rotate8 = from_string(&format!("{moore}symmetries:rotate8\n0,1,0,0,0,0,0,0,0,1\n"));
+        assert_eq!(rotate8.unwrap().transitions.len(), 8);
+        let rotate4 = from_string(&format!("{moore}symmetries:rotate4\n0,1,0,0,0,0,0,0,0,1\n"));
+        let rotate4 = rotate4.unwrap();
+        assert_eq!(next(&rotate4, 0, &[0, 0, 1, 0, 0, 0, 0, 0]), 1);
+        assert_eq!(next(&rotate4, 0, &[0, 1, 0, 0, 0, 0, 0, 0]), 0);
+    }
+
+    #[test]
+    fn test_builtin() {
+        let wireworld = builtin("wireworld").unwrap();
+        assert_eq!(wireworld, builtin("WireWorld").unwrap());
+        assert_eq!(wireworld.to_string(), "WireWorld");
+        assert_eq!(wireworld.states, 4);
+        assert!(matches!(wireworld.kind, RuleKind::Table(t) if t.palette == [0, 3, 1, 2]));
+        assert_eq!(builtin("Conway"), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        assert_eq!(
+            neighbors(&Neighborhood::VonNeumann { range: 1 }),
+            vec![
+                CartesianP::of(0, 1),
+                CartesianP::of(1, 0),
+                CartesianP::of(0, -1),
+                CartesianP::of(-1, 0)
+            ]
+        );
+        assert_eq!(neighbors(&Neighborhood::Moore { range: 1 })[1], CartesianP::of(1, 1));
+        assert_eq!(neighbors(&Neighborhood::Moore { range: 1 }).len(), 8);
+        assert_eq!(neighbors(&Neighborhood::Hexagonal), Vec::new());
+    }
+}
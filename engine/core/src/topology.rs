@@ -0,0 +1,339 @@
+use std::{collections::HashMap, fmt};
+
+use super::{
+    cell::State,
+    geometry::{coordinate::CartesianP, poligon::rect::Rect},
+    universe::{set_value, Universe},
+};
+
+/// The shape of the grid, written as a Golly rule suffix such as `:T100,80`.
+/// Every finite topology is a `width` by `height` rectangle centered at the
+/// origin, and differs only in how its edges are joined. A size of zero
+/// leaves that axis unbounded, so `:T100,0` is a cylinder.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Topology {
+    #[default]
+    Plane,
+    /// `:Pw,h`, every cell beyond the edges is permanently dead.
+    Bounded { width: u64, height: u64 },
+    /// `:Tw,h`, both pairs of edges wrap around.
+    Torus { width: u64, height: u64 },
+    /// `:Kw*,h` or `:Kw,h*`, both pairs of edges wrap around, one of them with
+    /// a twist.
+    KleinBottle { width: u64, height: u64, twist: Twist },
+}
+
+/// The pair of edges of a Klein bottle that wraps with a twist, which is the
+/// one whose length is marked with `*`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Twist {
+    /// The top and bottom edges, mirroring the horizontal position.
+    Horizontal,
+    /// The left and right edges, mirroring the vertical position.
+    Vertical,
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topology::Plane => Ok(()),
+            Topology::Bounded { width, height } => write!(f, ":P{width},{height}"),
+            Topology::Torus { width, height } => write!(f, ":T{width},{height}"),
+            Topology::KleinBottle { width, height, twist: Twist::Horizontal } => {
+                write!(f, ":K{width}*,{height}")
+            }
+            Topology::KleinBottle { width, height, twist: Twist::Vertical } => {
+                write!(f, ":K{width},{height}*")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidFormatErr;
+
+impl fmt::Display for InvalidFormatErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The topology must be in the \"T100,80\" notation, using P, T or K!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidSizeErr;
+
+impl fmt::Display for InvalidSizeErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The width and the height must be below 2^63, and above zero on a Klein bottle!")
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidFormat(InvalidFormatErr),
+    InvalidSize(InvalidSizeErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidFormat(e) => e.fmt(f),
+            FromStringErr::InvalidSize(e) => e.fmt(f),
+        }
+    }
+}
+
+pub fn from_string(as_str: &str) -> Result<Topology, FromStringErr> {
+    let as_str = as_str.trim();
+    let mut chars = as_str.chars();
+    let kind = chars.next().map(|c| c.to_ascii_uppercase());
+    let (width, height) =
+        chars.as_str().split_once(',').ok_or(FromStringErr::InvalidFormat(InvalidFormatErr))?;
+    let (width, height) = (width.trim(), height.trim());
+    let twist = match (width.strip_suffix('*'), height.strip_suffix('*')) {
+        (Some(_), Some(_)) => return Err(FromStringErr::InvalidFormat(InvalidFormatErr)),
+        (Some(_), None) => Some(Twist::Horizontal),
+        (None, Some(_)) => Some(Twist::Vertical),
+        (None, None) => None,
+    };
+    let parse = |value: &str| {
+        let value = value.strip_suffix('*').unwrap_or(value);
+        value.parse::<u64>().map_err(|_| FromStringErr::InvalidFormat(InvalidFormatErr))
+    };
+    let (width, height) = (parse(width)?, parse(height)?);
+    if width > i64::MAX as u64 || height > i64::MAX as u64 {
+        return Err(FromStringErr::InvalidSize(InvalidSizeErr));
+    }
+    match (kind, twist) {
+        (Some('P'), None) => Ok(Topology::Bounded { width, height }),
+        (Some('T'), None) => Ok(Topology::Torus { width, height }),
+        (Some('K'), _) if width == 0 || height == 0 => {
+            Err(FromStringErr::InvalidSize(InvalidSizeErr))
+        }
+        (Some('K'), twist) => {
+            Ok(Topology::KleinBottle { width, height, twist: twist.unwrap_or(Twist::Horizontal) })
+        }
+        _ => Err(FromStringErr::InvalidFormat(InvalidFormatErr)),
+    }
+}
+
+/// The first and last coordinates along an axis.
+pub type Span = (i64, i64);
+
+/// The span of an axis of `size` cells, or `None` when it is unbounded.
+fn get_span(size: u64) -> Option<Span> {
+    let size = i64::try_from(size).ok().filter(|size| *size > 0)?;
+    let start = -(size / 2);
+    Some((start, start + (size - 1)))
+}
+
+/// The spans of the width and the height of the topology.
+pub fn get_spans(t: &Topology) -> (Option<Span>, Option<Span>) {
+    match t {
+        Topology::Plane => (None, None),
+        Topology::Bounded { width, height }
+        | Topology::Torus { width, height }
+        | Topology::KleinBottle { width, height, .. } => (get_span(*width), get_span(*height)),
+    }
+}
+
+/// The rectangle of a topology bounded on both axes.
+pub fn get_rect(t: &Topology) -> Option<Rect> {
+    match get_spans(t) {
+        (Some((x1, x2)), Some((y1, y2))) => Some(Rect { x1, y1, x2, y2 }),
+        _ => None,
+    }
+}
+
+fn wrap(value: i64, start: i64, len: i64) -> (i64, i64) {
+    let offset = value - start;
+    (start + offset.rem_euclid(len), offset.div_euclid(len))
+}
+
+/// Maps a point to the cell it refers to inside the topology, or `None` if it
+/// falls beyond a dead edge.
+pub fn normalize(t: &Topology, p: &CartesianP) -> Option<CartesianP> {
+    let (xs, ys) = get_spans(t);
+    let inside = |value: i64, span: Option<Span>| {
+        span.is_none_or(|(start, end)| value >= start && value <= end)
+    };
+    // The position along the axis and how many times it wrapped around.
+    let around = |value: i64, span: Option<Span>| match span {
+        Some((start, end)) => wrap(value, start, end - start + 1),
+        None => (value, 0),
+    };
+    let mirror = |value: i64, span: Option<Span>| match span {
+        Some((start, end)) => start + end - value,
+        None => value,
+    };
+    match t {
+        Topology::Plane => Some(*p),
+        Topology::Bounded { .. } => (inside(p.x, xs) && inside(p.y, ys)).then_some(*p),
+        Topology::Torus { .. } => Some(CartesianP::of(around(p.x, xs).0, around(p.y, ys).0)),
+        Topology::KleinBottle { twist: Twist::Horizontal, .. } => {
+            let (y, turns) = around(p.y, ys);
+            let (x, _) = around(p.x, xs);
+            let x = if turns.rem_euclid(2) == 1 { mirror(x, xs) } else { x };
+            Some(CartesianP::of(x, y))
+        }
+        Topology::KleinBottle { twist: Twist::Vertical, .. } => {
+            let (x, turns) = around(p.x, xs);
+            let (y, _) = around(p.y, ys);
+            let y = if turns.rem_euclid(2) == 1 { mirror(y, ys) } else { y };
+            Some(CartesianP::of(x, y))
+        }
+    }
+}
+
+/// Moves every cell of the universe inside the topology, dropping the ones
+/// that fall beyond a dead edge.
+pub fn fit(u: &mut Universe, t: &Topology) {
+    if t == &Topology::Plane {
+        return;
+    }
+    let value: HashMap<CartesianP, State> =
+        u.value.iter().filter_map(|(p, s)| normalize(t, p).map(|p| (p, *s))).collect();
+    set_value(u, value);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_topology() {
+        assert_eq!(Topology::default(), Topology::Plane);
+        assert_eq!(format!("{}", Topology::Plane), "");
+        assert_eq!(format!("{}", Topology::Bounded { width: 30, height: 20 }), ":P30,20");
+        assert_eq!(format!("{}", Topology::Torus { width: 100, height: 80 }), ":T100,80");
+        assert_eq!(format!("{}", Topology::Torus { width: 10, height: 0 }), ":T10,0");
+        assert_eq!(
+            format!("{}", Topology::KleinBottle { width: 8, height: 6, twist: Twist::Horizontal }),
+            ":K8*,6"
+        );
+        assert_eq!(
+            format!("{}", Topology::KleinBottle { width: 8, height: 6, twist: Twist::Vertical }),
+            ":K8,6*"
+        );
+    }
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", InvalidFormatErr),
+            "The topology must be in the \"T100,80\" notation, using P, T or K!"
+        );
+        assert_eq!(
+            format!("{}", InvalidSizeErr),
+            "The width and the height must be below 2^63, and above zero on a Klein bottle!"
+        );
+        assert_eq!(from_string(""), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("T100"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("X10,10"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("T10,a"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("T10*,10"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("K10*,10*"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("C10,10"), Err(FromStringErr::InvalidFormat(InvalidFormatErr)));
+        assert_eq!(from_string("K0,10"), Err(FromStringErr::InvalidSize(InvalidSizeErr)));
+        assert_eq!(
+            from_string("T9223372036854775808,10"),
+            Err(FromStringErr::InvalidSize(InvalidSizeErr))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(from_string("P30,20"), Ok(Topology::Bounded { width: 30, height: 20 }));
+        assert_eq!(from_string("T100,80"), Ok(Topology::Torus { width: 100, height: 80 }));
+        assert_eq!(from_string("t100,80"), Ok(Topology::Torus { width: 100, height: 80 }));
+        assert_eq!(from_string("T10,0"), Ok(Topology::Torus { width: 10, height: 0 }));
+        assert_eq!(from_string("P0,20"), Ok(Topology::Bounded { width: 0, height: 20 }));
+        assert_eq!(
+            from_string("T9223372036854775807,1"),
+            Ok(Topology::Torus { width: 9223372036854775807, height: 1 })
+        );
+        let klein = |twist| Ok(Topology::KleinBottle { width: 8, height: 6, twist });
+        assert_eq!(from_string("K8*,6"), klein(Twist::Horizontal));
+        assert_eq!(from_string("K8,6"), klein(Twist::Horizontal));
+        assert_eq!(from_string("K8,6*"), klein(Twist::Vertical));
+    }
+
+    #[test]
+    fn test_get_rect() {
+        assert_eq!(get_rect(&Topology::Plane), None);
+        assert_eq!(
+            get_rect(&Topology::Torus { width: 4, height: 4 }),
+            Some(Rect::of(-2, -2, 1, 1))
+        );
+        assert_eq!(
+            get_rect(&Topology::Bounded { width: 5, height: 3 }),
+            Some(Rect::of(-2, -1, 2, 1))
+        );
+        assert_eq!(get_rect(&Topology::Torus { width: 4, height: 0 }), None);
+        assert_eq!(get_spans(&Topology::Torus { width: 4, height: 0 }), (Some((-2, 1)), None));
+        let max = i64::MAX as u64;
+        assert_eq!(
+            get_spans(&Topology::Torus { width: max, height: max }),
+            (Some((-(i64::MAX / 2), i64::MAX / 2)), Some((-(i64::MAX / 2), i64::MAX / 2)))
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        let p = CartesianP::of;
+        assert_eq!(normalize(&Topology::Plane, &p(100, -100)), Some(p(100, -100)));
+
+        let bounded = Topology::Bounded { width: 5, height: 3 };
+        assert_eq!(normalize(&bounded, &p(2, 1)), Some(p(2, 1)));
+        assert_eq!(normalize(&bounded, &p(3, 1)), None);
+        assert_eq!(normalize(&bounded, &p(2, -2)), None);
+
+        let torus = Topology::Torus { width: 5, height: 3 };
+        assert_eq!(normalize(&torus, &p(2, 1)), Some(p(2, 1)));
+        assert_eq!(normalize(&torus, &p(3, 1)), Some(p(-2, 1)));
+        assert_eq!(normalize(&torus, &p(-3, -2)), Some(p(2, 1)));
+        assert_eq!(normalize(&torus, &p(12, 7)), Some(p(2, 1)));
+
+        let cylinder = Topology::Torus { width: 5, height: 0 };
+        assert_eq!(normalize(&cylinder, &p(3, 1)), Some(p(-2, 1)));
+        assert_eq!(normalize(&cylinder, &p(-3, 0)), Some(p(2, 0)));
+        assert_eq!(normalize(&cylinder, &p(0, 200)), Some(p(0, 200)));
+
+        let strip = Topology::Bounded { width: 0, height: 3 };
+        assert_eq!(normalize(&strip, &p(-100, 1)), Some(p(-100, 1)));
+        assert_eq!(normalize(&strip, &p(0, 2)), None);
+
+        let klein = Topology::KleinBottle { width: 5, height: 3, twist: Twist::Horizontal };
+        assert_eq!(normalize(&klein, &p(3, 1)), Some(p(-2, 1)));
+        assert_eq!(normalize(&klein, &p(1, 2)), Some(p(-1, -1)));
+        assert_eq!(normalize(&klein, &p(-2, -2)), Some(p(2, 1)));
+        assert_eq!(normalize(&klein, &p(3, 2)), Some(p(2, -1)));
+        assert_eq!(normalize(&klein, &p(1, 5)), Some(p(1, -1)));
+
+        let klein = Topology::KleinBottle { width: 3, height: 5, twist: Twist::Vertical };
+        assert_eq!(normalize(&klein, &p(1, 3)), Some(p(1, -2)));
+        assert_eq!(normalize(&klein, &p(2, 1)), Some(p(-1, -1)));
+        assert_eq!(normalize(&klein, &p(-2, -2)), Some(p(1, 2)));
+        assert_eq!(normalize(&klein, &p(5, 1)), Some(p(-1, 1)));
+    }
+
+    #[test]
+    fn test_fit() {
+        let mut u = Universe::from(HashMap::from([
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(3, 0), State::Alive),
+            (CartesianP::of(0, 9), State::Alive),
+        ]));
+        let mut bounded = u.clone();
+        fit(&mut bounded, &Topology::Bounded { width: 5, height: 5 });
+        assert_eq!(bounded, Universe::from(HashMap::from([(CartesianP::of(0, 0), State::Alive)])));
+        fit(&mut u, &Topology::Torus { width: 5, height: 5 });
+        assert_eq!(
+            u,
+            Universe::from(HashMap::from([
+                (CartesianP::of(0, 0), State::Alive),
+                (CartesianP::of(-2, 0), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+            ]))
+        );
+    }
+}
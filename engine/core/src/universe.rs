@@ -0,0 +1,1447 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::{
+    cell::{self, toggle, State},
+    geometry::{
+        coordinate::{matrix_to_cartesian, CartesianP, MatrixP},
+        poligon::rect::{get_length, Rect},
+    },
+    margolus,
+    neighbor::{configuration_from_model, number_of_alive_from_model, offsets, states_from_model},
+    region::{self, chunk_of, reach, Regions},
+    rule::{Rule, RuleKind},
+    table,
+    topology::{get_spans, normalize, Topology},
+};
+
+use super::render::RenderSettings;
+
+#[derive(Debug, Clone, Default)]
+pub struct Universe {
+    pub value: HashMap<CartesianP, State>,
+    pub age: u64,
+    pub regions: Regions,
+}
+
+/// The regions are only a cache of the last generations, so they don't count.
+impl PartialEq for Universe {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.age == other.age
+    }
+}
+
+impl From<HashMap<CartesianP, State>> for Universe {
+    fn from(value: HashMap<CartesianP, State>) -> Self {
+        Universe { value, ..Default::default() }
+    }
+}
+
+/// Where the grid lands in the plane, either centered on the origin or with
+/// the given cell at the origin.
+#[derive(Debug, PartialEq)]
+pub enum Anchor {
+    Center,
+    At(MatrixP),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct GridFormat {
+    pub alive: char,
+    pub dead: char,
+    pub anchor: Anchor,
+}
+
+impl Default for GridFormat {
+    fn default() -> Self {
+        GridFormat { alive: '⬜', dead: '⬛', anchor: Anchor::Center }
+    }
+}
+
+impl GridFormat {
+    pub fn of(alive: char, dead: char) -> Self {
+        GridFormat { alive, dead, anchor: Anchor::Center }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidCharacterErr {
+    pub row: usize,
+    pub col: usize,
+    pub value: char,
+}
+
+impl fmt::Display for InvalidCharacterErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid character \"{}\" at row {}, column {}!", self.value, self.row, self.col)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InvalidLengthErr {
+    pub row: usize,
+}
+
+impl fmt::Display for InvalidLengthErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "The row {} must be as long as the first one!", self.row)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FromStringErr {
+    InvalidCharacter(InvalidCharacterErr),
+    InvalidLength(InvalidLengthErr),
+}
+
+impl fmt::Display for FromStringErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromStringErr::InvalidCharacter(e) => e.fmt(f),
+            FromStringErr::InvalidLength(e) => e.fmt(f),
+        }
+    }
+}
+
+pub fn from_string(as_str: Vec<String>) -> Result<Universe, FromStringErr> {
+    from_string_with(as_str, &GridFormat::default())
+}
+
+pub fn from_string_with(
+    as_str: Vec<String>,
+    format: &GridFormat,
+) -> Result<Universe, FromStringErr> {
+    let width = as_str.first().map(|row| row.chars().count()).unwrap_or(0) as i64;
+    let height = as_str.len() as i64;
+    let cam = match &format.anchor {
+        Anchor::Center => {
+            let x1 = -(width / 2);
+            let y1 = -(height / 2);
+            Rect { x1, y1, x2: x1 + width - 1, y2: y1 + height - 1 }
+        }
+        Anchor::At(p) => {
+            let x1 = -(p.col as i64);
+            let y2 = p.row as i64;
+            Rect { x1, y1: y2 - height + 1, x2: x1 + width - 1, y2 }
+        }
+    };
+    let mut value = HashMap::<CartesianP, State>::new();
+    for (row, row_str) in as_str.iter().enumerate() {
+        if row_str.chars().count() as i64 != width {
+            return Err(FromStringErr::InvalidLength(InvalidLengthErr { row: row + 1 }));
+        }
+        for (col, c) in row_str.chars().enumerate() {
+            if c == format.alive {
+                value.insert(
+                    matrix_to_cartesian(&MatrixP { row: row as u64, col: col as u64 }, &cam),
+                    State::Alive,
+                );
+            } else if c != format.dead {
+                return Err(FromStringErr::InvalidCharacter(InvalidCharacterErr {
+                    row: row + 1,
+                    col: col + 1,
+                    value: c,
+                }));
+            }
+        }
+    }
+    Ok(Universe::from(value))
+}
+
+pub fn get_value(u: &Universe, p: &CartesianP) -> State {
+    u.value.get(p).copied().unwrap_or(State::Dead)
+}
+
+/// Below this many cells to compute, handing them to other threads costs more
+/// than it saves.
+const PARALLEL_MIN_POINTS: usize = if cfg!(feature = "parallel") { 1 << 12 } else { usize::MAX };
+
+/// The height of the stripes of cells computed by each thread.
+#[cfg(feature = "parallel")]
+const STRIPE_HEIGHT: i64 = 32;
+
+pub fn iterate(u: &mut Universe, rule: &Rule) {
+    iterate_with(u, rule, PARALLEL_MIN_POINTS);
+}
+
+fn iterate_with(u: &mut Universe, rule: &Rule, parallel_min: usize) {
+    match &rule.kind {
+        RuleKind::Elementary(n) => return iterate_elementary(u, *n, &rule.topology),
+        RuleKind::Margolus(b) => return margolus::iterate(u, b, &rule.topology),
+        RuleKind::Totalistic | RuleKind::Isotropic(_) | RuleKind::Table(_) | RuleKind::Colored => {}
+    }
+    let offsets = offsets(&rule.neighborhood);
+    let neighbors = table::neighbors(&rule.neighborhood);
+    // Other topologies wrap the neighbors of a chunk far away from it.
+    if rule.topology != Topology::Plane {
+        let alive = u.value.keys();
+        let value = next_value(u, rule, (&offsets, &neighbors), alive, |_| true, parallel_min);
+        set_value(u, value);
+        u.age += 1;
+        return;
+    }
+    let reach = reach(&rule.neighborhood);
+    let mut regions = std::mem::take(&mut u.regions);
+    let plan = region::plan(&mut regions, &u.value, rule, reach);
+    let alive = region::cells_around(&regions, &plan.compute, reach);
+    let keep = |p: &CartesianP| plan.compute.contains(&chunk_of(p));
+    let value = next_value(u, rule, (&offsets, &neighbors), alive, keep, parallel_min);
+    region::update(&mut regions, &mut u.value, plan, value);
+    u.regions = regions;
+    u.age += 1;
+}
+
+/// The cells around `alive` in the next generation, among the ones to `keep`,
+/// computed in stripes by a thread pool when there are at least
+/// `parallel_min` of them.
+fn next_value<'a>(
+    u: &Universe,
+    rule: &Rule,
+    (offsets, neighbors): (&[CartesianP], &[CartesianP]),
+    alive: impl Iterator<Item = &'a CartesianP>,
+    keep: impl Fn(&CartesianP) -> bool,
+    parallel_min: usize,
+) -> HashMap<CartesianP, State> {
+    let points: HashSet<CartesianP> = alive
+        .flat_map(|point| {
+            offsets
+                .iter()
+                .map(|o| CartesianP::of(point.x + o.x, point.y + o.y))
+                .chain(std::iter::once(*point))
+        })
+        .filter_map(|point| normalize(&rule.topology, &point))
+        .collect();
+    let next = |point: &CartesianP| {
+        let s = get_value(u, point);
+        let new_cell = match &rule.kind {
+            RuleKind::Table(t) => {
+                let states = states_from_model(u, point, neighbors, &rule.topology);
+                cell::iterate_table(s, &states, t)
+            }
+            RuleKind::Isotropic(_) => {
+                let configuration = configuration_from_model(u, point, &rule.topology);
+                cell::iterate_isotropic(s, configuration, rule)
+            }
+            RuleKind::Colored => {
+                let states = states_from_model(u, point, offsets, &rule.topology);
+                cell::iterate_colored(s, &states, rule)
+            }
+            // The rules that evolve the whole universe at once don't get here.
+            RuleKind::Totalistic | RuleKind::Elementary(_) | RuleKind::Margolus(_) => {
+                let number_of_alive_neighbors =
+                    number_of_alive_from_model(u, point, offsets, &rule.topology);
+                cell::iterate(s, number_of_alive_neighbors, rule)
+            }
+        };
+        match new_cell {
+            State::Dead => None,
+            _ => Some((*point, new_cell)),
+        }
+    };
+    let points: Vec<CartesianP> = points.into_iter().filter(keep).collect();
+    evaluate(points, next, parallel_min)
+}
+
+#[cfg(feature = "parallel")]
+fn evaluate(
+    points: Vec<CartesianP>,
+    next: impl Fn(&CartesianP) -> Option<(CartesianP, State)> + Sync,
+    parallel_min: usize,
+) -> HashMap<CartesianP, State> {
+    use rayon::prelude::*;
+    if points.len() < parallel_min {
+        return points.iter().filter_map(next).collect();
+    }
+    let mut stripes = HashMap::<i64, Vec<CartesianP>>::new();
+    for p in points {
+        stripes.entry(p.y.div_euclid(STRIPE_HEIGHT)).or_default().push(p);
+    }
+    stripes
+        .into_par_iter()
+        .flat_map_iter(|(_, stripe)| stripe.into_iter().filter_map(|p| next(&p)))
+        .collect()
+}
+
+#[cfg(not(feature = "parallel"))]
+fn evaluate(
+    points: Vec<CartesianP>,
+    next: impl Fn(&CartesianP) -> Option<(CartesianP, State)>,
+    _parallel_min: usize,
+) -> HashMap<CartesianP, State> {
+    points.iter().filter_map(next).collect()
+}
+
+/// Evolves the row at y = 0 by the Wolfram rule `n`, where bit `k` gives the
+/// next state of a cell whose left, own and right states read `k` in binary.
+/// Cells off the row are dropped. Odd rules bring the dead cells to life, so
+/// they need a finite topology to visit every cell of the row.
+pub fn iterate_elementary(u: &mut Universe, n: u8, t: &Topology) {
+    let alive = |x: i64| {
+        normalize(t, &CartesianP::of(x, 0)).is_some_and(|p| get_value(u, &p) != State::Dead)
+    };
+    let xs: HashSet<i64> = match get_spans(t).0 {
+        Some((x1, x2)) if n % 2 == 1 => (x1..=x2).collect(),
+        _ => u
+            .value
+            .iter()
+            .filter(|(p, s)| p.y == 0 && **s != State::Dead)
+            .flat_map(|(p, _)| p.x - 1..=p.x + 1)
+            .filter_map(|x| normalize(t, &CartesianP::of(x, 0)).map(|p| p.x))
+            .collect(),
+    };
+    let value: HashMap<CartesianP, State> = xs
+        .into_iter()
+        .filter(|x| {
+            let k = u8::from(alive(x - 1)) << 2 | u8::from(alive(*x)) << 1 | u8::from(alive(x + 1));
+            n >> k & 1 == 1
+        })
+        .map(|x| (CartesianP::of(x, 0), State::Alive))
+        .collect();
+    u.age += 1;
+    set_value(u, value);
+}
+
+/// The number of cells in each state but the dead one, indexed by the number
+/// of the state minus one, which are the colors of a colored rule.
+pub fn get_population(u: &Universe, states: u8) -> Vec<u64> {
+    let mut result = vec![0; usize::from(states.max(2) - 1)];
+    for s in u.value.values() {
+        if let Some(count) =
+            usize::from(u8::from(*s)).checked_sub(1).and_then(|i| result.get_mut(i))
+        {
+            *count += 1;
+        }
+    }
+    result
+}
+
+/// Replaces every cell, which the regions can't follow.
+pub fn set_value(u: &mut Universe, value: HashMap<CartesianP, State>) {
+    u.value = value;
+    u.regions = Regions::default();
+}
+
+pub fn set_cell(u: &mut Universe, p: CartesianP, s: State) {
+    region::edit(&mut u.regions, &p, s);
+    match s {
+        State::Dead => {
+            u.value.remove(&p);
+        }
+        _ => {
+            u.value.insert(p, s);
+        }
+    }
+}
+
+pub fn toggle_cell(u: &mut Universe, p: CartesianP) {
+    set_cell(u, p, toggle(&get_value(u, &p)));
+}
+
+pub fn absolute_point_to_cartesian(s: &RenderSettings, p: MatrixP) -> CartesianP {
+    let dim = f64::from(s.dim);
+    let len = get_length(&s.cam) as f64;
+    let cell_size = dim / len;
+    let row = p.row as f64 / cell_size;
+    let col = p.col as f64 / cell_size;
+    let matrix_point = MatrixP { row: row as u64, col: col as u64 };
+    matrix_to_cartesian(&matrix_point, &s.cam)
+}
+
+pub fn toggle_cell_by_absolute_point(u: &mut Universe, s: &RenderSettings, p: MatrixP) {
+    toggle_cell(u, absolute_point_to_cartesian(s, p));
+}
+
+pub fn get_bounds(u: &Universe) -> Option<Rect> {
+    let mut points = u.value.iter().filter(|(_, s)| **s != State::Dead).map(|(p, _)| p);
+    let first = points.next()?;
+    Some(points.fold(Rect::of(first.x, first.y, first.x, first.y), |r, p| Rect {
+        x1: r.x1.min(p.x),
+        y1: r.y1.min(p.y),
+        x2: r.x2.max(p.x),
+        y2: r.y2.max(p.y),
+    }))
+}
+
+pub fn get_camera(u: &Universe, t: &Topology) -> Rect {
+    let bounds = get_bounds(u).unwrap_or(Rect::of(0, 0, 0, 0));
+    let (xs, ys) = get_spans(t);
+    let (mut min_x, mut max_x) = xs.unwrap_or((bounds.x1, bounds.x2));
+    let (mut min_y, mut max_y) = ys.unwrap_or((bounds.y1, bounds.y2));
+    let len_x = max_x - min_x + 1;
+    let len_y = max_y - min_y + 1;
+    if len_x > len_y {
+        let diff = len_x - len_y;
+        let diff_start = diff / 2;
+        let diff_end = diff - diff_start;
+        min_y -= diff_start;
+        max_y += diff_end;
+    }
+    if len_y > len_x {
+        let diff = len_y - len_x;
+        let diff_start = diff / 2;
+        let diff_end = diff - diff_start;
+        min_x -= diff_start;
+        max_x += diff_end;
+    }
+    Rect { x1: min_x - 4, y1: min_y - 4, x2: max_x + 4, y2: max_y + 4 }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        cell::State,
+        isotropic::{self, Isotropic},
+        neighbor::Neighborhood,
+        preset::get_preset_unsafe,
+        rule,
+    };
+
+    #[test]
+    fn test_model() {
+        assert_eq!(
+            Universe::default(),
+            Universe { value: HashMap::new(), age: 0, ..Default::default() }
+        );
+        assert_eq!(
+            Universe::from(HashMap::from([
+                (CartesianP::of(-1, -1), State::Alive),
+                (CartesianP::of(-1, 1), State::Alive),
+                (CartesianP::of(1, -1), State::Alive),
+                (CartesianP::of(1, 1), State::Alive),
+            ])),
+            Universe {
+                value: HashMap::from([
+                    (CartesianP::of(-1, -1), State::Alive),
+                    (CartesianP::of(-1, 1), State::Alive),
+                    (CartesianP::of(1, -1), State::Alive),
+                    (CartesianP::of(1, 1), State::Alive),
+                ]),
+                age: 0,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_string_err() {
+        assert_eq!(
+            format!("{}", InvalidCharacterErr { row: 2, col: 3, value: 'a' }),
+            "Invalid character \"a\" at row 2, column 3!"
+        );
+        assert_eq!(
+            format!("{}", InvalidLengthErr { row: 2 }),
+            "The row 2 must be as long as the first one!"
+        );
+        assert_eq!(
+            from_string(vec![String::from("⬛⬛"), String::from("⬛⬜⬛a")]),
+            Err(FromStringErr::InvalidLength(InvalidLengthErr { row: 2 })),
+        );
+        assert_eq!(
+            from_string(vec![String::from("abcdefg")]),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr {
+                row: 1,
+                col: 1,
+                value: 'a'
+            })),
+        );
+        assert_eq!(
+            from_string(vec![String::from("⬛⬛⬛"), String::from("⬛⬜O")]),
+            Err(FromStringErr::InvalidCharacter(InvalidCharacterErr {
+                row: 2,
+                col: 3,
+                value: 'O'
+            })),
+        );
+        assert_eq!(
+            from_string(vec![
+                String::from("⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛"),
+            ]),
+            Err(FromStringErr::InvalidLength(InvalidLengthErr { row: 2 })),
+        );
+        assert_eq!(
+            from_string_with(
+                vec![String::from("OO."), String::from(".⬜.")],
+                &GridFormat::of('O', '.')
+            )
+            .unwrap_err()
+            .to_string(),
+            "Invalid character \"⬜\" at row 2, column 2!"
+        );
+    }
+
+    #[test]
+    fn test_from_string_rectangular() {
+        assert_eq!(from_string(vec![]).unwrap(), Universe::default());
+        assert_eq!(from_string(vec![String::from("")]).unwrap(), Universe::default());
+        let glider = Universe::from(HashMap::from([
+            (CartesianP::of(-1, 1), State::Alive),
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(0, 1), State::Alive),
+            (CartesianP::of(1, -1), State::Alive),
+            (CartesianP::of(1, 0), State::Alive),
+        ]));
+        assert_eq!(
+            from_string(vec![
+                String::from("⬛⬛⬛⬛⬛"),
+                String::from("⬛⬜⬜⬛⬛"),
+                String::from("⬛⬛⬜⬜⬛"),
+                String::from("⬛⬛⬛⬜⬛"),
+            ])
+            .unwrap(),
+            Universe::from(HashMap::from([
+                (CartesianP::of(-1, 0), State::Alive),
+                (CartesianP::of(0, 0), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+                (CartesianP::of(1, -1), State::Alive),
+                (CartesianP::of(1, -2), State::Alive),
+            ]))
+        );
+        assert_eq!(
+            from_string_with(
+                vec![String::from("OO."), String::from(".OO"), String::from("..O")],
+                &GridFormat::of('O', '.')
+            )
+            .unwrap(),
+            glider
+        );
+        assert_eq!(
+            from_string_with(
+                vec![String::from("## "), String::from(" ##"), String::from("  #")],
+                &GridFormat::of('#', ' ')
+            )
+            .unwrap(),
+            glider
+        );
+        assert_eq!(
+            from_string_with(
+                vec![String::from("110"), String::from("011"), String::from("001")],
+                &GridFormat { alive: '1', dead: '0', anchor: Anchor::At(MatrixP::of(1, 1)) }
+            )
+            .unwrap(),
+            glider
+        );
+        assert_eq!(
+            from_string_with(
+                vec![String::from("O"), String::from("O")],
+                &GridFormat { alive: 'O', dead: '.', anchor: Anchor::At(MatrixP::of(5, 0)) }
+            )
+            .unwrap(),
+            Universe::from(HashMap::from([
+                (CartesianP::of(0, 5), State::Alive),
+                (CartesianP::of(0, 4), State::Alive),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_from_string() {
+        assert_eq!(from_string(vec![String::from("⬛")]).unwrap(), Universe::default());
+        assert_eq!(
+            from_string(vec![String::from("⬜")]).unwrap(),
+            Universe::from(HashMap::from([(CartesianP::of(0, 0), State::Alive)])),
+        );
+        assert_eq!(
+            from_string(vec![
+                String::from("⬛⬛⬛⬜"),
+                String::from("⬜⬛⬛⬛"),
+                String::from("⬛⬛⬜⬛"),
+                String::from("⬛⬛⬛⬛"),
+            ])
+            .unwrap(),
+            Universe::from(HashMap::from([
+                (CartesianP::of(-2, 0), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+                (CartesianP::of(1, 1), State::Alive),
+            ]))
+        );
+        assert_eq!(
+            from_string(vec![
+                String::from("⬛⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬜⬜⬛⬛"),
+                String::from("⬛⬛⬜⬜⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛⬛"),
+            ])
+            .unwrap(),
+            Universe::from(HashMap::from([
+                (CartesianP::of(-1, -1), State::Alive),
+                (CartesianP::of(-1, 0), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+                (CartesianP::of(0, 0), State::Alive),
+            ]),)
+        );
+        assert_eq!(
+            from_string(vec![
+                String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬛⬜⬛⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                String::from("⬛⬛⬛⬛⬛⬛⬛"),
+            ])
+            .unwrap(),
+            Universe::from(HashMap::from([
+                (CartesianP::of(0, -1), State::Alive),
+                (CartesianP::of(0, 0), State::Alive),
+                (CartesianP::of(0, 1), State::Alive),
+            ]),)
+        );
+    }
+
+    #[test]
+    fn test_toggle_model() {
+        let mut u = from_string(vec![
+            String::from("⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛"),
+            String::from("⬜⬜⬜⬜"),
+            String::from("⬜⬜⬜⬜"),
+        ])
+        .unwrap();
+        let state1 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛"),
+            String::from("⬜⬜⬜⬜"),
+            String::from("⬜⬜⬜⬜"),
+        ])
+        .unwrap();
+        let state2 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛"),
+            String::from("⬜⬜⬜⬜"),
+            String::from("⬜⬜⬜⬜"),
+        ])
+        .unwrap();
+        let state3 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛"),
+            String::from("⬜⬜⬛⬜"),
+            String::from("⬜⬜⬜⬜"),
+        ])
+        .unwrap();
+        let state4 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛"),
+            String::from("⬜⬜⬛⬜"),
+            String::from("⬜⬜⬜⬛"),
+        ])
+        .unwrap();
+        let state5 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛"),
+            String::from("⬜⬜⬛⬜"),
+            String::from("⬛⬜⬜⬛"),
+        ])
+        .unwrap();
+        let state6 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛"),
+            String::from("⬜⬛⬛⬜"),
+            String::from("⬛⬜⬜⬛"),
+        ])
+        .unwrap();
+        let state7 = from_string(vec![
+            String::from("⬜⬛⬛⬛"),
+            String::from("⬛⬜⬜⬛"),
+            String::from("⬜⬛⬛⬜"),
+            String::from("⬛⬜⬜⬛"),
+        ])
+        .unwrap();
+        let state8 = from_string(vec![
+            String::from("⬜⬛⬛⬜"),
+            String::from("⬛⬜⬜⬛"),
+            String::from("⬜⬛⬛⬜"),
+            String::from("⬛⬜⬜⬛"),
+        ])
+        .unwrap();
+        toggle_cell(&mut u, CartesianP::of(-2, 1));
+        assert_eq!(u, state1);
+        toggle_cell(&mut u, CartesianP::of(-1, 0));
+        assert_eq!(u, state2);
+        toggle_cell(&mut u, CartesianP::of(0, -1));
+        assert_eq!(u, state3);
+        toggle_cell(&mut u, CartesianP::of(1, -2));
+        assert_eq!(u, state4);
+        toggle_cell(&mut u, CartesianP::of(-2, -2));
+        assert_eq!(u, state5);
+        toggle_cell(&mut u, CartesianP::of(-1, -1));
+        assert_eq!(u, state6);
+        toggle_cell(&mut u, CartesianP::of(0, 0));
+        assert_eq!(u, state7);
+        toggle_cell(&mut u, CartesianP::of(1, 1));
+        assert_eq!(u, state8);
+    }
+
+    #[test]
+    fn test_set_cell() {
+        let mut u = Universe::default();
+        set_cell(&mut u, CartesianP::of(1, 2), State::Dying(2));
+        assert_eq!(get_value(&u, &CartesianP::of(1, 2)), State::Dying(2));
+        set_cell(&mut u, CartesianP::of(1, 2), State::Dead);
+        assert_eq!(u, Universe::default());
+    }
+
+    #[test]
+    fn test_toggle_cell_by_absolute_point() {
+        let mut u = Universe::default();
+        let state1 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+        ])
+        .unwrap();
+        let state2 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+        ])
+        .unwrap();
+        let state3 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+        ])
+        .unwrap();
+        let state4 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state5 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state6 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state7 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state8 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state9 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state10 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state11 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let s1 = RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1000, gap: 0 };
+        let s2 = RenderSettings { cam: Rect::of(-4, -4, 5, 5), dim: 1000, gap: 0 };
+        let s3 = RenderSettings { cam: Rect::of(-5, -4, 4, 5), dim: 1000, gap: 0 };
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(10, 10));
+        assert_eq!(u, state1);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(990, 10));
+        assert_eq!(u, state2);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(10, 990));
+        assert_eq!(u, state3);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(990, 990));
+        assert_eq!(u, state4);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(110, 110));
+        assert_eq!(u, state5);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(890, 110));
+        assert_eq!(u, state6);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(110, 890));
+        assert_eq!(u, state7);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(890, 890));
+        assert_eq!(u, state8);
+        toggle_cell_by_absolute_point(&mut u, &s1, MatrixP::of(710, 350));
+        assert_eq!(u, state9);
+        toggle_cell_by_absolute_point(&mut u, &s2, MatrixP::of(110, 890));
+        assert_eq!(u, state10);
+        toggle_cell_by_absolute_point(&mut u, &s3, MatrixP::of(110, 890));
+        assert_eq!(u, state11);
+    }
+
+    #[test]
+    fn test_toggle_cell_by_absolute_point_float_cell_size() {
+        let mut state1 = from_string(vec![
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬜⬛⬛⬛⬛⬛⬛⬜⬛"),
+            String::from("⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜"),
+        ])
+        .unwrap();
+        let state2 = from_string(vec![
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+            String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
+        ])
+        .unwrap();
+        let s = RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 996, gap: 0 };
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(1, 1));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(897, 99));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(99, 897));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(995, 995));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(100, 100));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(797, 199));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(199, 797));
+        toggle_cell_by_absolute_point(&mut state1, &s, MatrixP::of(895, 895));
+        assert_eq!(state1, state2);
+    }
+
+    #[test]
+    fn test_iterate() {
+        let mut model1x1iter0 = from_string(vec![String::from("⬜")]).unwrap();
+        let mut model1x1iter1 = from_string(vec![String::from("⬛")]).unwrap();
+        model1x1iter1.age = 1;
+        iterate(&mut model1x1iter0, &Rule::default());
+        assert_eq!(model1x1iter0, model1x1iter1);
+
+        let mut model2x2iter0 =
+            from_string(vec![String::from("⬜⬜"), String::from("⬜⬜")]).unwrap();
+        let mut model2x2iter1 =
+            from_string(vec![String::from("⬜⬜"), String::from("⬜⬜")]).unwrap();
+        model2x2iter1.age = 1;
+        iterate(&mut model2x2iter0, &Rule::default());
+        assert_eq!(model2x2iter0, model2x2iter1);
+
+        let mut model3x3_1_iter0 = from_string(vec![
+            String::from("⬛⬜⬛"),
+            String::from("⬛⬜⬛"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        let mut model3x3_1_iter1 = from_string(vec![
+            String::from("⬛⬛⬛"),
+            String::from("⬜⬜⬜"),
+            String::from("⬛⬛⬛"),
+        ])
+        .unwrap();
+        model3x3_1_iter1.age = 1;
+        iterate(&mut model3x3_1_iter0, &Rule::default());
+        assert_eq!(model3x3_1_iter0, model3x3_1_iter1);
+
+        let mut model3x3_2_iter0 = from_string(vec![
+            String::from("⬛⬛⬛"),
+            String::from("⬜⬜⬜"),
+            String::from("⬛⬛⬛"),
+        ])
+        .unwrap();
+        let mut model3x3_2_iter1 = from_string(vec![
+            String::from("⬛⬜⬛"),
+            String::from("⬛⬜⬛"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        model3x3_2_iter1.age = 1;
+        iterate(&mut model3x3_2_iter0, &Rule::default());
+        assert_eq!(model3x3_2_iter0, model3x3_2_iter1);
+
+        let mut model3x3_3_iter0 = from_string(vec![
+            String::from("⬛⬛⬜"),
+            String::from("⬜⬜⬜"),
+            String::from("⬛⬛⬛"),
+        ])
+        .unwrap();
+        let mut model3x3_3_iter1 = from_string(vec![
+            String::from("⬛⬛⬜"),
+            String::from("⬛⬜⬜"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        model3x3_3_iter1.age = 1;
+        iterate(&mut model3x3_3_iter0, &Rule::default());
+        assert_eq!(model3x3_3_iter0, model3x3_3_iter1);
+
+        let mut model3x3_4_iter0 = from_string(vec![
+            String::from("⬛⬛⬜"),
+            String::from("⬛⬜⬜"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        let mut model3x3_4_iter1 = from_string(vec![
+            String::from("⬛⬜⬜"),
+            String::from("⬛⬜⬜"),
+            String::from("⬛⬜⬜"),
+        ])
+        .unwrap();
+        model3x3_4_iter1.age = 1;
+        iterate(&mut model3x3_4_iter0, &Rule::default());
+        assert_eq!(model3x3_4_iter0, model3x3_4_iter1);
+
+        let mut model3x3_5_iter0 = from_string(vec![
+            String::from("⬜⬜⬛"),
+            String::from("⬜⬜⬜"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        let mut model3x3_5_iter1 = from_string(vec![
+            String::from("⬜⬛⬜"),
+            String::from("⬛⬛⬜"),
+            String::from("⬜⬜⬜"),
+        ])
+        .unwrap();
+        model3x3_5_iter1.age = 1;
+        iterate(&mut model3x3_5_iter0, &Rule::default());
+        assert_eq!(model3x3_5_iter0, model3x3_5_iter1);
+    }
+
+    #[test]
+    fn test_iterate_rule() {
+        let life_without_death = Rule::of(&[3], &[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut model_iter0 = from_string(vec![
+            String::from("⬛⬜⬛"),
+            String::from("⬛⬜⬛"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        let mut model_iter1 = from_string(vec![
+            String::from("⬛⬜⬛"),
+            String::from("⬜⬜⬜"),
+            String::from("⬛⬜⬛"),
+        ])
+        .unwrap();
+        model_iter1.age = 1;
+        iterate(&mut model_iter0, &life_without_death);
+        assert_eq!(model_iter0, model_iter1);
+    }
+
+    #[test]
+    fn test_iterate_generations() {
+        let brians_brain = Rule { states: 3, ..Rule::of(&[2], &[]) };
+        let mut u =
+            from_string(vec![String::from("⬛⬛"), String::from("⬜⬜"), String::from("⬛⬛")])
+                .unwrap();
+        iterate(&mut u, &brians_brain);
+        assert_eq!(
+            u.value,
+            HashMap::from([
+                (CartesianP::of(-1, 0), State::Dying(1)),
+                (CartesianP::of(0, 0), State::Dying(1)),
+                (CartesianP::of(-1, 1), State::Alive),
+                (CartesianP::of(0, 1), State::Alive),
+                (CartesianP::of(-1, -1), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+            ])
+        );
+        iterate(&mut u, &brians_brain);
+        assert_eq!(get_value(&u, &CartesianP::of(-1, 0)), State::Dead);
+        assert_eq!(get_value(&u, &CartesianP::of(-1, 1)), State::Dying(1));
+        assert_eq!(u.value.values().filter(|s| **s == State::Alive).count(), 6);
+        assert_eq!(u.value.values().filter(|s| **s == State::Dying(1)).count(), 4);
+        toggle_cell(&mut u, CartesianP::of(-1, 1));
+        assert_eq!(get_value(&u, &CartesianP::of(-1, 1)), State::Dead);
+    }
+
+    #[test]
+    fn test_iterate_neighborhood() {
+        let dot = from_string(vec![String::from("⬜")]).unwrap();
+        let population = |neighborhood| {
+            let mut u = dot.clone();
+            iterate(&mut u, &Rule { neighborhood, ..Rule::of(&[1], &[]) });
+            u.value.len()
+        };
+        assert_eq!(population(Neighborhood::default()), 8);
+        assert_eq!(population(Neighborhood::VonNeumann { range: 1 }), 4);
+        assert_eq!(population(Neighborhood::Hexagonal), 6);
+        assert_eq!(population(Neighborhood::Moore { range: 2 }), 24);
+        assert_eq!(population(Neighborhood::VonNeumann { range: 3 }), 24);
+        let mut u = dot.clone();
+        iterate(&mut u, &Rule { neighborhood: Neighborhood::Hexagonal, ..Rule::of(&[1], &[]) });
+        assert_eq!(get_value(&u, &CartesianP::of(1, 1)), State::Dead);
+        assert_eq!(get_value(&u, &CartesianP::of(-1, 1)), State::Alive);
+    }
+
+    #[test]
+    fn test_iterate_isotropic() {
+        let hensel = Rule {
+            kind: RuleKind::Isotropic(Box::new(Isotropic {
+                birth: isotropic::from_string("3").unwrap(),
+                survival: isotropic::from_string("2cekain3").unwrap(),
+            })),
+            ..Rule::of(&[], &[])
+        };
+        let mut expected = get_preset_unsafe("gosper_glider_gun");
+        let mut u = expected.clone();
+        for _ in 0..60 {
+            iterate(&mut expected, &Rule::default());
+            iterate(&mut u, &hensel);
+        }
+        assert_eq!(u, expected);
+        let tlife = rule::from_string("B3/S2-i34q").unwrap();
+        let mut blinker = from_string(vec![String::from("⬜⬜⬜")]).unwrap();
+        iterate(&mut blinker, &tlife);
+        assert_eq!(blinker.value.len(), 2);
+        iterate(&mut blinker, &tlife);
+        assert_eq!(blinker.value.len(), 0);
+    }
+
+    #[test]
+    fn test_iterate_table() {
+        let wire = table::from_string(
+            "@RULE Wire\n@TABLE\nn_states:4\nneighborhood:vonNeumann\nsymmetries:rotate4\nvar a={0,1,2,3}\nvar b={a}\nvar c={a}\nvar d={a}\n1,a,b,c,d,2\n2,a,b,c,d,3\n3,1,b,c,d,1\n",
+        )
+        .unwrap();
+        let rule = Rule::from(wire);
+        assert_eq!(rule.to_string(), "Wire");
+        let row = |states: [u8; 4]| {
+            Universe::from(
+                (0..4)
+                    .map(|x| (CartesianP::of(x, 0), State::from(states[x as usize])))
+                    .collect::<HashMap<CartesianP, State>>(),
+            )
+        };
+        let mut u = row([2, 1, 3, 3]);
+        iterate(&mut u, &rule);
+        assert_eq!(u.value, row([3, 2, 1, 3]).value);
+        iterate(&mut u, &rule);
+        assert_eq!(u.value, row([3, 3, 2, 1]).value);
+        iterate(&mut u, &rule);
+        assert_eq!(u.value, row([3, 3, 3, 2]).value);
+        assert_eq!(u.age, 3);
+    }
+
+    #[test]
+    fn test_iterate_elementary() {
+        let row = |xs: &[i64]| -> HashMap<CartesianP, State> {
+            xs.iter().map(|x| (CartesianP::of(*x, 0), State::Alive)).collect()
+        };
+        let mut u = Universe::from(row(&[0]));
+        u.value.insert(CartesianP::of(5, 5), State::Alive);
+        let rule30 = Rule { kind: RuleKind::Elementary(30), ..Rule::default() };
+        iterate(&mut u, &rule30);
+        assert_eq!(u.value, row(&[-1, 0, 1]));
+        iterate(&mut u, &rule30);
+        assert_eq!(u.value, row(&[-2, -1, 2]));
+        iterate(&mut u, &rule30);
+        assert_eq!(u.value, row(&[-3, -2, 0, 1, 2, 3]));
+        iterate(&mut u, &rule30);
+        assert_eq!(u.value, row(&[-4, -3, 0, 4]));
+        assert_eq!(u.age, 4);
+
+        let mut u = Universe::from(row(&[0]));
+        iterate_elementary(&mut u, 110, &Topology::Plane);
+        assert_eq!(u.value, row(&[-1, 0]));
+        iterate_elementary(&mut u, 110, &Topology::Plane);
+        assert_eq!(u.value, row(&[-2, -1, 0]));
+
+        let torus = Topology::Torus { width: 4, height: 1 };
+        let mut u = Universe::from(row(&[-2]));
+        iterate_elementary(&mut u, 30, &torus);
+        assert_eq!(u.value, row(&[-2, -1, 1]));
+
+        let mut u = Universe::default();
+        iterate_elementary(&mut u, 1, &torus);
+        assert_eq!(u.value, row(&[-2, -1, 0, 1]));
+        iterate_elementary(&mut u, 1, &torus);
+        assert_eq!(u.value, row(&[]));
+        iterate_elementary(&mut u, 1, &Topology::Bounded { width: 3, height: 1 });
+        assert_eq!(u.value, row(&[-1, 0, 1]));
+    }
+
+    #[test]
+    fn test_iterate_colored() {
+        let rule = rule::from_string("Immigration").unwrap();
+        let mut u = from_string(vec![String::from("⬜⬜⬜")]).unwrap();
+        u.value.insert(CartesianP::of(1, 0), State::Dying(1));
+        assert_eq!(get_population(&u, rule.states), vec![2, 1]);
+        iterate(&mut u, &rule);
+        assert_eq!(
+            u.value,
+            HashMap::from([
+                (CartesianP::of(0, 1), State::Alive),
+                (CartesianP::of(0, 0), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+            ])
+        );
+        u.value.insert(CartesianP::of(0, 1), State::Dying(1));
+        iterate(&mut u, &rule);
+        assert_eq!(
+            u.value,
+            HashMap::from([
+                (CartesianP::of(-1, 0), State::Alive),
+                (CartesianP::of(0, 0), State::Alive),
+                (CartesianP::of(1, 0), State::Alive),
+            ])
+        );
+        let mut u = get_preset_unsafe("glider");
+        let mut expected = u.clone();
+        for _ in 0..8 {
+            iterate(&mut u, &rule::from_string("QuadLife").unwrap());
+            iterate(&mut expected, &Rule::default());
+        }
+        assert_eq!(u, expected);
+        assert_eq!(get_population(&u, 5), vec![5, 0, 0, 0]);
+        assert_eq!(get_population(&u, 2), vec![5]);
+    }
+
+    #[test]
+    fn test_iterate_wireworld() {
+        let rule = table::wireworld();
+        let heads = |preset: &str, p: CartesianP, generations: u64| {
+            let mut u = get_preset_unsafe(preset);
+            let mut result = Vec::new();
+            for generation in 1..=generations {
+                iterate(&mut u, &rule);
+                if get_value(&u, &p) == State::Alive {
+                    result.push(generation);
+                }
+            }
+            result
+        };
+        assert_eq!(heads("wireworld_clock", CartesianP::of(6, 0), 40), vec![11, 21, 31]);
+        assert_eq!(heads("wireworld_diode", CartesianP::of(8, 2), 40), vec![14, 24, 34]);
+        assert_eq!(heads("wireworld_diode", CartesianP::of(8, -2), 40), vec![]);
+        assert_eq!(
+            heads("wireworld_xor", CartesianP::of(12, 0), 120),
+            vec![35, 41, 47, 71, 77, 83, 107, 113, 119]
+        );
+    }
+
+    #[test]
+    fn test_iterate_topology() {
+        let glider = from_string(vec![
+            String::from("⬛⬜⬛⬛"),
+            String::from("⬛⬛⬜⬛"),
+            String::from("⬜⬜⬜⬛"),
+            String::from("⬛⬛⬛⬛"),
+        ])
+        .unwrap();
+        let torus = Rule { topology: Topology::Torus { width: 6, height: 6 }, ..Rule::default() };
+        let mut u = glider.clone();
+        for _ in 0..24 {
+            iterate(&mut u, &torus);
+        }
+        assert_eq!(u.value, glider.value);
+
+        let bounded =
+            Rule { topology: Topology::Bounded { width: 4, height: 4 }, ..Rule::default() };
+        let mut u = glider.clone();
+        for _ in 0..16 {
+            iterate(&mut u, &bounded);
+        }
+        assert_eq!(
+            u.value,
+            HashMap::from([
+                (CartesianP::of(0, -1), State::Alive),
+                (CartesianP::of(0, -2), State::Alive),
+                (CartesianP::of(1, -1), State::Alive),
+                (CartesianP::of(1, -2), State::Alive),
+            ])
+        );
+
+        let blinker = from_string(vec![
+            String::from("⬛⬛⬛"),
+            String::from("⬜⬜⬜"),
+            String::from("⬛⬛⬛"),
+        ])
+        .unwrap();
+        let cylinder =
+            Rule { topology: Topology::Torus { width: 3, height: 0 }, ..Rule::default() };
+        let mut u = blinker.clone();
+        iterate(&mut u, &cylinder);
+        assert_eq!(u.value.len(), 9);
+        iterate(&mut u, &cylinder);
+        assert_eq!(get_bounds(&u), Some(Rect::of(-1, -2, 1, 2)));
+        let torus = Rule { topology: Topology::Torus { width: 3, height: 3 }, ..Rule::default() };
+        let mut u = blinker.clone();
+        iterate(&mut u, &torus);
+        assert_eq!(u.value.len(), 9);
+        iterate(&mut u, &torus);
+        assert_eq!(u.value, HashMap::new());
+    }
+
+    #[test]
+    fn test_iterate_regions() {
+        let rules = [
+            ("r_pentomino", Rule::default()),
+            ("puffer1", Rule::default()),
+            ("r_pentomino", rule::from_string("B2/S/C3").unwrap()),
+            ("r_pentomino", rule::from_string("B2/S34H").unwrap()),
+            ("wireworld_clock", rule::from_string("Wireworld").unwrap()),
+        ];
+        let edits = [
+            (200, CartesianP::of(3, 3)),
+            (201, CartesianP::of(3, 3)),
+            (202, CartesianP::of(40, -5)),
+        ];
+        for (preset, rule) in rules {
+            let mut u = get_preset_unsafe(preset);
+            let mut expected = u.clone();
+            for age in 0..300 {
+                // Edits on consecutive generations, where the regions repeat
+                // what they saw two generations ago.
+                for (_, p) in edits.iter().filter(|(at, _)| *at == age) {
+                    toggle_cell(&mut u, *p);
+                    toggle_cell(&mut expected, *p);
+                }
+                iterate(&mut u, &rule);
+                // A clone starts without regions, so it computes every cell.
+                expected = expected.clone();
+                iterate(&mut expected, &rule);
+                assert_eq!(u, expected);
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_iterate_parallel() {
+        use crate::preset::{get_preset, get_preset_groups, get_preset_rule};
+
+        for group in get_preset_groups() {
+            for preset in group.sub_groups.iter().flat_map(|sub_group| &sub_group.items) {
+                let Some(mut u) = get_preset(&preset.id) else {
+                    continue;
+                };
+                let rule = get_preset_rule(&preset.id).unwrap_or_default();
+                let mut expected = u.clone();
+                for _ in 0..500 {
+                    iterate_with(&mut u, &rule, 0);
+                    iterate_with(&mut expected, &rule, usize::MAX);
+                }
+                assert_eq!(u, expected, "{}", preset.id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_bounds() {
+        assert_eq!(get_bounds(&Universe::default()), None);
+        assert_eq!(
+            get_bounds(&from_string(vec![String::from("⬜")]).unwrap()),
+            Some(Rect::of(0, 0, 0, 0))
+        );
+        assert_eq!(
+            get_bounds(&Universe::from(HashMap::from([
+                (CartesianP::of(2, 2), State::Alive),
+                (CartesianP::of(3, 5), State::Alive),
+                (CartesianP::of(-5, 3), State::Alive),
+            ]))),
+            Some(Rect::of(-5, 2, 3, 5))
+        );
+    }
+
+    #[test]
+    fn test_get_camera() {
+        assert_eq!(
+            get_camera(
+                &from_string(vec![
+                    String::from("⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬜⬜⬛⬛"),
+                    String::from("⬛⬛⬜⬜⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛"),
+                ])
+                .unwrap(),
+                &Topology::Plane
+            ),
+            Rect::of(-5, -5, 4, 4)
+        );
+        assert_eq!(
+            get_camera(
+                &from_string(vec![
+                    String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬜⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬜⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬜⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                    String::from("⬛⬛⬛⬛⬛⬛⬛"),
+                ])
+                .unwrap(),
+                &Topology::Plane
+            ),
+            Rect::of(-5, -5, 5, 5)
+        );
+        assert_eq!(
+            get_camera(
+                &from_string(vec![
+                    String::from("⬛⬛⬛"),
+                    String::from("⬜⬜⬜"),
+                    String::from("⬛⬛⬛"),
+                ])
+                .unwrap(),
+                &Topology::Plane
+            ),
+            Rect::of(-5, -5, 5, 5)
+        );
+        assert_eq!(
+            get_camera(
+                &from_string(vec![
+                    String::from("⬛⬜⬛"),
+                    String::from("⬜⬜⬜"),
+                    String::from("⬛⬜⬛"),
+                ])
+                .unwrap(),
+                &Topology::Plane
+            ),
+            Rect::of(-5, -5, 5, 5)
+        );
+        assert_eq!(
+            get_camera(&from_string(vec![String::from("⬜"),]).unwrap(), &Topology::Plane),
+            Rect::of(-4, -4, 4, 4)
+        );
+        assert_eq!(get_camera(&Universe::default(), &Topology::Plane), Rect::of(-4, -4, 4, 4));
+        assert_eq!(
+            get_camera(&Universe::default(), &Topology::Torus { width: 100, height: 80 }),
+            Rect::of(-54, -54, 53, 53)
+        );
+        assert_eq!(
+            get_camera(&Universe::default(), &Topology::Torus { width: 100, height: 0 }),
+            Rect::of(-54, -53, 53, 54)
+        );
+        assert_eq!(
+            get_camera(
+                &from_string(vec![String::from("⬜"),]).unwrap(),
+                &Topology::Bounded { width: 3, height: 3 }
+            ),
+            Rect::of(-5, -5, 5, 5)
+        );
+        assert_eq!(
+            get_camera(
+                &Universe::from(HashMap::from([
+                    (CartesianP::of(2, 2), State::Alive),
+                    (CartesianP::of(3, 5), State::Alive),
+                    (CartesianP::of(5, 3), State::Alive),
+                ])),
+                &Topology::Plane
+            ),
+            Rect::of(-2, -2, 9, 9)
+        );
+        assert_eq!(
+            get_camera(
+                &Universe::from(HashMap::from([
+                    (CartesianP::of(2, 2), State::Alive),
+                    (CartesianP::of(3, 4), State::Alive),
+                    (CartesianP::of(5, 3), State::Alive),
+                ])),
+                &Topology::Plane
+            ),
+            Rect::of(-2, -2, 9, 9)
+        );
+        assert_eq!(
+            get_camera(
+                &Universe::from(HashMap::from([
+                    (CartesianP::of(2, 2), State::Alive),
+                    (CartesianP::of(3, 4), State::Alive),
+                    (CartesianP::of(4, 3), State::Alive),
+                ])),
+                &Topology::Plane
+            ),
+            Rect::of(-2, -2, 8, 8)
+        );
+    }
+}
@@ -1,6 +0,0 @@
-pub mod cell;
-pub mod geometry;
-pub mod neighbor;
-pub mod preset;
-pub mod render;
-pub mod universe;
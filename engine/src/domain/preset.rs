@@ -1,292 +0,0 @@
-use std::{collections::HashMap, sync::LazyLock};
-
-use super::universe::{from_string, Universe};
-
-#[derive(Clone)]
-pub struct PresetDiscover {
-    pub name: String,
-    pub year: u16,
-}
-
-#[derive(Clone)]
-pub struct Preset {
-    pub name: String,
-    pub id: String,
-    pub discover: PresetDiscover,
-}
-
-#[derive(Clone)]
-pub struct PresetSubGroup {
-    pub name: String,
-    pub id: String,
-    pub items: Vec<Preset>,
-}
-
-pub struct PresetGroupInfo {
-    pub name: String,
-    pub id: String,
-}
-
-pub struct PresetGroup {
-    pub info: PresetGroupInfo,
-    pub sub_groups: Vec<PresetSubGroup>,
-}
-
-static PRESETS: LazyLock<HashMap<String, Universe>> = LazyLock::new(|| {
-    HashMap::from([
-        (
-            String::from("boat"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛⬛"),
-                String::from("⬛⬜⬜⬛⬛"),
-                String::from("⬛⬜⬛⬜⬛"),
-                String::from("⬛⬛⬜⬜⬛"),
-                String::from("⬛⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-        (
-            String::from("block"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛"),
-                String::from("⬛⬜⬜⬛"),
-                String::from("⬛⬜⬜⬛"),
-                String::from("⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-        (
-            String::from("blinker"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬜⬛⬛"),
-                String::from("⬛⬛⬜⬛⬛"),
-                String::from("⬛⬛⬜⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-        (
-            String::from("r_pentomino"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬜⬜⬛"),
-                String::from("⬛⬜⬜⬛⬛"),
-                String::from("⬛⬛⬜⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-        (
-            String::from("glider"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛⬛"),
-                String::from("⬛⬜⬛⬜⬛"),
-                String::from("⬛⬛⬜⬜⬛"),
-                String::from("⬛⬛⬜⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-        (
-            String::from("gosper_glider_gun"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛"),
-                String::from("⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬛⬜⬜⬛⬛⬛⬛⬜⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-        (
-            String::from("puffer1"),
-            from_string(vec![
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬜⬜⬜⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬜⬜⬜⬛⬛"),
-                String::from("⬛⬜⬛⬛⬜⬛⬛⬛⬛⬛⬜⬜⬜⬛⬛⬛⬜⬜⬜⬛⬛⬛⬛⬛⬜⬛⬛⬜⬛"),
-                String::from("⬛⬛⬛⬛⬜⬛⬛⬛⬛⬜⬜⬛⬜⬛⬛⬛⬜⬛⬜⬜⬛⬛⬛⬛⬜⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬜⬛⬛⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬛⬛⬜⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬜⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬜⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬜⬛⬛⬛⬜⬜⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬜⬜⬛⬛⬛⬜⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-                String::from("⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛⬛"),
-            ])
-            .unwrap(),
-        ),
-    ])
-});
-
-pub fn get_preset_unsafe(preset: &str) -> Universe {
-    PRESETS.get(preset).cloned().unwrap()
-}
-
-pub fn get_preset(preset: &String) -> Option<Universe> {
-    PRESETS.get(preset).cloned()
-}
-
-pub fn get_preset_groups() -> Vec<PresetGroup> {
-    vec![
-        PresetGroup {
-            info: PresetGroupInfo {
-                name: String::from("StillLife"),
-                id: String::from("stillLife"),
-            },
-            sub_groups: vec![
-                PresetSubGroup {
-                    name: String::from("Ship"),
-                    id: String::from("ship"),
-                    items: vec![],
-                },
-                PresetSubGroup {
-                    name: String::from("Boat"),
-                    id: String::from("boat"),
-                    items: vec![Preset {
-                        name: String::from("Boat"),
-                        id: String::from("boat"),
-                        discover: PresetDiscover { name: String::from("JHC group"), year: 1970 },
-                    }],
-                },
-                PresetSubGroup {
-                    name: String::from("Loaf"),
-                    id: String::from("loaf"),
-                    items: vec![],
-                },
-                PresetSubGroup {
-                    name: String::from("General"),
-                    id: String::from("general"),
-                    items: vec![Preset {
-                        name: String::from("Block"),
-                        id: String::from("block"),
-                        discover: PresetDiscover { name: String::from("John Conway"), year: 1969 },
-                    }],
-                },
-            ],
-        },
-        PresetGroup {
-            info: PresetGroupInfo {
-                name: String::from("Oscillators"),
-                id: String::from("oscillators"),
-            },
-            sub_groups: vec![PresetSubGroup {
-                name: String::from("General"),
-                id: String::from("general"),
-                items: vec![Preset {
-                    name: String::from("Blinker"),
-                    id: String::from("blinker"),
-                    discover: PresetDiscover { name: String::from("John Conway"), year: 1969 },
-                }],
-            }],
-        },
-        PresetGroup {
-            info: PresetGroupInfo {
-                name: String::from("Methuselahs"),
-                id: String::from("methuselahs"),
-            },
-            sub_groups: vec![PresetSubGroup {
-                name: String::from("General"),
-                id: String::from("general"),
-                items: vec![Preset {
-                    name: String::from("R-Pentomino"),
-                    id: String::from("r_pentomino"),
-                    discover: PresetDiscover { name: String::from("John Conway"), year: 1969 },
-                }],
-            }],
-        },
-        PresetGroup {
-            info: PresetGroupInfo {
-                name: String::from("Spaceships"),
-                id: String::from("spaceships"),
-            },
-            sub_groups: vec![PresetSubGroup {
-                name: String::from("General"),
-                id: String::from("general"),
-                items: vec![Preset {
-                    name: String::from("Glider"),
-                    id: String::from("glider"),
-                    discover: PresetDiscover { name: String::from("Richard K. Guy"), year: 1969 },
-                }],
-            }],
-        },
-        PresetGroup {
-            info: PresetGroupInfo {
-                name: String::from("Glider gun"),
-                id: String::from("gliderGun"),
-            },
-            sub_groups: vec![PresetSubGroup {
-                name: String::from("General"),
-                id: String::from("general"),
-                items: vec![Preset {
-                    name: String::from("Gosper glider gun"),
-                    id: String::from("gosper_glider_gun"),
-                    discover: PresetDiscover { name: String::from("Bill Gosper"), year: 1970 },
-                }],
-            }],
-        },
-        PresetGroup {
-            info: PresetGroupInfo { name: String::from("Puffer"), id: String::from("puffer") },
-            sub_groups: vec![PresetSubGroup {
-                name: String::from("General"),
-                id: String::from("general"),
-                items: vec![Preset {
-                    name: String::from("Puffer 1"),
-                    id: String::from("puffer1"),
-                    discover: PresetDiscover { name: String::from("Bill Gosper"), year: 1971 },
-                }],
-            }],
-        },
-    ]
-}
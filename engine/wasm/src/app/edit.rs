@@ -0,0 +1,238 @@
+use game_of_life_core::{
+    cell::State, geometry::coordinate::CartesianP, rule::Rule, universe::Universe,
+};
+
+use super::history::{History, DEFAULT_BUDGET};
+
+/// How many edits can be undone by default.
+pub const DEFAULT_LIMIT: usize = 100;
+
+/// Everything a destructive edit can replace, along with the generations
+/// that led to it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Scene {
+    pub universe: Universe,
+    pub rule: Rule,
+    pub preset: Option<String>,
+    pub history: History,
+}
+
+impl Scene {
+    /// A scene with no past generations.
+    pub fn new(universe: Universe, rule: Rule, preset: Option<String>) -> Self {
+        Scene { universe, rule, preset, history: History::default() }
+    }
+
+    fn cost(&self) -> usize {
+        self.universe.value.len() + self.history.cost()
+    }
+}
+
+/// A reversible change made by the user, as opposed to the evolution.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Edit {
+    /// Toggling the same cell again reverts it.
+    Toggle(CartesianP),
+    /// Going through the states of a rule table, which toggling can't revert.
+    Set { p: CartesianP, before: State, after: State },
+    /// The cells that were dead before the paste and alive after it.
+    Paste(Vec<CartesianP>),
+    /// Loading a preset or a pattern, clearing, changing the rule, or the
+    /// local edits of a generation that the app then left.
+    Replace { before: Box<Scene>, after: Box<Scene> },
+}
+
+impl Edit {
+    /// Whether the edit only makes sense on the generation it was made on.
+    pub fn is_local(&self) -> bool {
+        !matches!(self, Edit::Replace { .. })
+    }
+
+    /// The cells the edit keeps, counting the history of the scenes it
+    /// replaces even where it shares it with other scenes.
+    fn cost(&self) -> usize {
+        match self {
+            Edit::Toggle(_) | Edit::Set { .. } => 1,
+            Edit::Paste(cells) => cells.len(),
+            Edit::Replace { before, after } => before.cost() + after.cost(),
+        }
+    }
+}
+
+/// The edits to undo and redo, up to `limit` of them, dropping the oldest
+/// ones while the cells they keep exceed the budget, which is the one of the
+/// history. The last edit is always kept.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EditStack {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    limit: usize,
+    budget: usize,
+}
+
+impl Default for EditStack {
+    fn default() -> Self {
+        EditStack::new(DEFAULT_LIMIT)
+    }
+}
+
+impl EditStack {
+    pub fn new(limit: usize) -> Self {
+        EditStack { undo: Vec::new(), redo: Vec::new(), limit, budget: DEFAULT_BUDGET }
+    }
+
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.shrink();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Records an edit that was just applied, discarding the ones that could
+    /// be redone.
+    pub fn push(&mut self, edit: Edit) {
+        self.redo.clear();
+        self.undo.push(edit);
+        self.shrink();
+    }
+
+    fn shrink(&mut self) {
+        let mut cost: usize = self.undo.iter().chain(&self.redo).map(Edit::cost).sum();
+        while self.undo.len() > self.limit || self.undo.len() > 1 && cost > self.budget {
+            cost -= self.undo.remove(0).cost();
+        }
+    }
+
+    /// Removes the local edits made since the last replacement, in the order
+    /// they were applied, along with the ones that could be redone on top of
+    /// them.
+    pub fn take_local(&mut self) -> Vec<Edit> {
+        let start = self.undo.iter().rposition(|e| !e.is_local()).map_or(0, |i| i + 1);
+        let end = self.redo.iter().rposition(|e| !e.is_local()).map_or(0, |i| i + 1);
+        self.redo.truncate(end);
+        self.undo.split_off(start)
+    }
+
+    /// Records an edit in place of local ones, keeping the ones that could
+    /// be redone.
+    pub fn settle(&mut self, edit: Edit) {
+        let redo = std::mem::take(&mut self.redo);
+        self.push(edit);
+        self.redo = redo;
+    }
+
+    /// Returns the edit to revert.
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo.pop()?;
+        self.redo.push(edit.clone());
+        Some(edit)
+    }
+
+    /// Returns the edit to apply again.
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo.pop()?;
+        self.undo.push(edit.clone());
+        Some(edit)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_edit_stack() {
+        let mut stack = EditStack::default();
+        assert!(!stack.can_undo());
+        assert!(!stack.can_redo());
+        assert_eq!(stack.undo(), None);
+        assert_eq!(stack.redo(), None);
+
+        stack.push(Edit::Toggle(CartesianP::of(0, 0)));
+        stack.push(Edit::Toggle(CartesianP::of(1, 0)));
+        assert!(stack.can_undo());
+        assert_eq!(stack.undo(), Some(Edit::Toggle(CartesianP::of(1, 0))));
+        assert!(stack.can_redo());
+        assert_eq!(stack.undo(), Some(Edit::Toggle(CartesianP::of(0, 0))));
+        assert!(!stack.can_undo());
+        assert_eq!(stack.redo(), Some(Edit::Toggle(CartesianP::of(0, 0))));
+        assert!(stack.can_undo());
+        assert!(stack.can_redo());
+
+        stack.push(Edit::Paste(vec![CartesianP::of(2, 0)]));
+        assert!(!stack.can_redo());
+        assert_eq!(stack.undo(), Some(Edit::Paste(vec![CartesianP::of(2, 0)])));
+        stack.clear();
+        assert!(!stack.can_undo());
+        assert!(!stack.can_redo());
+    }
+
+    #[test]
+    fn test_take_local() {
+        let scene = Scene::new(Universe::default(), Rule::default(), None);
+        let replace = Edit::Replace { before: Box::new(scene.clone()), after: Box::new(scene) };
+        let mut stack = EditStack::default();
+        stack.push(Edit::Toggle(CartesianP::of(0, 0)));
+        stack.push(replace.clone());
+        stack.push(Edit::Toggle(CartesianP::of(1, 0)));
+        stack.push(Edit::Paste(vec![CartesianP::of(2, 0)]));
+        stack.push(Edit::Toggle(CartesianP::of(3, 0)));
+        stack.undo();
+        assert_eq!(
+            stack.take_local(),
+            vec![Edit::Toggle(CartesianP::of(1, 0)), Edit::Paste(vec![CartesianP::of(2, 0)])]
+        );
+        assert!(!stack.can_redo());
+        assert_eq!(stack.take_local(), vec![]);
+
+        stack.undo();
+        stack.settle(Edit::Toggle(CartesianP::of(4, 0)));
+        assert_eq!(stack.redo(), Some(replace));
+        assert_eq!(stack.take_local(), vec![]);
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut stack = EditStack::new(2);
+        for x in 0..3 {
+            stack.push(Edit::Toggle(CartesianP::of(x, 0)));
+        }
+        assert_eq!(stack.undo(), Some(Edit::Toggle(CartesianP::of(2, 0))));
+        assert_eq!(stack.undo(), Some(Edit::Toggle(CartesianP::of(1, 0))));
+        assert_eq!(stack.undo(), None);
+    }
+
+    #[test]
+    fn test_budget() {
+        let replace = |x| {
+            let u = Universe::from(HashMap::from([(CartesianP::of(x, 0), State::Alive)]));
+            let scene = Scene::new(u, Rule::default(), None);
+            Edit::Replace { before: Box::new(scene.clone()), after: Box::new(scene) }
+        };
+        let mut stack = EditStack::default();
+        stack.set_budget(4);
+        stack.push(replace(0));
+        stack.push(replace(1));
+        stack.push(Edit::Toggle(CartesianP::of(0, 0)));
+        assert_eq!(stack.undo(), Some(Edit::Toggle(CartesianP::of(0, 0))));
+        assert_eq!(stack.undo(), Some(replace(1)));
+        assert_eq!(stack.undo(), None);
+        stack.set_budget(0);
+        stack.push(replace(2));
+        assert_eq!(stack.undo(), Some(replace(2)));
+        assert_eq!(stack.undo(), None);
+    }
+}
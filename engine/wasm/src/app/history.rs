@@ -0,0 +1,312 @@
+use std::{collections::VecDeque, rc::Rc};
+
+use game_of_life_core::{cell::State, geometry::coordinate::CartesianP, universe::Universe};
+
+/// How many cells the history keeps by default, counting every cell of the
+/// keyframes and every change of the deltas.
+pub const DEFAULT_BUDGET: usize = 1 << 20;
+
+const KEYFRAME_INTERVAL: usize = 32;
+
+#[derive(Debug, PartialEq, Clone)]
+struct Delta {
+    age: u64,
+    changes: Vec<(CartesianP, Option<State>)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Segment {
+    keyframe: Universe,
+    deltas: Vec<Delta>,
+}
+
+impl Segment {
+    fn cost(&self) -> usize {
+        self.keyframe.value.len()
+            + 1
+            + self.deltas.iter().map(|d| d.changes.len() + 1).sum::<usize>()
+    }
+
+    fn age_at(&self, index: usize) -> u64 {
+        match index {
+            0 => self.keyframe.age,
+            i => self.deltas[i - 1].age,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.deltas.len() + 1
+    }
+
+    /// Rebuilds the state at `index`, where 0 is the keyframe and `i` is the
+    /// keyframe with the first `i` deltas applied.
+    fn restore(&self, index: usize) -> Universe {
+        let mut u = self.keyframe.clone();
+        for delta in &self.deltas[..index] {
+            for (p, s) in &delta.changes {
+                match s {
+                    Some(s) => u.value.insert(*p, *s),
+                    None => u.value.remove(p),
+                };
+            }
+            u.age = delta.age;
+        }
+        u
+    }
+}
+
+fn diff(from: &Universe, to: &Universe) -> Vec<(CartesianP, Option<State>)> {
+    let removed = from.value.keys().filter(|p| !to.value.contains_key(p)).map(|p| (*p, None));
+    let changed =
+        to.value.iter().filter(|(p, s)| from.value.get(p) != Some(s)).map(|(p, s)| (*p, Some(*s)));
+    removed.chain(changed).collect()
+}
+
+/// The universes the app went through, oldest first, stored as keyframes
+/// followed by the changes of each later state. A segment never grows past a
+/// quarter of the budget, and when the stored cells exceed the budget, the
+/// oldest segments are dropped. Clones share their segments until they change.
+#[derive(Debug, PartialEq, Clone)]
+pub struct History {
+    segments: VecDeque<Rc<Segment>>,
+    last: Option<Universe>,
+    cost: usize,
+    budget: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new(DEFAULT_BUDGET)
+    }
+}
+
+impl History {
+    pub fn new(budget: usize) -> Self {
+        History { segments: VecDeque::new(), last: None, cost: 0, budget }
+    }
+
+    pub fn len(&self) -> usize {
+        self.segments.iter().map(|s| s.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// The cells stored, as counted against the budget.
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    pub fn set_budget(&mut self, budget: usize) {
+        self.budget = budget;
+        self.shrink();
+    }
+
+    pub fn clear(&mut self) {
+        self.segments.clear();
+        self.last = None;
+        self.cost = 0;
+    }
+
+    pub fn push(&mut self, u: &Universe) {
+        let changes = match &self.last {
+            Some(last) if !self.segments.is_empty() => Some(diff(last, u)),
+            _ => None,
+        };
+        match (changes, self.segments.back_mut()) {
+            (Some(changes), Some(segment))
+                if segment.deltas.len() < KEYFRAME_INTERVAL
+                    && changes.len() < u.value.len()
+                    && segment.cost() + changes.len() < self.budget / 4 =>
+            {
+                self.cost += changes.len() + 1;
+                Rc::make_mut(segment).deltas.push(Delta { age: u.age, changes });
+            }
+            _ => {
+                self.cost += u.value.len() + 1;
+                self.segments
+                    .push_back(Rc::new(Segment { keyframe: u.clone(), deltas: Vec::new() }));
+            }
+        }
+        self.last = Some(u.clone());
+        self.shrink();
+    }
+
+    /// Removes the most recent state and returns it.
+    pub fn pop(&mut self) -> Option<Universe> {
+        let last = self.last.take()?;
+        self.truncate(self.len() - 1);
+        Some(last)
+    }
+
+    /// Removes every state after the latest one not newer than `age`, and
+    /// returns that state too, or `None` if `age` is older than the history.
+    pub fn seek(&mut self, age: u64) -> Option<Universe> {
+        let mut index = self.len();
+        for segment in self.segments.iter().rev() {
+            for i in (0..segment.len()).rev() {
+                index -= 1;
+                if segment.age_at(i) <= age {
+                    let found = segment.restore(i);
+                    self.truncate(index);
+                    return Some(found);
+                }
+            }
+        }
+        None
+    }
+
+    /// Keeps only the first `len` states.
+    fn truncate(&mut self, len: usize) {
+        let mut remaining = len;
+        let mut kept = 0;
+        for segment in self.segments.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            let segment_len = remaining.min(segment.len());
+            if segment_len < segment.len() {
+                Rc::make_mut(segment).deltas.truncate(segment_len - 1);
+            }
+            remaining -= segment_len;
+            kept += 1;
+        }
+        self.segments.truncate(kept);
+        self.cost = self.segments.iter().map(|s| s.cost()).sum();
+        self.last = self.segments.back().map(|s| s.restore(s.deltas.len()));
+    }
+
+    fn shrink(&mut self) {
+        while self.cost > self.budget {
+            match self.segments.pop_front() {
+                Some(segment) => self.cost -= segment.cost(),
+                None => break,
+            }
+        }
+        if self.segments.is_empty() {
+            self.last = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use game_of_life_core::{preset::get_preset_unsafe, rule::Rule, universe::iterate};
+
+    use super::*;
+
+    fn run(preset: &str, generations: u64) -> Vec<Universe> {
+        let mut u = get_preset_unsafe(preset);
+        let mut states = vec![u.clone()];
+        for _ in 0..generations {
+            iterate(&mut u, &Rule::default());
+            states.push(u.clone());
+        }
+        states
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let states = run("gosper_glider_gun", 80);
+        let mut history = History::default();
+        assert_eq!(history.pop(), None);
+        for u in &states {
+            history.push(u);
+        }
+        assert_eq!(history.len(), 81);
+        assert!(history.segments.len() >= 3);
+        for u in states.iter().rev() {
+            assert_eq!(history.pop().as_ref(), Some(u));
+        }
+        assert_eq!(history.pop(), None);
+        assert!(history.is_empty());
+        assert_eq!(history.cost, 0);
+    }
+
+    #[test]
+    fn test_push_after_pop() {
+        let states = run("glider", 40);
+        let mut history = History::default();
+        for u in &states[..36] {
+            history.push(u);
+        }
+        history.pop();
+        history.pop();
+        for u in &states[34..] {
+            history.push(u);
+        }
+        for u in states.iter().rev() {
+            assert_eq!(history.pop().as_ref(), Some(u));
+        }
+    }
+
+    #[test]
+    fn test_seek() {
+        let states = run("glider", 100);
+        let mut history = History::default();
+        for u in states.iter().step_by(4) {
+            history.push(u);
+        }
+        assert_eq!(history.seek(101), Some(states[100].clone()));
+        assert_eq!(history.len(), 25);
+        assert_eq!(history.seek(42), Some(states[40].clone()));
+        assert_eq!(history.len(), 10);
+        assert_eq!(history.pop(), Some(states[36].clone()));
+        assert_eq!(history.seek(0), Some(states[0].clone()));
+        assert!(history.is_empty());
+        assert_eq!(history.seek(0), None);
+    }
+
+    #[test]
+    fn test_budget() {
+        let states = run("glider", 100);
+        let mut history = History::new(100);
+        for u in &states {
+            history.push(u);
+        }
+        assert!(history.cost <= 100);
+        assert_eq!(history.cost, history.segments.iter().map(|s| s.cost()).sum());
+        assert_eq!(history.pop(), Some(states[100].clone()));
+        let oldest = history.segments.front().unwrap().keyframe.age;
+        assert!(oldest > 0);
+        assert_eq!(history.seek(oldest - 1), None);
+        assert_eq!(history.seek(oldest), Some(states[oldest as usize].clone()));
+
+        let mut history = History::new(2);
+        history.push(&states[0]);
+        assert!(history.is_empty());
+        assert_eq!(history.pop(), None);
+
+        let mut history = History::default();
+        for u in &states {
+            history.push(u);
+        }
+        history.set_budget(0);
+        assert!(history.is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let from = Universe::from(HashMap::from([
+            (CartesianP::of(0, 0), State::Alive),
+            (CartesianP::of(1, 0), State::Alive),
+        ]));
+        let to = Universe::from(HashMap::from([
+            (CartesianP::of(1, 0), State::Alive),
+            (CartesianP::of(2, 0), State::Alive),
+        ]));
+        let mut changes = diff(&from, &to);
+        changes.sort_by_key(|(p, _)| p.x);
+        assert_eq!(
+            changes,
+            vec![(CartesianP::of(0, 0), None), (CartesianP::of(2, 0), Some(State::Alive))]
+        );
+    }
+}
@@ -0,0 +1,1576 @@
+use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+
+use self::{
+    edit::{Edit, EditStack, Scene},
+    history::History,
+};
+
+use game_of_life_core::{
+    analysis::{analyze, Analysis},
+    cell::{cycle, toggle, State},
+    evolution::{build_evolution, Algorithm, Evolution},
+    format::{self, rle, Format, Pattern},
+    geometry::{
+        coordinate::{CartesianP, MatrixP},
+        poligon::rect::{get_length, move_by, zoom_in, zoom_out, zoom_to},
+    },
+    margolus,
+    preset::{get_preset, get_preset_groups, get_preset_rule, get_preset_unsafe, Preset},
+    render::RenderSettings,
+    rule::{self, Rule, RuleKind},
+    table,
+    topology::{fit, normalize, Topology},
+    universe::{
+        absolute_point_to_cartesian, get_camera, get_population, get_value, iterate, set_cell,
+        toggle_cell, Universe,
+    },
+};
+
+pub mod edit;
+pub mod history;
+#[cfg(feature = "web")]
+pub mod web;
+
+pub struct PresetOptionItem {
+    pub label: String,
+    pub value: String,
+}
+
+pub struct PresetOptionGroup {
+    pub label: String,
+    pub value: String,
+    pub options: Vec<PresetOptionItem>,
+}
+
+pub fn build_presets() -> Vec<Preset> {
+    get_preset_groups()
+        .iter()
+        .flat_map(|group| {
+            group
+                .sub_groups
+                .iter()
+                .flat_map(|sub_group| sub_group.items.clone())
+                .collect::<Vec<Preset>>()
+        })
+        .collect()
+}
+
+pub fn build_preset_option_groups() -> Vec<PresetOptionGroup> {
+    get_preset_groups()
+        .iter()
+        .map(|group| PresetOptionGroup {
+            label: group.info.name.clone(),
+            value: group.info.id.clone(),
+            options: group
+                .sub_groups
+                .iter()
+                .flat_map(|sub_group| sub_group.items.clone())
+                .map(|item| PresetOptionItem { label: item.name, value: item.id })
+                .collect(),
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Status {
+    Resumed,
+    Paused,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AppSettings {
+    pub preset: Option<String>,
+    pub fps: u16,
+    pub status: Status,
+    pub render_settings: RenderSettings,
+    pub rule: Rule,
+    pub algorithm: Algorithm,
+    pub step: u8,
+}
+
+pub struct Model {
+    pub universe: Universe,
+    pub settings: AppSettings,
+    #[cfg(feature = "web")]
+    pub holder: Option<web::Holder>,
+    pub evolution: Box<dyn Evolution>,
+    pub history: History,
+    pub edits: EditStack,
+    pub rows: VecDeque<Universe>,
+    pub jump: Option<Jump>,
+    pub jumps: u64,
+}
+
+impl Default for Model {
+    fn default() -> Self {
+        let universe = get_preset_unsafe("block");
+        let cam = get_camera(&universe, &Topology::Plane);
+        Model {
+            universe,
+            settings: AppSettings {
+                preset: Some(String::from("block")),
+                fps: 4,
+                status: Status::Paused,
+                render_settings: RenderSettings { cam, dim: 0, gap: 0 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            },
+            #[cfg(feature = "web")]
+            holder: None,
+            evolution: build_evolution(Algorithm::Sparse),
+            history: History::default(),
+            edits: EditStack::default(),
+            rows: VecDeque::new(),
+            jump: None,
+            jumps: 0,
+        }
+    }
+}
+
+type Listener = Box<dyn FnMut(Prop) + 'static>;
+
+#[derive(Debug, Clone)]
+pub enum Prop {
+    Universe,
+    Preset,
+    Gap,
+    FPS,
+    Status,
+    Dim,
+    Cam,
+    Rule,
+    Algorithm,
+    Step,
+    History,
+}
+
+fn get_scene(m: &Model) -> Scene {
+    Scene {
+        universe: m.universe.clone(),
+        rule: m.settings.rule.clone(),
+        preset: m.settings.preset.clone(),
+        history: m.history.clone(),
+    }
+}
+
+fn set_scene(m: &mut Model, scene: Scene, recenter: bool) {
+    if recenter {
+        m.settings.render_settings.cam = get_camera(&scene.universe, &scene.rule.topology);
+    }
+    m.universe = scene.universe;
+    m.settings.rule = scene.rule;
+    m.settings.preset = scene.preset;
+    let budget = m.history.budget();
+    m.history = scene.history;
+    m.history.set_budget(budget);
+    m.rows.clear();
+    m.jump = None;
+}
+
+fn replace_scene(m: &mut Model, scene: Scene, recenter: bool) {
+    let before = get_scene(m);
+    set_scene(m, scene.clone(), recenter);
+    m.edits.push(Edit::Replace { before: Box::new(before), after: Box::new(scene) });
+}
+
+/// Applies or reverts a local edit.
+fn apply_cells(u: &mut Universe, edit: &Edit, forward: bool) {
+    match edit {
+        Edit::Toggle(p) => toggle_cell(u, *p),
+        Edit::Set { p, before, after } => set_cell(u, *p, if forward { *after } else { *before }),
+        Edit::Paste(cells) => {
+            for p in cells {
+                set_cell(u, *p, if forward { State::Alive } else { State::Dead });
+            }
+        }
+        Edit::Replace { .. } => {}
+    }
+}
+
+fn apply_edit(m: &mut Model, edit: Edit, forward: bool) {
+    match edit {
+        Edit::Replace { before, after } => {
+            let recenter =
+                before.universe != after.universe || before.rule.topology != after.rule.topology;
+            set_scene(m, if forward { *after } else { *before }, recenter);
+        }
+        edit => {
+            apply_cells(&mut m.universe, &edit, forward);
+            m.settings.preset = None;
+        }
+    }
+    m.jump = None;
+}
+
+/// Rule tables go through their palette and colored rules through their
+/// colors, as their states are not just alive or dead. Toggling kills the
+/// dying cells of the other rules, so undoing it has to know their stage.
+fn toggle_edit(m: &Model, p: CartesianP) -> Edit {
+    let rule = &m.settings.rule;
+    let before = get_value(&m.universe, &p);
+    let after = match &rule.kind {
+        RuleKind::Table(t) => cycle(&before, &t.palette),
+        RuleKind::Colored => cycle(&before, &(0..rule.states).collect::<Vec<u8>>()),
+        _ if rule.states > 2 || !matches!(before, State::Alive | State::Dead) => toggle(&before),
+        _ => return Edit::Toggle(p),
+    };
+    Edit::Set { p, before, after }
+}
+
+/// As many rows of the space-time diagram as the camera can show when zoomed
+/// out the most.
+const MAX_ROWS: usize = 200;
+
+fn push_row(rows: &mut VecDeque<Universe>, u: &Universe) {
+    while rows.back().is_some_and(|r| r.age >= u.age) {
+        rows.pop_back();
+    }
+    rows.push_back(u.clone());
+    if rows.len() > MAX_ROWS {
+        rows.pop_front();
+    }
+}
+
+fn advance(m: &mut Model, generations: u64) -> bool {
+    m.history.push(&m.universe);
+    m.jump = None;
+    evolve(m, generations, 0)
+}
+
+/// Elementary rules go one generation at a time, so that the last ones leave
+/// their row for the space-time diagram, `left` being how many more will
+/// follow. Returns false if the evolution couldn't go that far.
+fn evolve(m: &mut Model, generations: u64, left: u64) -> bool {
+    if !matches!(m.settings.rule.kind, RuleKind::Elementary(_)) {
+        return m.evolution.advance(&mut m.universe, &m.settings.rule, generations).is_ok();
+    }
+    for remaining in (left..generations + left).rev() {
+        if remaining < MAX_ROWS as u64 {
+            push_row(&mut m.rows, &m.universe);
+        }
+        iterate(&mut m.universe, &m.settings.rule);
+    }
+    true
+}
+
+/// Reversible block rules go back without the history, one generation at a
+/// time.
+fn step_backward(m: &mut Model, generations: u64) -> bool {
+    let b = match m.settings.rule.kind {
+        RuleKind::Margolus(b) if margolus::inverse(&b).is_some() => b,
+        _ => return false,
+    };
+    if generations > m.universe.age {
+        return false;
+    }
+    for _ in 0..generations {
+        margolus::step_backward(&mut m.universe, &b, &m.settings.rule.topology);
+    }
+    true
+}
+
+/// Turns the edits made to the current generation into a single replacement,
+/// before it changes, so that they can still be undone afterwards.
+fn settle_edits(m: &mut Model) -> bool {
+    let edits = m.edits.take_local();
+    if edits.is_empty() {
+        return false;
+    }
+    let after = get_scene(m);
+    let mut before = after.clone();
+    for edit in edits.iter().rev() {
+        apply_cells(&mut before.universe, edit, false);
+    }
+    m.edits.settle(Edit::Replace { before: Box::new(before), after: Box::new(after) });
+    true
+}
+
+const MAX_STEP: u8 = 62;
+
+/// A jump of many generations, made a slice at a time so the page can run in
+/// between. Any other change to the universe cancels it.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Jump {
+    id: u64,
+    pub done: u64,
+    pub total: u64,
+}
+
+pub enum Command {
+    Start,
+    Stop,
+}
+
+/// A simulation with its own model, listeners and timer, so a page can host
+/// several independent boards.
+#[derive(Clone, Default)]
+pub struct App {
+    model: Rc<RefCell<Model>>,
+    listeners: Rc<RefCell<Vec<Listener>>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct AppInfo {
+    pub preset: Option<String>,
+    pub gap: u8,
+    pub size: u16,
+    pub fps: u16,
+    pub status: Status,
+    pub age: u64,
+    pub rule: String,
+    pub algorithm: Algorithm,
+    pub step: u8,
+}
+
+impl App {
+    fn with_model<R>(&self, f: impl FnOnce(&mut Model) -> R) -> R {
+        f(&mut self.model.borrow_mut())
+    }
+
+    pub fn add_on_change_listener<F>(&self, cb: F)
+    where
+        F: FnMut(Prop) + 'static,
+    {
+        self.listeners.borrow_mut().push(Box::new(cb));
+    }
+
+    fn on_change(&self, param: Prop) {
+        for cb in self.listeners.borrow_mut().iter_mut() {
+            cb(param.clone());
+        }
+    }
+
+    pub fn pause(&self) {
+        self.with_model(|m| {
+            m.settings.status = Status::Paused;
+        });
+        self.on_change(Prop::Status);
+    }
+
+    pub fn resume(&self) {
+        self.with_model(|m| {
+            m.settings.status = Status::Resumed;
+        });
+        self.on_change(Prop::Status);
+    }
+
+    pub fn set_dimension(&self, dim: u16) {
+        self.with_model(|m| {
+            m.settings.render_settings.dim = dim;
+        });
+        self.on_change(Prop::Dim);
+    }
+
+    pub fn set_gap(&self, gap: u8) {
+        self.with_model(|m| {
+            m.settings.render_settings.gap = gap;
+        });
+        self.on_change(Prop::Gap);
+    }
+
+    pub fn set_fps(&self, fps: u16) {
+        self.with_model(|m| {
+            m.settings.fps = fps;
+        });
+        self.on_change(Prop::FPS);
+    }
+
+    /// Presets with a rule of their own bring it along, and the others go back
+    /// to Conway's rule if they can't run under a rule table or an elementary
+    /// rule.
+    pub fn set_preset(&self, preset: String) {
+        if let Some(mut selected_preset) = get_preset(&preset) {
+            let rule_changed = self.with_model(|m| {
+                let current = m.settings.rule.clone();
+                let topology = current.topology;
+                let rule = match get_preset_rule(&preset) {
+                    Some(rule) => Rule { topology, ..rule },
+                    None if matches!(
+                        current.kind,
+                        RuleKind::Table(_) | RuleKind::Elementary(_)
+                    ) =>
+                    {
+                        Rule { topology, ..Rule::default() }
+                    }
+                    None => current.clone(),
+                };
+                fit(&mut selected_preset, &rule.topology);
+                let rule_changed = rule != current;
+                replace_scene(m, Scene::new(selected_preset, rule, Some(preset)), true);
+                rule_changed
+            });
+            self.on_change(Prop::Universe);
+            self.on_change(Prop::Preset);
+            self.on_change(Prop::Cam);
+            if rule_changed {
+                self.on_change(Prop::Rule);
+            }
+            self.on_change(Prop::History);
+        }
+    }
+
+    pub fn single_iteration(&self) {
+        let had_edits = self.with_model(|m| {
+            m.settings.status = Status::Paused;
+            let had_edits = settle_edits(m);
+            advance(m, 1 << m.settings.step);
+            had_edits
+        });
+        self.on_change(Prop::Status);
+        self.on_change(Prop::Universe);
+        if had_edits {
+            self.on_change(Prop::History);
+        }
+    }
+
+    /// Pauses the app when the universe can't go any further.
+    pub fn iterate(&self) {
+        let (had_edits, stopped) = self.with_model(|m| {
+            let had_edits = settle_edits(m);
+            let stopped = !advance(m, 1 << m.settings.step);
+            if stopped {
+                m.settings.status = Status::Paused;
+            }
+            (had_edits, stopped)
+        });
+        if stopped {
+            self.on_change(Prop::Status);
+        }
+        self.on_change(Prop::Universe);
+        if had_edits {
+            self.on_change(Prop::History);
+        }
+    }
+
+    pub fn step_back(&self) -> bool {
+        let (stepped, had_edits) = self.with_model(|m| {
+            m.settings.status = Status::Paused;
+            m.jump = None;
+            let had_edits = settle_edits(m);
+            let stepped = match m.history.pop() {
+                Some(u) => {
+                    m.universe = u;
+                    true
+                }
+                None => step_backward(m, 1),
+            };
+            (stepped, had_edits)
+        });
+        self.on_change(Prop::Status);
+        if stepped {
+            self.on_change(Prop::Universe);
+        }
+        if had_edits {
+            self.on_change(Prop::History);
+        }
+        stepped
+    }
+
+    pub fn seek_to(&self, generation: u64) -> bool {
+        let (sought, had_edits) = self.with_model(|m| {
+            m.settings.status = Status::Paused;
+            m.jump = None;
+            if generation == m.universe.age {
+                return (true, false);
+            }
+            let had_edits = settle_edits(m);
+            if generation < m.universe.age {
+                match m.history.seek(generation) {
+                    Some(u) => m.universe = u,
+                    None if step_backward(m, m.universe.age - generation) => {}
+                    None => return (false, had_edits),
+                }
+            }
+            if generation > m.universe.age {
+                let generations = generation - m.universe.age;
+                return (advance(m, generations), had_edits);
+            }
+            (true, had_edits)
+        });
+        self.on_change(Prop::Status);
+        self.on_change(Prop::Universe);
+        if had_edits {
+            self.on_change(Prop::History);
+        }
+        sought
+    }
+
+    /// Starts a jump of `generations`, pausing the app, which the listeners
+    /// only hear about once it ends.
+    pub fn start_jump(&self, generations: u64) -> Jump {
+        let (jump, was_resumed, had_edits) = self.with_model(|m| {
+            let was_resumed = m.settings.status == Status::Resumed;
+            m.settings.status = Status::Paused;
+            let had_edits = settle_edits(m);
+            m.history.push(&m.universe);
+            m.jumps += 1;
+            let jump = Jump { id: m.jumps, done: 0, total: generations };
+            m.jump = Some(jump);
+            (jump, was_resumed, had_edits)
+        });
+        if was_resumed {
+            self.on_change(Prop::Status);
+        }
+        if had_edits {
+            self.on_change(Prop::History);
+        }
+        jump
+    }
+
+    /// Goes up to `generations` further, telling the listeners if that ends
+    /// the jump. Returns false if the jump was cancelled.
+    pub fn continue_jump(&self, jump: &mut Jump, generations: u64) -> bool {
+        let finished = self.with_model(|m| {
+            if m.jump.map(|j| j.id) != Some(jump.id) {
+                return None;
+            }
+            let generations = generations.min(jump.total - jump.done);
+            jump.done += generations;
+            let went = evolve(m, generations, jump.total - jump.done);
+            m.jump = Some(*jump).filter(|j| went && j.done < j.total);
+            Some(m.jump.is_none())
+        });
+        let Some(finished) = finished else {
+            return false;
+        };
+        if finished {
+            self.end_jump();
+        }
+        true
+    }
+
+    /// Stops the current jump where it got to.
+    pub fn cancel_jump(&self) -> bool {
+        let Some(jump) = self.with_model(|m| m.jump.take()) else {
+            return false;
+        };
+        if jump.done > 0 {
+            self.end_jump();
+        }
+        true
+    }
+
+    fn end_jump(&self) {
+        self.on_change(Prop::Universe);
+    }
+
+    pub fn set_history_budget(&self, cells: usize) {
+        self.with_model(|m| {
+            m.history.set_budget(cells);
+            m.edits.set_budget(cells);
+        });
+        self.on_change(Prop::History);
+    }
+
+    pub fn set_rule(&self, rule: String) -> Result<(), rule::FromStringErr> {
+        self.replace_rule(rule::from_string(&rule)?);
+        Ok(())
+    }
+
+    /// Loads a Golly `.rule` file, keeping the topology of the current rule.
+    pub fn set_rule_table(&self, text: String) -> Result<(), table::FromStringErr> {
+        let topology = self.model.borrow().settings.rule.topology;
+        self.replace_rule(Rule { topology, ..Rule::from(table::from_string(&text)?) });
+        Ok(())
+    }
+
+    /// Switches to the Wolfram rule `n`, keeping the topology of the current
+    /// rule, which must be finite for the odd ones.
+    pub fn set_elementary_rule(&self, n: u8) -> Result<(), rule::FromStringErr> {
+        let topology = self.model.borrow().settings.rule.topology;
+        self.set_rule(format!("W{n}{topology}"))
+    }
+
+    fn replace_rule(&self, rule: Rule) {
+        let topology_changed = self.with_model(|m| {
+            let topology_changed = m.settings.rule.topology != rule.topology;
+            let mut scene = Scene::new(m.universe.clone(), rule, m.settings.preset.clone());
+            fit(&mut scene.universe, &scene.rule.topology);
+            replace_scene(m, scene, topology_changed);
+            topology_changed
+        });
+        self.on_change(Prop::Rule);
+        if topology_changed {
+            self.on_change(Prop::Universe);
+            self.on_change(Prop::Cam);
+        }
+        self.on_change(Prop::History);
+    }
+
+    pub fn set_algorithm(&self, algorithm: Algorithm) {
+        self.with_model(|m| {
+            m.settings.algorithm = algorithm;
+            m.evolution = build_evolution(algorithm);
+        });
+        self.on_change(Prop::Algorithm);
+    }
+
+    pub fn set_step(&self, step: u8) {
+        if step > MAX_STEP {
+            return;
+        }
+        self.with_model(|m| {
+            m.settings.step = step;
+        });
+        self.on_change(Prop::Step);
+    }
+
+    fn load(&self, mut pattern: Pattern) {
+        self.with_model(|m| {
+            let rule = pattern.rule.unwrap_or_else(|| m.settings.rule.clone());
+            fit(&mut pattern.universe, &rule.topology);
+            replace_scene(m, Scene::new(pattern.universe, rule, None), true);
+        });
+        self.on_change(Prop::Universe);
+        self.on_change(Prop::Preset);
+        self.on_change(Prop::Cam);
+        self.on_change(Prop::Rule);
+        self.on_change(Prop::History);
+    }
+
+    pub fn load_rle(&self, text: String) -> Result<(), rle::FromStringErr> {
+        self.load(rle::from_string(&text)?);
+        Ok(())
+    }
+
+    pub fn load_pattern(&self, text: String) -> Result<(), format::FromStringErr> {
+        self.load(format::from_string(&text)?);
+        Ok(())
+    }
+
+    pub fn paste_rle(&self, text: String, at: CartesianP) -> Result<(), rle::FromStringErr> {
+        let pattern = rle::from_string(&text)?;
+        let pasted = self.with_model(|m| {
+            let mut cells: Vec<CartesianP> = pattern
+                .universe
+                .value
+                .keys()
+                .filter_map(|p| {
+                    normalize(&m.settings.rule.topology, &CartesianP::of(p.x + at.x, p.y + at.y))
+                })
+                .filter(|p| !m.universe.value.contains_key(p))
+                .collect();
+            cells.sort_by_key(|p| (p.x, p.y));
+            cells.dedup();
+            if cells.is_empty() {
+                return false;
+            }
+            let edit = Edit::Paste(cells);
+            apply_edit(m, edit.clone(), true);
+            m.edits.push(edit);
+            true
+        });
+        if pasted {
+            self.on_change(Prop::Universe);
+            self.on_change(Prop::Preset);
+            self.on_change(Prop::History);
+        }
+        Ok(())
+    }
+
+    pub fn clear(&self) {
+        self.with_model(|m| {
+            let rule = m.settings.rule.clone();
+            replace_scene(m, Scene::new(Universe::default(), rule, None), false);
+        });
+        self.on_change(Prop::Universe);
+        self.on_change(Prop::Preset);
+        self.on_change(Prop::History);
+    }
+
+    pub fn undo(&self) -> bool {
+        self.revert_or_reapply(false)
+    }
+
+    pub fn redo(&self) -> bool {
+        self.revert_or_reapply(true)
+    }
+
+    fn revert_or_reapply(&self, forward: bool) -> bool {
+        let applied = self.with_model(|m| {
+            let edit = if forward { m.edits.redo() } else { m.edits.undo() };
+            match edit {
+                Some(edit) => {
+                    apply_edit(m, edit, forward);
+                    true
+                }
+                None => false,
+            }
+        });
+        if applied {
+            self.on_change(Prop::Universe);
+            self.on_change(Prop::Preset);
+            self.on_change(Prop::Rule);
+            self.on_change(Prop::Cam);
+            self.on_change(Prop::History);
+        }
+        applied
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.model.borrow().edits.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.model.borrow().edits.can_redo()
+    }
+
+    pub fn analyze(&self, max_generations: u64) -> Option<Analysis> {
+        self.with_model(|m| analyze(&m.universe, &m.settings.rule, max_generations))
+    }
+
+    /// The number of cells of each color, or of each state but the dead one.
+    pub fn get_population(&self) -> Vec<u64> {
+        self.with_model(|m| get_population(&m.universe, m.settings.rule.states))
+    }
+
+    pub fn export_rle(&self) -> String {
+        self.with_model(|m| rle::to_string(&m.universe, &m.settings.rule))
+    }
+
+    pub fn export_pattern(&self, format: Format) -> String {
+        self.with_model(|m| format::to_string(format, &m.universe, &m.settings.rule))
+    }
+
+    pub fn toggle_by_point(&self, p: CartesianP) {
+        self.with_model(|m| {
+            if let Some(p) = normalize(&m.settings.rule.topology, &p) {
+                let edit = toggle_edit(m, p);
+                apply_edit(m, edit.clone(), true);
+                m.edits.push(edit);
+            }
+        });
+        self.on_change(Prop::Universe);
+        self.on_change(Prop::Preset);
+        self.on_change(Prop::History);
+    }
+
+    pub fn toggle_model_cell_by_absolute_point(&self, p: MatrixP) {
+        self.with_model(|m| {
+            let mut p = absolute_point_to_cartesian(&m.settings.render_settings, p);
+            if matches!(m.settings.rule.kind, RuleKind::Elementary(_)) {
+                p.y = 0;
+            }
+            if let Some(p) = normalize(&m.settings.rule.topology, &p) {
+                let edit = toggle_edit(m, p);
+                apply_edit(m, edit.clone(), true);
+                m.edits.push(edit);
+            }
+        });
+        self.on_change(Prop::Universe);
+        self.on_change(Prop::Preset);
+        self.on_change(Prop::History);
+    }
+
+    pub fn zoom_in(&self) {
+        let cam = self.model.borrow().settings.render_settings.cam;
+        if get_length(&cam) <= 2 {
+            return;
+        }
+        self.with_model(|m| {
+            zoom_in(&mut m.settings.render_settings.cam);
+        });
+        self.on_change(Prop::Cam);
+    }
+
+    pub fn zoom_out(&self) {
+        let cam = self.model.borrow().settings.render_settings.cam;
+        if get_length(&cam) >= 200 {
+            return;
+        }
+        self.with_model(|m| {
+            zoom_out(&mut m.settings.render_settings.cam);
+        });
+        self.on_change(Prop::Cam);
+    }
+
+    pub fn zoom_to(&self, new_size: u16) {
+        if !(2..=200).contains(&new_size) {
+            return;
+        }
+        self.with_model(|m| {
+            zoom_to(&mut m.settings.render_settings.cam, new_size);
+        });
+        self.on_change(Prop::Cam);
+    }
+
+    pub fn move_cam(&self, delta: CartesianP) {
+        self.with_model(|m| {
+            move_by(&mut m.settings.render_settings.cam, delta);
+        });
+        self.on_change(Prop::Universe);
+    }
+
+    pub fn get_settings(&self) -> AppInfo {
+        self.with_model(|m| {
+            let s = m.settings.clone();
+            let u = m.universe.clone();
+            AppInfo {
+                preset: s.preset,
+                gap: s.render_settings.gap,
+                size: get_length(&s.render_settings.cam) as u16,
+                fps: s.fps,
+                status: s.status,
+                age: u.age,
+                rule: s.rule.to_string(),
+                algorithm: s.algorithm,
+                step: s.step,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use game_of_life_core::{
+        analysis::Classification, cell::State, evolution::Sparse, geometry::poligon::rect::Rect,
+        universe::get_bounds,
+    };
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_instance() {
+        let app = App::default();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 4,
+                status: Status::Paused,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 0, gap: 0 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+        assert_eq!(app.model.borrow().universe.clone(), get_preset_unsafe("block"));
+        let settings = app.get_settings();
+        assert_eq!(
+            AppInfo {
+                preset: Some(String::from("block")),
+                gap: 0,
+                size: 10,
+                fps: 4,
+                status: Status::Paused,
+                age: 0,
+                rule: String::from("B3/S23"),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            },
+            settings
+        );
+
+        app.pause();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 4,
+                status: Status::Paused,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 0, gap: 0 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.resume();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 4,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 0, gap: 0 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.set_dimension(1080);
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 4,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1080, gap: 0 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.set_gap(2);
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 4,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.set_fps(60);
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.set_preset(String::from("Gaius Julius Caesar"));
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+        app.set_preset(String::from("r_pentomino"));
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("r_pentomino")),
+                fps: 60,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 5, 5), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+        app.set_preset(String::from("block"));
+        app.iterate();
+        let block = get_preset_unsafe("block");
+        assert_eq!(
+            app.model.borrow().universe.clone(),
+            Universe { age: 1, value: block.value.clone(), ..Default::default() }
+        );
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Resumed,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.single_iteration();
+        assert_eq!(
+            app.model.borrow().universe.clone(),
+            Universe { age: 2, value: block.value.clone(), ..Default::default() }
+        );
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Paused,
+                render_settings: RenderSettings { cam: Rect::of(-5, -5, 4, 4), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.zoom_to(4);
+        app.zoom_in();
+        app.zoom_in();
+        app.zoom_in();
+        app.zoom_in();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Paused,
+                render_settings: RenderSettings { cam: Rect::of(-1, -1, 0, 0), dim: 1080, gap: 2 },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+        app.zoom_to(198);
+        app.zoom_out();
+        app.zoom_out();
+        app.zoom_out();
+        app.zoom_out();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Paused,
+                render_settings: RenderSettings {
+                    cam: Rect::of(-100, -100, 99, 99),
+                    dim: 1080,
+                    gap: 2,
+                },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.zoom_to(40);
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Paused,
+                render_settings: RenderSettings {
+                    cam: Rect::of(-20, -20, 19, 19),
+                    dim: 1080,
+                    gap: 2,
+                },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+        app.zoom_in();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Paused,
+                render_settings: RenderSettings {
+                    cam: Rect::of(-19, -19, 18, 18),
+                    dim: 1080,
+                    gap: 2,
+                },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+        app.zoom_out();
+        assert_eq!(
+            app.model.borrow().settings.clone(),
+            AppSettings {
+                preset: Some(String::from("block")),
+                fps: 60,
+                status: Status::Paused,
+                render_settings: RenderSettings {
+                    cam: Rect::of(-20, -20, 19, 19),
+                    dim: 1080,
+                    gap: 2,
+                },
+                rule: Rule::default(),
+                algorithm: Algorithm::Sparse,
+                step: 0,
+            }
+        );
+
+        app.move_cam(CartesianP::of(20, 20));
+        assert_eq!(
+            app.model.borrow().universe.clone(),
+            Universe { age: 2, value: block.value.clone(), ..Default::default() }
+        );
+
+        app.toggle_by_point(CartesianP::of(0, 0));
+        assert_eq!(
+            app.model.borrow().universe.clone(),
+            Universe {
+                age: 2,
+                value: HashMap::from([
+                    (CartesianP::of(-1, -1), State::Alive),
+                    (CartesianP::of(-1, 0), State::Alive),
+                    (CartesianP::of(0, -1), State::Alive),
+                ]),
+                ..Default::default()
+            }
+        );
+
+        assert_eq!(
+            app.set_rule(String::from("B3/S2/3")),
+            Err(rule::FromStringErr::InvalidFormat(rule::InvalidFormatErr))
+        );
+        assert_eq!(app.model.borrow().settings.rule.clone(), Rule::default());
+        assert_eq!(app.set_rule(String::from("/2/3")), Ok(()));
+        assert_eq!(app.get_settings().rule, String::from("B2/S/C3"));
+        assert_eq!(app.set_rule(String::from("23/36")), Ok(()));
+        assert_eq!(app.model.borrow().settings.rule.clone(), Rule::of(&[3, 6], &[2, 3]));
+        assert_eq!(app.get_settings().rule, String::from("B36/S23"));
+
+        assert_eq!(app.export_rle(), "x = 2, y = 2, rule = B36/S23\no$2o!\n");
+        assert_eq!(
+            app.load_rle(String::from("x = 3, y = 3, rule = B3/S23\nbo$2bo$3oq!")),
+            Err(rle::FromStringErr::InvalidCharacter(format::InvalidCharacterErr {
+                line: 2,
+                col: 10
+            }))
+        );
+        assert_eq!(app.load_rle(String::from("x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!")), Ok(()));
+        assert_eq!(
+            app.model.borrow().universe.clone(),
+            Universe::from(HashMap::from([
+                (CartesianP::of(0, 1), State::Alive),
+                (CartesianP::of(1, 0), State::Alive),
+                (CartesianP::of(-1, -1), State::Alive),
+                (CartesianP::of(0, -1), State::Alive),
+                (CartesianP::of(1, -1), State::Alive),
+            ]))
+        );
+        assert_eq!(app.model.borrow().settings.rule.clone(), Rule::default());
+        assert_eq!(app.model.borrow().settings.preset.clone(), None);
+        assert_eq!(app.export_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+
+        app.set_algorithm(Algorithm::HashLife);
+        app.set_step(63);
+        app.set_step(4);
+        assert_eq!(app.model.borrow().settings.algorithm, Algorithm::HashLife);
+        assert_eq!(app.model.borrow().settings.step, 4);
+        app.single_iteration();
+        assert_eq!(app.model.borrow().universe.age, 16);
+        assert_eq!(app.export_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+        assert_eq!(get_bounds(&app.model.borrow().universe.clone()), Some(Rect::of(3, -5, 5, -3)));
+
+        app.set_algorithm(Algorithm::Sparse);
+        app.set_step(0);
+        assert_eq!(app.set_rule(String::from("B3/S23:T6,6")), Ok(()));
+        assert_eq!(app.model.borrow().settings.render_settings.cam, Rect::of(-7, -7, 6, 6));
+        assert_eq!(get_bounds(&app.model.borrow().universe.clone()), Some(Rect::of(-3, -3, -1, 2)));
+        app.set_preset(String::from("glider"));
+        for _ in 0..24 {
+            app.iterate();
+        }
+        let mut glider = get_preset_unsafe("glider");
+        glider.age = 24;
+        assert_eq!(app.model.borrow().universe.clone(), glider);
+        app.toggle_by_point(CartesianP::of(3, 3));
+        assert_eq!(app.model.borrow().universe.value.len(), 6);
+        assert!(app.model.borrow().universe.value.contains_key(&CartesianP::of(-3, -3)));
+        assert_eq!(app.set_rule(String::from("B3/S23:P2,2")), Ok(()));
+        assert_eq!(app.model.borrow().universe.value.len(), 2);
+        app.toggle_by_point(CartesianP::of(5, 5));
+        assert_eq!(app.model.borrow().universe.value.len(), 2);
+    }
+
+    fn glider_at(age: u64) -> Universe {
+        let mut u = get_preset_unsafe("glider");
+        Sparse.advance(&mut u, &Rule::default(), age).unwrap();
+        u
+    }
+
+    #[test]
+    fn test_history() {
+        let app = App::default();
+        app.set_preset(String::from("glider"));
+        assert!(!app.step_back());
+        for _ in 0..10 {
+            app.iterate();
+        }
+        assert!(app.step_back());
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(9));
+        assert_eq!(app.get_settings().status, Status::Paused);
+        assert!(app.seek_to(3));
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(3));
+
+        app.set_step(2);
+        app.iterate();
+        app.iterate();
+        assert_eq!(app.get_settings().age, 11);
+        assert!(app.seek_to(5));
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(5));
+        assert!(app.seek_to(20));
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(20));
+        assert!(app.step_back());
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(5));
+        assert!(app.seek_to(0));
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(0));
+        assert!(!app.step_back());
+
+        app.iterate();
+        app.toggle_by_point(CartesianP::of(10, 10));
+        assert!(app.step_back());
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(0));
+        assert!(app.undo());
+        assert_eq!(app.get_settings().age, 4);
+        assert!(!app.model.borrow().universe.value.contains_key(&CartesianP::of(10, 10)));
+        assert!(app.redo());
+        assert!(app.model.borrow().universe.value.contains_key(&CartesianP::of(10, 10)));
+        assert!(app.seek_to(0));
+        assert_eq!(app.model.borrow().universe.clone(), glider_at(0));
+
+        app.set_history_budget(0);
+        app.iterate();
+        assert!(!app.step_back());
+    }
+
+    #[test]
+    fn test_edits() {
+        let app = App::default();
+        let universe = || app.model.borrow().universe.clone();
+        assert!(!app.can_undo());
+        assert!(!app.can_redo());
+        assert!(!app.undo());
+        assert!(!app.redo());
+
+        app.toggle_by_point(CartesianP::of(5, 5));
+        assert!(app.can_undo());
+        assert!(universe().value.contains_key(&CartesianP::of(5, 5)));
+        assert!(app.undo());
+        assert_eq!(universe(), get_preset_unsafe("block"));
+        assert!(!app.can_undo());
+        assert!(app.can_redo());
+        assert!(app.redo());
+        assert!(universe().value.contains_key(&CartesianP::of(5, 5)));
+        assert!(!app.can_redo());
+
+        app.set_preset(String::from("glider"));
+        assert_eq!(universe(), get_preset_unsafe("glider"));
+        assert!(app.undo());
+        assert_eq!(universe().value.len(), 5);
+        assert!(universe().value.contains_key(&CartesianP::of(5, 5)));
+        assert_eq!(app.get_settings().preset, None);
+        assert!(app.undo());
+        assert_eq!(universe(), get_preset_unsafe("block"));
+        assert_eq!(app.get_settings().preset, None);
+        assert!(app.redo());
+        assert!(app.redo());
+        assert_eq!(universe(), get_preset_unsafe("glider"));
+        assert_eq!(app.get_settings().preset, Some(String::from("glider")));
+
+        app.clear();
+        assert_eq!(universe(), Universe::default());
+        assert_eq!(
+            app.paste_rle(String::from("x = 2, y = 1\n2o!"), CartesianP::of(10, 10)),
+            Ok(())
+        );
+        assert_eq!(universe().value.len(), 2);
+        assert_eq!(
+            app.paste_rle(String::from("x = 3, y = 1\n3o!"), CartesianP::of(10, 10)),
+            Ok(())
+        );
+        assert_eq!(universe().value.len(), 3);
+        assert!(app.undo());
+        assert_eq!(universe().value.len(), 2);
+        assert!(app.undo());
+        assert_eq!(universe(), Universe::default());
+        assert!(app.undo());
+        assert_eq!(universe(), get_preset_unsafe("glider"));
+        assert!(app.redo());
+        assert!(app.redo());
+        assert!(app.redo());
+        assert_eq!(universe().value.len(), 3);
+
+        assert_eq!(app.set_rule(String::from("B36/S23:P4,4")), Ok(()));
+        assert_eq!(universe().value.len(), 0);
+        assert!(app.undo());
+        assert_eq!(universe().value.len(), 3);
+        assert_eq!(app.get_settings().rule, String::from("B3/S23"));
+        assert!(app.redo());
+        assert_eq!(app.get_settings().rule, String::from("B36/S23:P4,4"));
+        assert!(app.undo());
+
+        app.iterate();
+        assert!(app.can_undo());
+        assert!(app.can_redo());
+        assert!(app.undo());
+        assert_eq!(universe(), Universe::default());
+        assert!(app.redo());
+        assert_eq!(universe().value.len(), 3);
+        assert_eq!(universe().age, 0);
+        assert!(app.redo());
+        assert_eq!(app.get_settings().rule, String::from("B36/S23:P4,4"));
+        assert!(app.undo());
+        assert!(app.undo());
+
+        app.set_preset(String::from("glider"));
+        app.resume();
+        app.clear();
+        app.iterate();
+        assert!(app.undo());
+        assert_eq!(universe(), get_preset_unsafe("glider"));
+        app.iterate();
+        app.iterate();
+        assert!(app.step_back());
+        assert_eq!(universe(), glider_at(1));
+        assert!(app.can_undo());
+    }
+
+    #[test]
+    fn test_independent_apps() {
+        let a = App::default();
+        let b = App::default();
+        let props = Rc::new(RefCell::new(Vec::new()));
+        a.add_on_change_listener({
+            let props = props.clone();
+            move |prop| props.borrow_mut().push(prop)
+        });
+        a.set_preset(String::from("glider"));
+        a.iterate();
+        assert_eq!(a.get_settings().preset, Some(String::from("glider")));
+        assert_eq!(a.get_settings().age, 1);
+        assert_eq!(b.get_settings().preset, Some(String::from("block")));
+        assert_eq!(b.get_settings().age, 0);
+        assert_eq!(props.borrow().len(), 5);
+        b.iterate();
+        assert_eq!(props.borrow().len(), 5);
+
+        let weak = Rc::downgrade(&a.model);
+        drop(a);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_analyze() {
+        let app = App::default();
+        assert_eq!(app.analyze(10).map(|a| a.classification), Some(Classification::StillLife));
+        app.set_preset(String::from("glider"));
+        assert_eq!(app.analyze(3), None);
+        let glider = app.analyze(10).unwrap();
+        assert_eq!(glider.classification, Classification::Spaceship);
+        assert_eq!(glider.period, 4);
+        assert_eq!(app.get_settings().age, 0);
+    }
+
+    #[test]
+    fn test_load_pattern() {
+        let app = App::default();
+        assert_eq!(app.export_pattern(Format::Cells), "OO\nOO\n");
+        assert_eq!(
+            app.load_pattern(String::from("!Name: Glider\n.O.\n..O\nO0O\n")),
+            Err(format::FromStringErr::Cells(format::cells::FromStringErr::InvalidCharacter(
+                format::InvalidCharacterErr { line: 4, col: 2 }
+            )))
+        );
+        assert_eq!(app.model.borrow().universe.clone(), get_preset_unsafe("block"));
+        assert_eq!(app.load_pattern(String::from("!Name: Glider\n.O.\n..O\nOOO\n")), Ok(()));
+        assert_eq!(app.export_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+        assert_eq!(app.model.borrow().settings.preset.clone(), None);
+        assert_eq!(app.load_pattern(String::from("#Life 1.05\n#R 23/36\n#P 0 0\n**\n")), Ok(()));
+        assert_eq!(app.model.borrow().settings.rule.clone(), Rule::of(&[3, 6], &[2, 3]));
+        assert_eq!(app.export_pattern(Format::Life106), "#Life 1.06\n0 0\n1 0\n");
+        assert!(app.undo());
+        assert_eq!(app.model.borrow().settings.rule.clone(), Rule::default());
+    }
+
+    #[test]
+    fn test_set_rule_table() {
+        let app = App::default();
+        assert_eq!(app.set_rule(String::from("B3/S23:T8,8")), Ok(()));
+        assert_eq!(
+            app.set_rule_table(String::from("@RULE Empty\n")),
+            Err(table::FromStringErr::MissingSection(table::MissingSectionErr {
+                section: "TABLE"
+            }))
+        );
+        assert_eq!(app.get_settings().rule, "B3/S23:T8,8");
+        let grow = "@RULE Grow\n@TABLE\nn_states:2\nneighborhood:vonNeumann\nsymmetries:rotate4\n0,1,0,0,0,1\n";
+        assert_eq!(app.set_rule_table(String::from(grow)), Ok(()));
+        assert_eq!(app.get_settings().rule, "Grow:T8,8");
+        assert!(app.undo());
+        assert_eq!(app.get_settings().rule, "B3/S23:T8,8");
+        assert!(app.redo());
+        app.iterate();
+        assert_eq!(app.model.borrow().universe.value.len(), 12);
+    }
+
+    #[test]
+    fn test_wireworld() {
+        let app = App::default();
+        app.set_preset(String::from("wireworld_clock"));
+        assert_eq!(app.get_settings().rule, "WireWorld");
+        app.toggle_by_point(CartesianP::of(0, 5));
+        assert_eq!(get_value(&app.model.borrow().universe, &CartesianP::of(0, 5)), State::Dying(2));
+        app.toggle_by_point(CartesianP::of(0, 5));
+        app.toggle_by_point(CartesianP::of(0, 5));
+        assert_eq!(get_value(&app.model.borrow().universe, &CartesianP::of(0, 5)), State::Dying(1));
+        assert!(app.undo());
+        assert_eq!(get_value(&app.model.borrow().universe, &CartesianP::of(0, 5)), State::Alive);
+        assert!(app.undo());
+        assert!(app.undo());
+        assert_eq!(app.model.borrow().universe.clone(), get_preset_unsafe("wireworld_clock"));
+        app.set_preset(String::from("glider"));
+        assert_eq!(app.get_settings().rule, "B3/S23");
+        assert_eq!(app.set_rule(String::from("B36/S23")), Ok(()));
+        app.set_preset(String::from("block"));
+        assert_eq!(app.get_settings().rule, "B36/S23");
+    }
+
+    #[test]
+    fn test_margolus_rule() {
+        let app = App::default();
+        assert_eq!(app.set_rule(String::from("Critters")), Ok(()));
+        app.set_preset(String::from("glider"));
+        assert_eq!(app.get_settings().rule, "Critters");
+        app.set_step(3);
+        app.iterate();
+        app.iterate();
+        app.set_history_budget(0);
+        assert_eq!(app.get_settings().age, 16);
+        assert!(app.step_back());
+        assert_eq!(app.get_settings().age, 15);
+        assert!(app.seek_to(0));
+        assert_eq!(app.model.borrow().universe, get_preset_unsafe("glider"));
+        assert!(!app.step_back());
+        app.iterate();
+        assert_eq!(app.set_rule(String::from("MS,D0;1;2;3;4;5;6;7;8;9;10;11;12;13;14;14")), Ok(()));
+        assert!(!app.step_back());
+        assert!(!app.seek_to(0));
+    }
+
+    #[test]
+    fn test_colored_rule() {
+        let app = App::default();
+        assert_eq!(app.get_population(), vec![4]);
+        assert_eq!(app.load_rle(String::from("x = 3, y = 1, rule = Immigration\nABA!")), Ok(()));
+        assert_eq!(app.get_settings().rule, "Immigration");
+        assert_eq!(app.get_population(), vec![2, 1]);
+        app.iterate();
+        assert_eq!(get_value(&app.model.borrow().universe, &CartesianP::of(0, 1)), State::Alive);
+        assert_eq!(get_value(&app.model.borrow().universe, &CartesianP::of(0, 0)), State::Dying(1));
+        assert_eq!(app.get_population(), vec![2, 1]);
+        app.toggle_by_point(CartesianP::of(5, 5));
+        app.toggle_by_point(CartesianP::of(5, 5));
+        assert_eq!(get_value(&app.model.borrow().universe, &CartesianP::of(5, 5)), State::Dying(1));
+        assert_eq!(app.get_population(), vec![2, 2]);
+        app.toggle_by_point(CartesianP::of(5, 5));
+        assert_eq!(app.get_population(), vec![2, 1]);
+        assert_eq!(app.set_rule(String::from("QuadLife")), Ok(()));
+        assert_eq!(app.get_population(), vec![2, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_generations_rule() {
+        let app = App::default();
+        let p = CartesianP::of(0, 0);
+        let value = || get_value(&app.model.borrow().universe, &p);
+        app.clear();
+        assert_eq!(app.set_rule(String::from("B2/S/C3")), Ok(()));
+        app.toggle_by_point(p);
+        app.iterate();
+        assert_eq!(value(), State::Dying(1));
+        app.toggle_by_point(p);
+        assert_eq!(value(), State::Dead);
+        assert!(app.undo());
+        assert_eq!(value(), State::Dying(1));
+        assert!(app.redo());
+        assert_eq!(value(), State::Dead);
+    }
+
+    #[test]
+    fn test_elementary_rule() {
+        let app = App::default();
+        assert_eq!(
+            app.set_elementary_rule(1),
+            Err(rule::FromStringErr::UnsupportedB0(rule::UnsupportedB0Err))
+        );
+        assert_eq!(app.set_elementary_rule(30), Ok(()));
+        assert_eq!(app.get_settings().rule, "W30");
+        app.clear();
+        app.set_dimension(100);
+        app.toggle_model_cell_by_absolute_point(MatrixP { row: 75, col: 55 });
+        assert_eq!(
+            app.model.borrow().universe.value,
+            HashMap::from([(CartesianP::of(0, 0), State::Alive)])
+        );
+        app.set_step(2);
+        app.iterate();
+        app.iterate();
+        let ages = |app: &App| app.model.borrow().rows.iter().map(|r| r.age).collect::<Vec<u64>>();
+        assert_eq!(ages(&app), (0..8).collect::<Vec<u64>>());
+        assert_eq!(app.model.borrow().rows[1].value.len(), 3);
+        assert_eq!(app.get_settings().age, 8);
+        assert!(app.step_back());
+        app.single_iteration();
+        assert_eq!(ages(&app), (0..8).collect::<Vec<u64>>());
+        assert!(app.seek_to(1000));
+        assert_eq!(ages(&app), (800..1000).collect::<Vec<u64>>());
+        app.set_preset(String::from("glider"));
+        assert_eq!(app.get_settings().rule, "B3/S23");
+        assert!(app.model.borrow().rows.is_empty());
+        assert_eq!(app.set_rule(String::from("B3/S23:T20,20")), Ok(()));
+        assert_eq!(app.set_elementary_rule(1), Ok(()));
+        assert_eq!(app.get_settings().rule, "W1:T20,20");
+    }
+
+    #[test]
+    fn test_jump() {
+        let app = App::default();
+        let props = Rc::new(RefCell::new(Vec::new()));
+        app.add_on_change_listener({
+            let props = props.clone();
+            move |prop| props.borrow_mut().push(prop)
+        });
+        app.set_preset(String::from("glider"));
+        app.resume();
+        props.borrow_mut().clear();
+        let mut jump = app.start_jump(100);
+        assert_eq!(app.get_settings().status, Status::Paused);
+        assert!(matches!(props.borrow().as_slice(), [Prop::Status]));
+        assert!(app.continue_jump(&mut jump, 30));
+        assert!(app.continue_jump(&mut jump, 30));
+        assert_eq!(jump.done, 60);
+        assert_eq!(app.get_settings().age, 60);
+        assert_eq!(props.borrow().len(), 1);
+        assert!(app.continue_jump(&mut jump, 60));
+        assert_eq!(jump.done, 100);
+        assert!(matches!(props.borrow().as_slice(), [Prop::Status, Prop::Universe]));
+        let mut expected = get_preset_unsafe("glider");
+        Sparse.advance(&mut expected, &Rule::default(), 100).unwrap();
+        assert_eq!(app.model.borrow().universe, expected);
+        assert!(!app.continue_jump(&mut jump, 1));
+        assert!(!app.cancel_jump());
+        assert!(app.step_back());
+        assert_eq!(app.get_settings().age, 0);
+
+        props.borrow_mut().clear();
+        let mut jump = app.start_jump(1000);
+        assert!(app.continue_jump(&mut jump, 10));
+        assert!(app.cancel_jump());
+        assert!(!app.continue_jump(&mut jump, 10));
+        assert_eq!(app.get_settings().age, 10);
+        assert!(matches!(props.borrow().as_slice(), [Prop::Universe]));
+
+        let mut jump = app.start_jump(1000);
+        app.iterate();
+        assert!(!app.continue_jump(&mut jump, 10));
+        let mut first = app.start_jump(1000);
+        let mut second = app.start_jump(5);
+        assert!(!app.continue_jump(&mut first, 10));
+        assert!(app.continue_jump(&mut second, 10));
+        assert_eq!(second.done, 5);
+        assert_eq!(app.get_settings().age, 16);
+    }
+
+    #[test]
+    fn test_jump_elementary_rule() {
+        let app = App::default();
+        assert_eq!(app.set_elementary_rule(30), Ok(()));
+        let mut jump = app.start_jump(500);
+        while jump.done < jump.total {
+            assert!(app.continue_jump(&mut jump, 7));
+        }
+        let ages = app.model.borrow().rows.iter().map(|r| r.age).collect::<Vec<u64>>();
+        assert_eq!(ages, (300..500).collect::<Vec<u64>>());
+    }
+}
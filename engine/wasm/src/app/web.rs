@@ -0,0 +1,224 @@
+use gloo_timers::callback::{Interval, Timeout};
+use js_sys::Date;
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+use web_sys::CanvasRenderingContext2d;
+
+use game_of_life_core::{
+    cell::State,
+    geometry::poligon::rect::RectF64,
+    render::{get_space_time_to_render, get_topology_to_render, get_values_to_render_with},
+    rule::{Rule, RuleKind},
+};
+
+use super::{App, Jump, Listener, Model, Prop, Status};
+
+#[derive(Clone)]
+pub struct Holder {
+    context: CanvasRenderingContext2d,
+}
+
+impl Holder {
+    fn draw_square(&self, r: RectF64, color: String) {
+        if self.context.is_undefined() || self.context.is_null() {
+            return;
+        }
+        self.context.set_fill_style_str(&color);
+        self.context.fill_rect(r.x1, r.y1, r.x2 - r.x1, r.y2 - r.y1);
+    }
+}
+
+unsafe impl Send for Holder {}
+
+fn fps_to_mili(fps: u16) -> u16 {
+    1000 / fps
+}
+
+/// How long a slice of a jump keeps the page busy.
+const SLICE_MILI: f64 = 16.0;
+
+const DEAD_COLOR: &str = "#dbdbdb";
+const ALIVE_COLOR: &str = "#2e2e2e";
+const OUT_OF_BOUNDS_COLOR: &str = "#9e9e9e";
+const ALIVE_SHADE: u16 = 0x2e;
+const DEAD_SHADE: u16 = 0xdb;
+const FACTION_COLORS: [&str; 4] = ["#d32f2f", "#1976d2", "#388e3c", "#f9a825"];
+
+/// The colors of a rule table come first, then the factions of a colored
+/// rule, then dying cells fade from the alive color to the dead one as they
+/// age.
+fn state_color(state: State, rule: &Rule) -> String {
+    let table_color = match &rule.kind {
+        RuleKind::Table(t) => t.colors.get(&u8::from(state)),
+        _ => None,
+    };
+    if let Some([r, g, b]) = table_color {
+        return format!("#{r:02x}{g:02x}{b:02x}");
+    }
+    let faction = usize::from(u8::from(state)).checked_sub(1).and_then(|i| FACTION_COLORS.get(i));
+    if let (RuleKind::Colored, Some(color)) = (&rule.kind, faction) {
+        return String::from(*color);
+    }
+    let states = rule.states;
+    match state {
+        State::Dying(k) if states > 2 => {
+            let shade =
+                ALIVE_SHADE + (DEAD_SHADE - ALIVE_SHADE) * u16::from(k) / u16::from(states - 1);
+            format!("#{shade:02x}{shade:02x}{shade:02x}")
+        }
+        State::Dead => String::from(DEAD_COLOR),
+        _ => String::from(ALIVE_COLOR),
+    }
+}
+
+/// Held by the timer and the listeners, so they don't keep a dropped app alive.
+#[derive(Clone)]
+struct WeakApp {
+    model: Weak<RefCell<Model>>,
+    listeners: Weak<RefCell<Vec<Listener>>>,
+}
+
+impl WeakApp {
+    fn upgrade(&self) -> Option<App> {
+        Some(App { model: self.model.upgrade()?, listeners: self.listeners.upgrade()? })
+    }
+}
+
+/// Runs the jump until the slice is over, with batches of generations that
+/// grow while they are quick and shrink when they are slow, then lets the page
+/// run before the next slice.
+fn run_slice(
+    weak: WeakApp,
+    mut jump: Jump,
+    mut batch: u64,
+    mut on_progress: impl FnMut(u64, u64) + 'static,
+) {
+    Timeout::new(0, move || {
+        let Some(app) = weak.upgrade() else {
+            return;
+        };
+        let start = Date::now();
+        while jump.done < jump.total && Date::now() - start < SLICE_MILI {
+            let batch_start = Date::now();
+            if !app.continue_jump(&mut jump, batch) {
+                return;
+            }
+            batch = if Date::now() - batch_start < SLICE_MILI / 4.0 {
+                batch.saturating_mul(2)
+            } else {
+                (batch / 2).max(1)
+            };
+        }
+        on_progress(jump.done, jump.total);
+        if jump.done < jump.total {
+            run_slice(weak, jump, batch, on_progress);
+        }
+    })
+    .forget();
+}
+
+impl App {
+    /// Jumps `generations` ahead in slices, calling `on_progress` with the
+    /// generations done and the total after each one.
+    pub fn advance_by(&self, generations: u64, on_progress: impl FnMut(u64, u64) + 'static) {
+        let jump = self.start_jump(generations);
+        run_slice(self.downgrade(), jump, 1, on_progress);
+    }
+
+    fn render(&self) {
+        let (settings, holder) = self.with_model(|m| (m.settings.clone(), m.holder.clone()));
+        if settings.render_settings.dim == 0 {
+            return;
+        }
+        if let Some(holder) = holder {
+            let bg = RectF64 {
+                x1: 0.0,
+                y1: 0.0,
+                x2: f64::from(settings.render_settings.dim),
+                y2: f64::from(settings.render_settings.dim),
+            };
+            let dead = state_color(State::Dead, &settings.rule);
+            let area = get_topology_to_render(&settings.rule.topology, &settings.render_settings);
+            // The rows of a space-time diagram are generations, so only the
+            // width of the topology shows.
+            let area = match settings.rule.kind {
+                RuleKind::Elementary(_) => area.map(|a| RectF64 { y1: bg.y1, y2: bg.y2, ..a }),
+                _ => area,
+            };
+            match area {
+                Some(area) => {
+                    holder.draw_square(bg, String::from(OUT_OF_BOUNDS_COLOR));
+                    holder.draw_square(area, dead);
+                }
+                None => holder.draw_square(bg, dead),
+            }
+            let values_to_render = self.with_model(|m| match settings.rule.kind {
+                RuleKind::Elementary(_) => get_space_time_to_render(
+                    m.rows.make_contiguous(),
+                    &m.universe,
+                    &settings.render_settings,
+                ),
+                _ => get_values_to_render_with(
+                    &m.universe,
+                    &settings.render_settings,
+                    &settings.rule.neighborhood,
+                ),
+            });
+            for (sq, state) in values_to_render {
+                holder.draw_square(sq, state_color(state, &settings.rule));
+            }
+        }
+    }
+
+    fn downgrade(&self) -> WeakApp {
+        WeakApp { model: Rc::downgrade(&self.model), listeners: Rc::downgrade(&self.listeners) }
+    }
+
+    pub fn init(&self, context: CanvasRenderingContext2d) {
+        self.model.borrow_mut().holder = Some(Holder { context });
+        let mut interval: Option<Interval> = None;
+        let weak = self.downgrade();
+        self.add_on_change_listener({
+            move |prop| {
+                let Some(app) = weak.upgrade() else {
+                    return;
+                };
+                let status = app.model.borrow().settings.status.clone();
+                match status {
+                    Status::Resumed => match prop {
+                        Prop::Status | Prop::FPS => {
+                            if interval.is_none() {
+                                if let Some(i) = interval.take() {
+                                    i.cancel();
+                                }
+                            }
+                            let fps = app.model.borrow().settings.fps;
+                            let weak = weak.clone();
+                            interval = Some(Interval::new(u32::from(fps_to_mili(fps)), move || {
+                                if let Some(app) = weak.upgrade() {
+                                    app.iterate();
+                                    app.render();
+                                }
+                            }))
+                        }
+                        _ => {}
+                    },
+                    Status::Paused => match prop {
+                        Prop::Gap | Prop::Dim | Prop::Universe | Prop::Cam => {
+                            app.render();
+                        }
+                        Prop::Status => {
+                            if let Some(i) = interval.take() {
+                                i.cancel();
+                            }
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        });
+        self.render();
+    }
+}
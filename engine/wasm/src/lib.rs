@@ -0,0 +1,451 @@
+pub mod app;
+
+use crate::app::{App, Prop, Status};
+use game_of_life_core::{
+    analysis::Classification,
+    evolution::Algorithm,
+    format::{detect_format, Format},
+    geometry::coordinate::{CartesianP, MatrixP},
+    preset::get_preset_groups,
+};
+use js_sys::Function;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "web")]
+use web_sys::CanvasRenderingContext2d;
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct EngineCartesianPoint {
+    pub x: i64,
+    pub y: i64,
+}
+
+#[wasm_bindgen]
+impl EngineCartesianPoint {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: i64, y: i64) -> EngineCartesianPoint {
+        EngineCartesianPoint { x, y }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone)]
+pub struct EngineMatrixPoint {
+    pub row: u64,
+    pub col: u64,
+}
+
+#[wasm_bindgen]
+impl EngineMatrixPoint {
+    #[wasm_bindgen(constructor)]
+    pub fn new(row: u64, col: u64) -> EngineMatrixPoint {
+        EngineMatrixPoint { row, col }
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum EngineStatus {
+    Resumed,
+    Paused,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum EngineAlgorithm {
+    Sparse,
+    HashLife,
+    Tiled,
+}
+
+/// What changed, passed to the change listeners.
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum EngineProp {
+    Universe,
+    Preset,
+    Gap,
+    FPS,
+    Status,
+    Dim,
+    Cam,
+    Rule,
+    Algorithm,
+    Step,
+    History,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum EngineFormat {
+    Rle,
+    Cells,
+    Life105,
+    Life106,
+    Macrocell,
+}
+
+#[wasm_bindgen]
+#[derive(Clone, Copy)]
+pub enum EngineClassification {
+    Empty,
+    StillLife,
+    Oscillator,
+    Spaceship,
+}
+
+#[wasm_bindgen]
+pub struct EngineAnalysis {
+    pub classification: EngineClassification,
+    pub start: u64,
+    pub period: u64,
+    pub dx: i64,
+    pub dy: i64,
+    speed: Option<String>,
+}
+
+#[wasm_bindgen]
+impl EngineAnalysis {
+    #[wasm_bindgen(getter)]
+    pub fn speed(&self) -> Option<String> {
+        self.speed.clone()
+    }
+}
+
+#[wasm_bindgen]
+pub struct EngineInfo {
+    preset: Option<String>,
+    pub gap: u8,
+    pub size: u16,
+    pub fps: u16,
+    pub status: EngineStatus,
+    pub age: u64,
+    rule: String,
+    pub algorithm: EngineAlgorithm,
+    pub step: u8,
+}
+
+#[wasm_bindgen]
+impl EngineInfo {
+    #[wasm_bindgen(getter)]
+    pub fn preset(&self) -> Option<String> {
+        self.preset.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn rule(&self) -> String {
+        self.rule.clone()
+    }
+}
+
+#[derive(Serialize)]
+pub struct EnginePresetInfo {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct EnginePresetGroup {
+    pub info: EnginePresetInfo,
+    pub items: Vec<EnginePresetInfo>,
+}
+
+/// An independent board, owning its model, listeners and timer.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct Engine {
+    app: App,
+}
+
+#[wasm_bindgen]
+impl Engine {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Engine {
+        Engine::default()
+    }
+
+    #[wasm_bindgen(js_name = "pause")]
+    pub fn pause(&self) {
+        self.app.pause();
+    }
+
+    #[wasm_bindgen(js_name = "resume")]
+    pub fn resume(&self) {
+        self.app.resume();
+    }
+
+    #[wasm_bindgen(js_name = "setDimension")]
+    pub fn set_dimension(&self, dim: u16) {
+        self.app.set_dimension(dim);
+    }
+
+    #[wasm_bindgen(js_name = "setGap")]
+    pub fn set_gap(&self, gap: u8) {
+        self.app.set_gap(gap);
+    }
+
+    #[wasm_bindgen(js_name = "setFPS")]
+    pub fn set_fps(&self, fps: u16) {
+        self.app.set_fps(fps);
+    }
+
+    #[wasm_bindgen(js_name = "setPreset")]
+    pub fn set_preset(&self, preset: String) {
+        self.app.set_preset(preset);
+    }
+
+    #[wasm_bindgen(js_name = "setRule")]
+    pub fn set_rule(&self, rule: String) -> Result<(), JsValue> {
+        self.app.set_rule(rule).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "setRuleTable")]
+    pub fn set_rule_table(&self, text: String) -> Result<(), JsValue> {
+        self.app.set_rule_table(text).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "setElementaryRule")]
+    pub fn set_elementary_rule(&self, n: u8) -> Result<(), JsValue> {
+        self.app.set_elementary_rule(n).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "setAlgorithm")]
+    pub fn set_algorithm(&self, algorithm: EngineAlgorithm) {
+        self.app.set_algorithm(match algorithm {
+            EngineAlgorithm::Sparse => Algorithm::Sparse,
+            EngineAlgorithm::HashLife => Algorithm::HashLife,
+            EngineAlgorithm::Tiled => Algorithm::Tiled,
+        });
+    }
+
+    #[wasm_bindgen(js_name = "setStep")]
+    pub fn set_step(&self, step: u8) {
+        self.app.set_step(step);
+    }
+
+    #[wasm_bindgen(js_name = "loadRLE")]
+    pub fn load_rle(&self, text: String) -> Result<(), JsValue> {
+        self.app.load_rle(text).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "loadPattern")]
+    pub fn load_pattern(&self, text: String) -> Result<(), JsValue> {
+        self.app.load_pattern(text).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "exportPattern")]
+    pub fn export_pattern(&self, format: EngineFormat) -> String {
+        self.app.export_pattern(match format {
+            EngineFormat::Rle => Format::Rle,
+            EngineFormat::Cells => Format::Cells,
+            EngineFormat::Life105 => Format::Life105,
+            EngineFormat::Life106 => Format::Life106,
+            EngineFormat::Macrocell => Format::Macrocell,
+        })
+    }
+
+    #[wasm_bindgen(js_name = "analyze")]
+    pub fn analyze(&self, max_generations: u32) -> Option<EngineAnalysis> {
+        self.app.analyze(u64::from(max_generations)).map(|a| EngineAnalysis {
+            classification: match a.classification {
+                Classification::Empty => EngineClassification::Empty,
+                Classification::StillLife => EngineClassification::StillLife,
+                Classification::Oscillator => EngineClassification::Oscillator,
+                Classification::Spaceship => EngineClassification::Spaceship,
+            },
+            start: a.start,
+            period: a.period,
+            dx: a.dx,
+            dy: a.dy,
+            speed: a.speed(),
+        })
+    }
+
+    #[wasm_bindgen(js_name = "getPopulation")]
+    pub fn get_population(&self) -> Vec<u64> {
+        self.app.get_population()
+    }
+
+    #[wasm_bindgen(js_name = "exportRLE")]
+    pub fn export_rle(&self) -> String {
+        self.app.export_rle()
+    }
+
+    #[wasm_bindgen(js_name = "pasteRLE")]
+    pub fn paste_rle(&self, text: String, at: EngineCartesianPoint) -> Result<(), JsValue> {
+        self.app
+            .paste_rle(text, CartesianP { x: at.x, y: at.y })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "clear")]
+    pub fn clear(&self) {
+        self.app.clear();
+    }
+
+    #[wasm_bindgen(js_name = "undo")]
+    pub fn undo(&self) -> bool {
+        self.app.undo()
+    }
+
+    #[wasm_bindgen(js_name = "redo")]
+    pub fn redo(&self) -> bool {
+        self.app.redo()
+    }
+
+    #[wasm_bindgen(js_name = "canUndo")]
+    pub fn can_undo(&self) -> bool {
+        self.app.can_undo()
+    }
+
+    #[wasm_bindgen(js_name = "canRedo")]
+    pub fn can_redo(&self) -> bool {
+        self.app.can_redo()
+    }
+
+    #[wasm_bindgen(js_name = "singleIteration")]
+    pub fn single_iteration(&self) {
+        self.app.single_iteration();
+    }
+
+    #[wasm_bindgen(js_name = "stepBack")]
+    pub fn step_back(&self) -> bool {
+        self.app.step_back()
+    }
+
+    #[wasm_bindgen(js_name = "seekTo")]
+    pub fn seek_to(&self, generation: u64) -> bool {
+        self.app.seek_to(generation)
+    }
+
+    #[wasm_bindgen(js_name = "cancelAdvance")]
+    pub fn cancel_advance(&self) -> bool {
+        self.app.cancel_jump()
+    }
+
+    #[wasm_bindgen(js_name = "setHistoryBudget")]
+    pub fn set_history_budget(&self, cells: u32) {
+        self.app.set_history_budget(cells as usize);
+    }
+
+    #[wasm_bindgen(js_name = "toggle")]
+    pub fn toggle(&self, point: EngineMatrixPoint) {
+        self.app.toggle_model_cell_by_absolute_point(MatrixP { row: point.row, col: point.col });
+    }
+
+    #[wasm_bindgen(js_name = "zoomIn")]
+    pub fn zoom_in(&self) {
+        self.app.zoom_in();
+    }
+
+    #[wasm_bindgen(js_name = "zoomOut")]
+    pub fn zoom_out(&self) {
+        self.app.zoom_out();
+    }
+
+    #[wasm_bindgen(js_name = "zoomTo")]
+    pub fn zoom_to(&self, new_size: u16) {
+        self.app.zoom_to(new_size);
+    }
+
+    #[wasm_bindgen(js_name = "moveBy")]
+    pub fn move_by(&self, delta: EngineCartesianPoint) {
+        self.app.move_cam(CartesianP { x: delta.x, y: delta.y });
+    }
+
+    #[wasm_bindgen(js_name = "getSettings")]
+    pub fn get_settings(&self) -> EngineInfo {
+        let settings = self.app.get_settings();
+        EngineInfo {
+            preset: settings.preset,
+            size: settings.size,
+            fps: settings.fps,
+            gap: settings.gap,
+            status: match settings.status {
+                Status::Paused => EngineStatus::Paused,
+                Status::Resumed => EngineStatus::Resumed,
+            },
+            age: settings.age,
+            rule: settings.rule,
+            algorithm: match settings.algorithm {
+                Algorithm::Sparse => EngineAlgorithm::Sparse,
+                Algorithm::HashLife => EngineAlgorithm::HashLife,
+                Algorithm::Tiled => EngineAlgorithm::Tiled,
+            },
+            step: settings.step,
+        }
+    }
+
+    #[wasm_bindgen(js_name = "addOnChangeListener")]
+    pub fn add_on_change_listener(&self, cb: Function) {
+        self.app.add_on_change_listener(move |prop| {
+            let prop = match prop {
+                Prop::Universe => EngineProp::Universe,
+                Prop::Preset => EngineProp::Preset,
+                Prop::Gap => EngineProp::Gap,
+                Prop::FPS => EngineProp::FPS,
+                Prop::Status => EngineProp::Status,
+                Prop::Dim => EngineProp::Dim,
+                Prop::Cam => EngineProp::Cam,
+                Prop::Rule => EngineProp::Rule,
+                Prop::Algorithm => EngineProp::Algorithm,
+                Prop::Step => EngineProp::Step,
+                Prop::History => EngineProp::History,
+            };
+            cb.call1(&JsValue::null(), &JsValue::from(prop)).unwrap();
+        });
+    }
+}
+
+#[cfg(feature = "web")]
+#[wasm_bindgen]
+impl Engine {
+    #[wasm_bindgen(js_name = "init")]
+    pub fn init(&self, value: CanvasRenderingContext2d) {
+        self.app.init(value);
+    }
+
+    /// Evolves `generations` generations without rendering until the end,
+    /// calling `onProgress(done, total)` between slices.
+    #[wasm_bindgen(js_name = "advance")]
+    pub fn advance(&self, generations: u64, on_progress: Function) {
+        self.app.advance_by(generations, move |done, total| {
+            let (done, total) = (JsValue::from_f64(done as f64), JsValue::from_f64(total as f64));
+            on_progress.call2(&JsValue::null(), &done, &total).unwrap();
+        });
+    }
+}
+
+#[wasm_bindgen(js_name = "engineDetectFormat")]
+pub fn main_detect_format(text: String) -> EngineFormat {
+    match detect_format(&text) {
+        Format::Rle => EngineFormat::Rle,
+        Format::Cells => EngineFormat::Cells,
+        Format::Life105 => EngineFormat::Life105,
+        Format::Life106 => EngineFormat::Life106,
+        Format::Macrocell => EngineFormat::Macrocell,
+    }
+}
+
+#[wasm_bindgen(js_name = "engineGetPresets")]
+pub fn main_get_presets() -> JsValue {
+    let groups: Vec<EnginePresetGroup> = get_preset_groups()
+        .iter()
+        .map(|g| EnginePresetGroup {
+            info: EnginePresetInfo { id: g.info.id.clone(), name: g.info.name.clone() },
+            items: g
+                .sub_groups
+                .iter()
+                .flat_map(|sub_group| {
+                    sub_group
+                        .items
+                        .iter()
+                        .map(|i| EnginePresetInfo { id: i.id.clone(), name: i.name.clone() })
+                        .collect::<Vec<EnginePresetInfo>>()
+                })
+                .collect(),
+        })
+        .collect();
+    serde_wasm_bindgen::to_value(&groups).unwrap()
+}
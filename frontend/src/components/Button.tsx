@@ -4,14 +4,16 @@ import { Icon } from "./Icon";
 type Props = {
     readonly label: string;
     readonly onClick: () => void;
-    readonly icon: "next" | "pause" | "play";
+    readonly icon: "next" | "pause" | "play" | "undo" | "redo";
+    readonly disabled?: boolean;
 };
 
-export function Button({ label, onClick, icon }: Props): ReactElement {
+export function Button({ label, onClick, icon, disabled }: Props): ReactElement {
     return (
         <button
-            className="bg-indigo-500 text-white shadow my-2 rounded flex justify-center gap-1"
+            className="bg-indigo-500 text-white shadow my-2 rounded flex justify-center gap-1 disabled:opacity-50"
             onClick={onClick}
+            disabled={disabled}
         >
             <Icon icon={icon} />
             {label}
@@ -1,7 +1,7 @@
 import type { ReactElement } from "react";
 
 type Props = {
-    readonly icon: "next" | "pause" | "play";
+    readonly icon: "next" | "pause" | "play" | "undo" | "redo";
 };
 
 export function Next(): ReactElement {
@@ -49,6 +49,36 @@ export function Play(): ReactElement {
     );
 }
 
+export function Undo(): ReactElement {
+    return (
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox="0 0 512 512"
+            className="w-4"
+        >
+            <path
+                fill="white"
+                d="M212 96 52 236l160 140v-92c100 0 180 30 248 132-20-140-100-236-248-252V96z"
+            />
+        </svg>
+    );
+}
+
+export function Redo(): ReactElement {
+    return (
+        <svg
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox="0 0 512 512"
+            className="w-4"
+        >
+            <path
+                fill="white"
+                d="M300 96l160 140-160 140v-92c-100 0-180 30-248 132 20-140 100-236 248-252V96z"
+            />
+        </svg>
+    );
+}
+
 export function Icon({ icon }: Props): ReactElement {
     switch (icon) {
         case "next":
@@ -57,5 +87,9 @@ export function Icon({ icon }: Props): ReactElement {
             return <Pause />;
         case "play":
             return <Play />;
+        case "undo":
+            return <Undo />;
+        case "redo":
+            return <Redo />;
     }
 }
@@ -1,37 +1,44 @@
 import { useEffect, useState } from "react";
-import {
-    engineAddOnChangeListener,
-    engineGetSettings,
-    EngineInfo,
-    engineInit,
-    engineSetDimension,
-} from "game_of_life_engine";
+import { Engine, EngineInfo, EngineProp } from "game_of_life_engine";
 import { useWindowDimension } from "./useWindowDimension";
 
+type Edits = {
+    readonly canUndo: boolean;
+    readonly canRedo: boolean;
+};
+
 type GameOfLife = {
     readonly init: (canvasElement: HTMLCanvasElement) => void;
     readonly model: EngineInfo | undefined;
+    readonly edits: Edits;
+    readonly engine: Engine | undefined;
 };
 
 export function useGameOfLife(): GameOfLife {
-    const [hasInited, setInit] = useState(false);
+    const [engine, setEngine] = useState<Engine | undefined>(undefined);
     const [model, setModel] = useState<EngineInfo | undefined>(undefined);
+    const [edits, setEdits] = useState<Edits>({ canUndo: false, canRedo: false });
     const dimension = useWindowDimension();
 
     useEffect(() => {
-        if (hasInited && dimension > 0) {
-            engineSetDimension(dimension);
+        if (engine && dimension > 0) {
+            engine.setDimension(dimension);
         }
-    }, [dimension, hasInited]);
+    }, [dimension, engine]);
 
     function init(canvasElement: HTMLCanvasElement) {
         const context = canvasElement.getContext("2d");
         if (!context) {
             return;
         }
-        engineInit(context);
-        engineAddOnChangeListener(() => {
-            let obj = engineGetSettings();
+        const engine = new Engine();
+        engine.init(context);
+        engine.addOnChangeListener((prop: EngineProp) => {
+            if (prop === EngineProp.History) {
+                setEdits({ canUndo: engine.canUndo(), canRedo: engine.canRedo() });
+                return;
+            }
+            let obj = engine.getSettings();
             setModel({
                 size: obj.size,
                 fps: obj.fps,
@@ -41,8 +48,8 @@ export function useGameOfLife(): GameOfLife {
                 status: obj.status,
             } as any);
         });
-        setInit(true);
+        setEngine(engine);
     }
 
-    return { init, model };
+    return { init, model, edits, engine };
 }
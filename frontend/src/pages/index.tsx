@@ -4,18 +4,7 @@ import initWASM, {
     EngineCartesianPoint,
     engineGetPresets,
     EngineMatrixPoint,
-    engineMoveBy,
-    enginePause,
-    engineResume,
-    engineSetFPS,
-    engineSetGap,
-    engineSetPreset,
-    engineSingleIteration,
     EngineStatus,
-    engineToggle,
-    engineZoomIn,
-    engineZoomOut,
-    engineZoomTo,
 } from "game_of_life_engine";
 import { Button } from "../components/Button";
 import { RangeInput } from "../components/RangeInput";
@@ -24,7 +13,7 @@ import { useWindowDimension } from "../hooks/useWindowDimension";
 import { useGameOfLife } from "../hooks/useGameOfLife";
 
 export default function Main(): ReactElement {
-    const { init, model } = useGameOfLife();
+    const { init, model, edits, engine } = useGameOfLife();
     const initiated = useRef(false);
     const [presets, setPresets] = useState<any[]>([]);
     const canvasRef = useRef<HTMLCanvasElement>(null);
@@ -48,42 +37,42 @@ export default function Main(): ReactElement {
             switch (e.key) {
                 case "w":
                     try {
-                        engineMoveBy(new EngineCartesianPoint(BigInt(0), BigInt(1)));
+                        engine?.moveBy(new EngineCartesianPoint(BigInt(0), BigInt(1)));
                     } catch (e) {
                         console.error(e);
                     }
                     break;
                 case "a":
                     try {
-                        engineMoveBy(new EngineCartesianPoint(BigInt(-1), BigInt(0)));
+                        engine?.moveBy(new EngineCartesianPoint(BigInt(-1), BigInt(0)));
                     } catch (e) {
                         console.error(e);
                     }
                     break;
                 case "s":
                     try {
-                        engineMoveBy(new EngineCartesianPoint(BigInt(0), BigInt(-1)));
+                        engine?.moveBy(new EngineCartesianPoint(BigInt(0), BigInt(-1)));
                     } catch (e) {
                         console.error(e);
                     }
                     break;
                 case "d":
                     try {
-                        engineMoveBy(new EngineCartesianPoint(BigInt(1), BigInt(0)));
+                        engine?.moveBy(new EngineCartesianPoint(BigInt(1), BigInt(0)));
                     } catch (e) {
                         console.error(e);
                     }
                     break;
                 case "+":
                     try {
-                        engineZoomIn();
+                        engine?.zoomIn();
                     } catch (e) {
                         console.error(e);
                     }
                     break;
                 case "-":
                     try {
-                        engineZoomOut();
+                        engine?.zoomOut();
                     } catch (e) {
                         console.error(e);
                     }
@@ -103,7 +92,7 @@ export default function Main(): ReactElement {
         const row = e.pageX - e.currentTarget.offsetLeft;
         const col = e.pageY - e.currentTarget.offsetTop;
         try {
-            engineToggle(new EngineMatrixPoint(BigInt(Number(col)), BigInt(Number(row))));
+            engine?.toggle(new EngineMatrixPoint(BigInt(Number(col)), BigInt(Number(row))));
         } catch (e) {
             console.error(e);
         }
@@ -111,7 +100,7 @@ export default function Main(): ReactElement {
 
     function handleZoomTo(size: number) {
         try {
-            engineZoomTo(size);
+            engine?.zoomTo(size);
         } catch (e) {
             console.error(e);
         }
@@ -119,7 +108,7 @@ export default function Main(): ReactElement {
 
     function handleSetGap(gap: number) {
         try {
-            engineSetGap(gap);
+            engine?.setGap(gap);
         } catch (e) {
             console.error(e);
         }
@@ -127,7 +116,7 @@ export default function Main(): ReactElement {
 
     function handleSetFPS(fps: number) {
         try {
-            engineSetFPS(fps);
+            engine?.setFPS(fps);
         } catch (e) {
             console.error(e);
         }
@@ -135,7 +124,7 @@ export default function Main(): ReactElement {
 
     function handleSetPreset(preset: string) {
         try {
-            engineSetPreset(preset);
+            engine?.setPreset(preset);
         } catch (e) {
             console.error(e);
         }
@@ -146,14 +135,14 @@ export default function Main(): ReactElement {
         switch (model.status) {
             case EngineStatus.Resumed:
                 try {
-                    enginePause();
+                    engine?.pause();
                 } catch (e) {
                     console.error(e);
                 }
                 break;
             case EngineStatus.Paused:
                 try {
-                    engineResume();
+                    engine?.resume();
                 } catch (e) {
                     console.error(e);
                 }
@@ -163,7 +152,23 @@ export default function Main(): ReactElement {
 
     function handleIterate() {
         try {
-            engineSingleIteration();
+            engine?.singleIteration();
+        } catch (e) {
+            console.error(e);
+        }
+    }
+
+    function handleUndo() {
+        try {
+            engine?.undo();
+        } catch (e) {
+            console.error(e);
+        }
+    }
+
+    function handleRedo() {
+        try {
+            engine?.redo();
         } catch (e) {
             console.error(e);
         }
@@ -247,6 +252,8 @@ export default function Main(): ReactElement {
                     onClick={handleToggle}
                 />
                 <Button icon="next" label="ITERATE" onClick={handleIterate} />
+                <Button icon="undo" label="UNDO" onClick={handleUndo} disabled={!edits.canUndo} />
+                <Button icon="redo" label="REDO" onClick={handleRedo} disabled={!edits.canRedo} />
             </div>
         </main>
     );